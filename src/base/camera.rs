@@ -0,0 +1,361 @@
+use {
+    super::math::{Bounds, Transform2D, Vec2},
+    crate::util::{
+        event::{EventType, MouseEvent, RawWindowEvent, WindowEvent},
+        mouse::{MouseButton, MouseWheelDirection},
+    },
+};
+
+/**
+ * The smallest zoom a camera can have, which keeps its view invertible
+ */
+pub const MIN_ZOOM: f32 = 1e-6;
+
+/**
+ * The largest zoom a camera can have
+ */
+pub const MAX_ZOOM: f32 = 1e6;
+
+/**
+ * A 2D camera which maps world coordinates onto the screen, with `position` appearing at the center of the viewport
+ */
+#[derive(Clone, Debug)]
+pub struct Camera2D {
+    position: Vec2,
+    zoom: f32,
+    rotation: f32,
+    viewport: Vec2,
+    controls: bool,
+    pan_button: MouseButton,
+    zoom_step: f32,
+    cursor: Vec2,
+    dragging: bool,
+}
+
+impl Camera2D {
+    /**
+     * Initialize a new `Camera2D` looking at the origin of a viewport with the provided size in pixels
+     */
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            position: Vec2::ZERO,
+            zoom: 1.0,
+            rotation: 0.0,
+            viewport: Vec2::new(width as f32, height as f32),
+            controls: false,
+            pan_button: MouseButton::Left,
+            zoom_step: 1.1,
+            cursor: Vec2::ZERO,
+            dragging: false,
+        }
+    }
+
+    /**
+     * The world position at the center of the viewport
+     */
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn set_position<T: Into<Vec2>>(&mut self, position: T) {
+        self.position = position.into()
+    }
+
+    /**
+     * The number of pixels per world unit
+     */
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    /**
+     * Set the number of pixels per world unit, clamped between `MIN_ZOOM` and `MAX_ZOOM`, ignoring values which are not positive
+     */
+    pub fn set_zoom(&mut self, zoom: f32) {
+        if zoom > 0.0 && !zoom.is_nan() {
+            self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM)
+        }
+    }
+
+    /**
+     * The rotation of the world on the screen in radians
+     */
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /**
+     * Set the rotation of the world on the screen in radians, ignoring values which are not finite
+     */
+    pub fn set_rotation(&mut self, rotation: f32) {
+        if rotation.is_finite() {
+            self.rotation = rotation
+        }
+    }
+
+    /**
+     * The size of the viewport in pixels
+     */
+    pub fn viewport(&self) -> Vec2 {
+        self.viewport
+    }
+
+    pub fn set_viewport(&mut self, width: u32, height: u32) {
+        self.viewport = Vec2::new(width as f32, height as f32)
+    }
+
+    /**
+     * Move the camera by the provided world offset
+     */
+    pub fn pan<T: Into<Vec2>>(&mut self, offset: T) {
+        self.position += offset.into()
+    }
+
+    /**
+     * Multiply the zoom by `factor` while keeping the world point under the provided screen point fixed
+     */
+    pub fn zoom_at<T: Into<Vec2>>(&mut self, screen: T, factor: f32) {
+        let screen = screen.into();
+        let before = self.screen_to_world(screen);
+        self.set_zoom(self.zoom * factor);
+        self.position += before - self.screen_to_world(screen);
+    }
+
+    /**
+     * The transform mapping world coordinates onto the screen
+     */
+    pub fn view(&self) -> Transform2D {
+        Transform2D::translation(self.viewport.x / 2.0, self.viewport.y / 2.0)
+            * Transform2D::rotation(self.rotation)
+            * Transform2D::scaling(self.zoom, self.zoom)
+            * Transform2D::translation(-self.position.x, -self.position.y)
+    }
+
+    /**
+     * Convert a point in world coordinates into screen coordinates
+     */
+    pub fn world_to_screen<T: Into<Vec2>>(&self, point: T) -> Vec2 {
+        self.view().apply(point)
+    }
+
+    /**
+     * Convert a point in screen coordinates, such as those of a `MouseEvent`, into world coordinates
+     */
+    pub fn screen_to_world<T: Into<Vec2>>(&self, point: T) -> Vec2 {
+        //  The view is never degenerate since the zoom is clamped and the rotation is finite
+        self.view().inverse().unwrap().apply(point)
+    }
+
+    /**
+     * The world area currently visible through the viewport
+     */
+    pub fn visible(&self) -> Bounds {
+        Bounds::new(Vec2::ZERO, self.viewport).transform(&self.view().inverse().unwrap())
+    }
+
+    /**
+     * Return `true` if the built-in mouse controls are enabled
+     */
+    pub fn controls(&self) -> bool {
+        self.controls
+    }
+
+    /**
+     * Enable or disable mouse-wheel zooming towards the cursor and drag panning
+     */
+    pub fn set_controls(&mut self, enabled: bool) {
+        self.controls = enabled;
+        self.dragging = false
+    }
+
+    /**
+     * Set the mouse button which pans the camera while held
+     */
+    pub fn set_pan_button(&mut self, button: MouseButton) {
+        self.pan_button = button
+    }
+
+    /**
+     * Set the factor the zoom is multiplied by for each step of the mouse wheel
+     */
+    pub fn set_zoom_step(&mut self, step: f32) {
+        if step > 0.0 {
+            self.zoom_step = step
+        }
+    }
+
+    /**
+     * Update the camera from the provided event, returning `true` if the view changed
+     */
+    pub fn handle_event(&mut self, event: &EventType) -> bool {
+        match *event {
+            EventType::Window(WindowEvent::Window {
+                win_event: RawWindowEvent::SizeChanged(width, height),
+                ..
+            }) => {
+                self.set_viewport(width.max(0) as u32, height.max(0) as u32);
+                true
+            }
+            EventType::Mouse(ref mouse) if self.controls => match *mouse {
+                MouseEvent::MouseButtonDown {
+                    mouse_btn, x, y, ..
+                } if mouse_btn == self.pan_button => {
+                    self.cursor = Vec2::new(x as f32, y as f32);
+                    self.dragging = true;
+                    false
+                }
+                MouseEvent::MouseButtonUp { mouse_btn, .. } if mouse_btn == self.pan_button => {
+                    self.dragging = false;
+                    false
+                }
+                MouseEvent::MouseMotion { x, y, .. } => {
+                    let cursor = Vec2::new(x as f32, y as f32);
+                    let delta = cursor - self.cursor;
+                    self.cursor = cursor;
+
+                    if self.dragging {
+                        //  Dragging moves the world along with the cursor
+                        let delta = self.view().inverse().unwrap().apply_vector(delta);
+                        self.pan(-delta);
+                    }
+                    self.dragging
+                }
+                MouseEvent::MouseWheel { y, direction, .. } if y != 0 => {
+                    let steps = if let MouseWheelDirection::Flipped = direction {
+                        -y
+                    } else {
+                        y
+                    };
+                    self.zoom_at(self.cursor, self.zoom_step.powi(steps));
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::util::mouse::MouseState, std::f32::consts::FRAC_PI_2};
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        a.distance(b) < 1e-3
+    }
+
+    fn camera() -> Camera2D {
+        let mut camera = Camera2D::new(800, 600);
+        camera.set_position((10.0, -5.0));
+        camera.set_zoom(2.0);
+        camera.set_rotation(0.3);
+        camera
+    }
+
+    #[test]
+    fn round_trip() {
+        let camera = camera();
+        assert!(close(
+            camera.world_to_screen((10.0, -5.0)),
+            Vec2::new(400.0, 300.0)
+        ));
+
+        for p in [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(-37.5, 12.25),
+            Vec2::new(1e3, 1e3),
+        ] {
+            assert!(close(camera.screen_to_world(camera.world_to_screen(p)), p));
+        }
+        let mut turned = Camera2D::new(800, 600);
+        turned.set_rotation(FRAC_PI_2);
+        assert!(close(
+            turned.world_to_screen((1.0, 0.0)),
+            Vec2::new(400.0, 301.0)
+        ));
+    }
+
+    #[test]
+    fn zoom_at() {
+        let mut camera = camera();
+        let anchor = camera.screen_to_world((100.0, 50.0));
+        camera.zoom_at((100.0, 50.0), 3.0);
+
+        assert_eq!(camera.zoom(), 6.0);
+        assert!(close(camera.screen_to_world((100.0, 50.0)), anchor));
+    }
+
+    #[test]
+    fn limits() {
+        let mut camera = camera();
+        camera.set_zoom(0.0);
+        camera.set_zoom(f32::NAN);
+        camera.set_rotation(f32::INFINITY);
+        assert_eq!((camera.zoom(), camera.rotation()), (2.0, 0.3));
+
+        camera.set_zoom(1e-30);
+        assert_eq!(camera.zoom(), MIN_ZOOM);
+        for _ in 0..1000 {
+            camera.zoom_at((0.0, 0.0), 10.0);
+        }
+        assert_eq!(camera.zoom(), MAX_ZOOM);
+        assert!(camera.screen_to_world((0.0, 0.0)).x.is_finite());
+    }
+
+    #[test]
+    fn visible() {
+        let mut camera = Camera2D::new(800, 600);
+        camera.set_zoom(2.0);
+        let visible = camera.visible();
+
+        assert!(close(visible.min, Vec2::new(-200.0, -150.0)));
+        assert!(close(visible.max, Vec2::new(200.0, 150.0)));
+    }
+
+    #[test]
+    fn mouse_controls() {
+        let mut camera = Camera2D::new(800, 600);
+        let wheel = EventType::Mouse(MouseEvent::MouseWheel {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            x: 0,
+            y: 1,
+            direction: MouseWheelDirection::Normal,
+        });
+
+        //  The controls are off until enabled
+        assert!(!camera.handle_event(&wheel));
+        camera.set_controls(true);
+        assert!(camera.handle_event(&wheel));
+        assert_eq!(camera.zoom(), 1.1);
+
+        let motion = |x, y| {
+            EventType::Mouse(MouseEvent::MouseMotion {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                mousestate: MouseState::from_sdl_state(0),
+                x,
+                y,
+                xrel: 0,
+                yrel: 0,
+            })
+        };
+        camera.handle_event(&EventType::Mouse(MouseEvent::MouseButtonDown {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mouse_btn: MouseButton::Left,
+            clicks: 1,
+            x: 100,
+            y: 100,
+        }));
+        let grabbed = camera.screen_to_world((100.0, 100.0));
+
+        //  Dragging keeps the grabbed world point under the cursor
+        assert!(camera.handle_event(&motion(100, 100)));
+        assert!(camera.handle_event(&motion(150, 80)));
+        assert!(close(camera.screen_to_world((150.0, 80.0)), grabbed));
+    }
+}
@@ -0,0 +1,407 @@
+use {
+    super::{
+        math::{Bounds, Vec2},
+        polygon::{MultiPolygon, Polygon},
+    },
+    std::collections::{HashMap, HashSet},
+};
+
+/**
+ * A point of the contours, either a sample or the crossing of a level along the edge between two samples
+ *
+ * Neighbouring cells build the same key for the points they share, which is how their pieces are joined together
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Key {
+    Node(usize),
+    Cross(usize, usize, u8),
+}
+
+/**
+ * A vertex of a triangle being clipped, along with the edge of the triangle leading to the next vertex if it lies on one
+ */
+#[derive(Clone, Copy, Debug)]
+struct Vertex {
+    key: Key,
+    position: Vec2,
+    value: f32,
+    edge: Option<(usize, usize)>,
+}
+
+/**
+ * The values of a function `f(x, y)` sampled on a regular grid over a region, from which contours are traced with marching squares
+ *
+ * The center of every cell is sampled too, and each cell is split into four triangles around it. This settles the saddles which marching squares alone leaves ambiguous
+ */
+#[derive(Clone, Debug)]
+pub struct ScalarField {
+    bounds: Bounds,
+    resolution: (usize, usize),
+    values: Vec<f32>,
+}
+
+impl ScalarField {
+    /**
+     * Sample `f` over the region with the provided number of cells along each axis
+     */
+    pub fn sample<F: Fn(f32, f32) -> f32>(f: F, bounds: Bounds, (nx, ny): (usize, usize)) -> Self {
+        let (nx, ny) = (nx.max(1), ny.max(1));
+        let mut field = Self {
+            bounds,
+            resolution: (nx, ny),
+            values: Vec::new(),
+        };
+        field.values = (0..field.nodes())
+            .map(|n| {
+                let p = field.position(n);
+                f(p.x, p.y)
+            })
+            .collect();
+        field
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /**
+     * The number of cells along each axis
+     */
+    pub fn resolution(&self) -> (usize, usize) {
+        self.resolution
+    }
+
+    /**
+     * The number of samples, counting the corners of the cells and then their centers
+     */
+    fn nodes(&self) -> usize {
+        let (nx, ny) = self.resolution;
+        (nx + 1) * (ny + 1) + nx * ny
+    }
+
+    fn corner(&self, i: usize, j: usize) -> usize {
+        j * (self.resolution.0 + 1) + i
+    }
+
+    fn center(&self, i: usize, j: usize) -> usize {
+        let (nx, ny) = self.resolution;
+        (nx + 1) * (ny + 1) + j * nx + i
+    }
+
+    fn position(&self, n: usize) -> Vec2 {
+        let (nx, ny) = self.resolution;
+        let size = Vec2::new(
+            self.bounds.width() / nx as f32,
+            self.bounds.height() / ny as f32,
+        );
+        let corners = (nx + 1) * (ny + 1);
+
+        let (x, y) = if n < corners {
+            ((n % (nx + 1)) as f32, (n / (nx + 1)) as f32)
+        } else {
+            let n = n - corners;
+            ((n % nx) as f32 + 0.5, (n / nx) as f32 + 0.5)
+        };
+        self.bounds.min + Vec2::new(x * size.x, y * size.y)
+    }
+
+    /**
+     * The four triangles of every cell, each winding counter-clockwise in a y-up space
+     */
+    fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        let (nx, ny) = self.resolution;
+
+        (0..ny).flat_map(move |j| {
+            (0..nx).flat_map(move |i| {
+                let c = self.center(i, j);
+                let corners = [
+                    self.corner(i, j),
+                    self.corner(i + 1, j),
+                    self.corner(i + 1, j + 1),
+                    self.corner(i, j + 1),
+                ];
+                (0..4).map(move |k| [corners[k], corners[(k + 1) % 4], c])
+            })
+        })
+    }
+
+    /**
+     * The point where `level` is crossed along the edge between two samples
+     */
+    fn cross(&self, a: usize, b: usize, level: f32, index: u8) -> Vertex {
+        //  Interpolate in a fixed order so that both cells sharing the edge agree on the position exactly
+        let (a, b) = (a.min(b), a.max(b));
+        let (va, vb) = (self.values[a], self.values[b]);
+        let t = if vb != va {
+            ((level - va) / (vb - va)).clamp(0.0, 1.0)
+        } else {
+            0.5
+        };
+        let (pa, pb) = (self.position(a), self.position(b));
+
+        Vertex {
+            key: Key::Cross(a, b, index),
+            position: pa + (pb - pa) * t,
+            value: level,
+            edge: None,
+        }
+    }
+
+    /**
+     * Trace the lines along which the field equals `level`
+     *
+     * Lines which close on themselves repeat their first point at the end, and the rest run between the borders of the region
+     */
+    pub fn isolines(&self, level: f32) -> Vec<Vec<Vec2>> {
+        let mut positions = HashMap::new();
+        let mut neighbours: HashMap<Key, Vec<Key>> = HashMap::new();
+
+        for triangle in self.triangles() {
+            let values = triangle.map(|n| self.values[n]);
+            if values.iter().any(|v| !v.is_finite()) {
+                continue;
+            }
+
+            let crossings = (0..3)
+                .filter(|&k| (values[k] >= level) != (values[(k + 1) % 3] >= level))
+                .map(|k| self.cross(triangle[k], triangle[(k + 1) % 3], level, 0))
+                .collect::<Vec<Vertex>>();
+
+            if let [a, b] = crossings[..] {
+                positions.insert(a.key, a.position);
+                positions.insert(b.key, b.position);
+                neighbours.entry(a.key).or_default().push(b.key);
+                neighbours.entry(b.key).or_default().push(a.key);
+            }
+        }
+
+        //  Open lines are walked from their ends first, and whatever remains forms loops
+        let mut visited = HashSet::new();
+        let mut starts = neighbours
+            .iter()
+            .filter(|(_, next)| next.len() == 1)
+            .map(|(key, _)| *key)
+            .collect::<Vec<Key>>();
+        starts.extend(neighbours.keys().copied());
+
+        let mut lines = Vec::new();
+        for start in starts {
+            if visited.contains(&start) {
+                continue;
+            }
+            let mut line = vec![start];
+            visited.insert(start);
+            let mut current = start;
+
+            while let Some(&next) = neighbours[&current].iter().find(|k| !visited.contains(*k)) {
+                visited.insert(next);
+                line.push(next);
+                current = next;
+            }
+            if line.len() > 2 && neighbours[&current].contains(&start) {
+                line.push(start)
+            }
+            if line.len() > 1 {
+                lines.push(line.into_iter().map(|key| positions[&key]).collect());
+            }
+        }
+        lines
+    }
+
+    /**
+     * Trace the region where the field lies within `[lower, upper)` as contours, which wind counter-clockwise in a y-up space around filled areas and clockwise around holes
+     */
+    pub fn isoband(&self, lower: f32, upper: f32) -> MultiPolygon {
+        let mut positions = HashMap::new();
+        let mut edges: HashSet<(Key, Key)> = HashSet::new();
+
+        for triangle in self.triangles() {
+            if triangle.iter().any(|&n| !self.values[n].is_finite()) {
+                continue;
+            }
+            let polygon = triangle
+                .iter()
+                .enumerate()
+                .map(|(k, &n)| Vertex {
+                    key: Key::Node(n),
+                    position: self.position(n),
+                    value: self.values[n],
+                    edge: Some((n, triangle[(k + 1) % 3])),
+                })
+                .collect::<Vec<Vertex>>();
+
+            let polygon = self.clip(polygon, lower, 0, |v| v >= lower);
+            let polygon = self.clip(polygon, upper, 1, |v| v < upper);
+
+            //  Edges shared by neighbouring pieces run in opposite directions and cancel out, leaving only the border of the band
+            for (k, a) in polygon.iter().enumerate() {
+                let b = polygon[(k + 1) % polygon.len()];
+                positions.insert(a.key, a.position);
+
+                if a.key != b.key && !edges.remove(&(b.key, a.key)) {
+                    edges.insert((a.key, b.key));
+                }
+            }
+        }
+
+        let mut next: HashMap<Key, Vec<Key>> = HashMap::new();
+        for (a, b) in edges {
+            next.entry(a).or_default().push(b)
+        }
+
+        let mut polygons = Vec::new();
+        while let Some(&start) = next.keys().next() {
+            let mut ring = vec![positions[&start]];
+            let mut current = start;
+
+            while let Some(to) = next.get_mut(&current).and_then(Vec::pop) {
+                if next[&current].is_empty() {
+                    next.remove(&current);
+                }
+                if to == start {
+                    break;
+                }
+                ring.push(positions[&to]);
+                current = to;
+            }
+
+            if let Ok(polygon) = Polygon::new(simplify(ring)) {
+                polygons.push(polygon)
+            }
+        }
+        MultiPolygon::new(polygons)
+    }
+
+    /**
+     * Clip a convex piece of a triangle to where `inside` holds for the field, using the Sutherland-Hodgman algorithm on the sampled values
+     */
+    fn clip<F: Fn(f32) -> bool>(
+        &self,
+        polygon: Vec<Vertex>,
+        level: f32,
+        index: u8,
+        inside: F,
+    ) -> Vec<Vertex> {
+        let mut output = Vec::new();
+
+        for (k, &current) in polygon.iter().enumerate() {
+            let next = polygon[(k + 1) % polygon.len()];
+
+            if inside(current.value) {
+                output.push(current)
+            }
+            if inside(current.value) != inside(next.value) {
+                //  Only the edges of the triangle can be crossed, since cuts across it lie along a single level
+                let Some((a, b)) = current.edge else {
+                    continue;
+                };
+                let mut crossing = self.cross(a, b, level, index);
+
+                //  Entering continues along the same edge, while leaving cuts across the triangle
+                if !inside(current.value) {
+                    crossing.edge = current.edge
+                }
+                output.push(crossing)
+            }
+        }
+        output
+    }
+}
+
+/**
+ * Remove the points which lie on a straight line between their neighbours, such as those left along the border of the region
+ */
+fn simplify(mut ring: Vec<Vec2>) -> Vec<Vec2> {
+    let mut k = 0;
+
+    while ring.len() > 3 && k < ring.len() {
+        let n = ring.len();
+        let (prev, p, next) = (ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]);
+        let (u, v) = (p - prev, next - p);
+
+        if u.cross(v).abs() <= 1e-6 * u.length() * v.length() && u.dot(v) >= 0.0 {
+            ring.remove(k);
+            k = k.saturating_sub(1);
+        } else {
+            k += 1
+        }
+    }
+    ring
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::f32::consts::PI};
+
+    fn field<F: Fn(f32, f32) -> f32>(f: F) -> ScalarField {
+        ScalarField::sample(f, Bounds::new((-10.0, -10.0), (10.0, 10.0)), (40, 40))
+    }
+
+    #[test]
+    fn sampling() {
+        let field = ScalarField::sample(|x, y| x + y, Bounds::new((0.0, 0.0), (4.0, 2.0)), (0, 2));
+        assert_eq!(field.resolution(), (1, 2));
+        assert_eq!(field.nodes(), 6 + 2);
+        assert_eq!(field.position(field.corner(1, 2)), Vec2::new(4.0, 2.0));
+        assert_eq!(field.position(field.center(0, 1)), Vec2::new(2.0, 1.5));
+        assert_eq!(field.values[field.center(0, 0)], 2.5);
+    }
+
+    #[test]
+    fn closed_isolines() {
+        let lines = field(|x, y| x * x + y * y).isolines(25.0);
+        assert_eq!(lines.len(), 1);
+
+        let line = &lines[0];
+        assert_eq!(line.first(), line.last());
+        assert!(line.iter().all(|p| (p.length() - 5.0).abs() < 0.1));
+
+        //  Separate hills give separate loops
+        let hills = field(|x, y| ((x - 5.0).powi(2) + y * y).min((x + 5.0).powi(2) + y * y));
+        assert_eq!(hills.isolines(4.0).len(), 2);
+    }
+
+    #[test]
+    fn open_isolines() {
+        //  Lines which leave the region run between its borders
+        let lines = field(|x, y| x - y / 2.0).isolines(1.0);
+        assert_eq!(lines.len(), 1);
+
+        let line = &lines[0];
+        assert!(line.iter().all(|p| (p.x - p.y / 2.0 - 1.0).abs() < 1e-4));
+        let ends = [line[0].y, line.last().unwrap().y];
+        assert!(ends.contains(&-10.0) && ends.contains(&10.0));
+
+        //  Samples which are not finite leave gaps instead of lines
+        let holes = field(|x, y| if x.abs() < 1.0 { f32::NAN } else { y });
+        assert_eq!(holes.isolines(0.5).len(), 2);
+        assert!(field(|_, _| 1.0).isolines(1.0).is_empty());
+    }
+
+    #[test]
+    fn isobands() {
+        let bowl = field(|x, y| x * x + y * y);
+
+        let disc = bowl.isoband(f32::NEG_INFINITY, 25.0);
+        assert_eq!(disc.len(), 1);
+        assert!((disc.area() - 25.0 * PI).abs() < 0.5);
+
+        //  A band between two levels is a ring, whose hole winds the other way
+        let ring = bowl.isoband(4.0, 25.0);
+        assert_eq!(ring.len(), 2);
+        assert!((ring.area() - 21.0 * PI).abs() < 0.5);
+        let mut windings = ring
+            .polygons()
+            .iter()
+            .map(|p| p.signed_area().signum())
+            .collect::<Vec<f32>>();
+        windings.sort_by(f32::total_cmp);
+        assert_eq!(windings, [-1.0, 1.0]);
+
+        //  Bands covering the whole region are its border, with the points along it removed
+        let whole = bowl.isoband(0.0, 1000.0);
+        assert_eq!(whole.len(), 1);
+        assert_eq!(whole.polygons()[0].len(), 4);
+        assert_eq!(whole.area(), 400.0);
+    }
+}
@@ -1,22 +1,63 @@
+mod antialias;
+mod clip;
+mod plot;
+mod primitives;
+mod text;
+
 use {
     super::{
+        camera::Camera2D,
+        contour::ScalarField,
         error::{Error, Result},
+        mask::AlphaMask,
+        math::{
+            clip_polyline, liang_barsky, Bounds, Function, FunctionType, Interval, Plottable,
+            Transform2D, Variable, Vec2,
+        },
+        path::{Path, Subpath},
+        plot::Plot,
         polygon::*,
+        stroke::{StrokeStyle, TOLERANCE},
     },
     crate::util::event::EventType,
     sdl2::{
         init,
         pixels::Color,
+        rect::Rect,
         render::WindowCanvas,
         video::FullscreenType::{Desktop, Off},
         EventPump, Sdl,
     },
 };
 
+/**
+ * The most samples taken when plotting a function over a range
+ */
+const MAX_PLOT_SAMPLES: usize = 1 << 20;
+
+/**
+ * The drawing state which is pushed by `Engine::save` and popped by `Engine::restore`
+ */
+#[derive(Clone, Debug)]
+struct State {
+    transform: Transform2D,
+    color: Color,
+    stroke: StrokeStyle,
+    clip: Option<Rect>,
+    clips: usize,
+}
+
 pub struct Engine {
     canvas: WindowCanvas,
     running: bool,
     ctx: Sdl,
+    transform: Transform2D,
+    stroke: StrokeStyle,
+    stack: Vec<State>,
+    camera: Option<Camera2D>,
+    plot: Option<Plot>,
+    antialiasing: bool,
+    clips: Vec<AlphaMask>,
 }
 
 impl Engine {
@@ -41,6 +82,13 @@ impl Engine {
             canvas,
             running,
             ctx,
+            transform: Transform2D::IDENTITY,
+            stroke: StrokeStyle::default(),
+            stack: Vec::new(),
+            camera: None,
+            plot: None,
+            antialiasing: false,
+            clips: Vec::new(),
         };
         engine.clear();
         engine.flush();
@@ -69,53 +117,643 @@ impl Engine {
     }
 
     /**
-     * Draw a singular line from one `Point` to another
+     * Return the current draw color
+     */
+    pub fn draw_color(&self) -> Color {
+        self.canvas.draw_color()
+    }
+
+    /**
+     * Set the color used by subsequent drawing
+     */
+    pub fn set_draw_color(&mut self, color: Color) {
+        self.canvas.set_draw_color(color)
+    }
+
+    /**
+     * Return `true` if lines and filled polygons are drawn with anti-aliasing
+     */
+    pub fn antialiasing(&self) -> bool {
+        self.antialiasing
+    }
+
+    /**
+     * Enable or disable anti-aliasing, which blends the edges of lines and filled polygons onto the canvas with alpha
+     */
+    pub fn set_antialiasing(&mut self, enabled: bool) {
+        self.antialiasing = enabled
+    }
+
+    /**
+     * Return the style used to outline lines and polygons
+     */
+    pub fn stroke_style(&self) -> &StrokeStyle {
+        &self.stroke
+    }
+
+    /**
+     * Set the style used to outline subsequent lines and polygons
+     */
+    pub fn set_stroke_style(&mut self, stroke: StrokeStyle) {
+        self.stroke = stroke
+    }
+
+    /**
+     * Return the transform currently applied to everything drawn
+     */
+    pub fn current_transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    /**
+     * Replace the current transform
+     */
+    pub fn set_transform(&mut self, transform: Transform2D) {
+        self.transform = transform
+    }
+
+    /**
+     * Reset the current transform to the identity
+     */
+    pub fn reset_transform(&mut self) {
+        self.transform = Transform2D::IDENTITY
+    }
+
+    /**
+     * Apply the provided transform to subsequent drawing, in the local coordinates of the current transform
+     */
+    pub fn transform(&mut self, transform: &Transform2D) {
+        self.transform *= *transform
+    }
+
+    /**
+     * Move the origin of subsequent drawing
+     */
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.transform(&Transform2D::translation(x, y))
+    }
+
+    /**
+     * Rotate subsequent drawing about the current origin by `angle` radians
+     */
+    pub fn rotate(&mut self, angle: f32) {
+        self.transform(&Transform2D::rotation(angle))
+    }
+
+    /**
+     * Scale subsequent drawing away from the current origin
+     */
+    pub fn scale(&mut self, x: f32, y: f32) {
+        self.transform(&Transform2D::scaling(x, y))
+    }
+
+    /**
+     * Return a reference of the camera, if one is attached
+     */
+    pub fn camera(&self) -> Option<&Camera2D> {
+        self.camera.as_ref()
+    }
+
+    /**
+     * Return a mutable reference of the camera, if one is attached
+     */
+    pub fn camera_mut(&mut self) -> Option<&mut Camera2D> {
+        self.camera.as_mut()
+    }
+
+    /**
+     * Attach a camera, making all subsequent drawing happen in world coordinates, or detach it with `None`
+     */
+    pub fn set_camera(&mut self, camera: Option<Camera2D>) -> Result<()> {
+        self.camera = camera;
+
+        if let Some(camera) = self.camera.as_mut() {
+            let (width, height) = self.canvas.output_size()?;
+            camera.set_viewport(width, height)
+        }
+        Ok(())
+    }
+
+    /**
+     * Return a reference of the plot, if one is attached
+     */
+    pub fn plot(&self) -> Option<&Plot> {
+        self.plot.as_ref()
+    }
+
+    /**
+     * Return a mutable reference of the plot, if one is attached
      */
-    pub fn draw_line<T: Into<Point>>(&mut self, start: T, end: T) -> Result<()> {
-        self.canvas.draw_line(start, end).map_err(Into::into)
+    pub fn plot_mut(&mut self) -> Option<&mut Plot> {
+        self.plot.as_mut()
     }
 
     /**
-     * Draw a singular line from one `Point` to another
+     * Attach a plot, which is drawn by `render_plot` and pans and zooms with the mouse once its controls are enabled, or detach it with `None`
      */
-    pub fn draw_lines<A: Into<Point>, T: IntoIterator<Item = A>>(&mut self, iter: T) -> Result<()> {
-        self.canvas
-            .draw_lines(
-                iter.into_iter()
-                    .map(Into::into)
-                    .collect::<Vec<Point>>()
-                    .as_slice(),
-            )
-            .map_err(Into::into)
+    pub fn set_plot(&mut self, plot: Option<Plot>) {
+        self.plot = plot
     }
 
     /**
-     * Draw a potential `Polygon` onto the current viewport
+     * The transform from drawing coordinates to the screen, combining the camera with the current transform
      */
-    pub fn draw_polygon(&mut self, polygon: &Polygon) -> Result<()> {
-        self.draw_lines(polygon)
+    fn device_transform(&self) -> Transform2D {
+        match &self.camera {
+            Some(camera) => camera.view() * self.transform,
+            None => self.transform,
+        }
+    }
+
+    /**
+     * Return `false` if the provided bounds in drawing coordinates are guaranteed to be off-screen
+     */
+    fn visible(&self, bounds: &Bounds) -> bool {
+        let (width, height) = self.canvas.output_size().unwrap_or((u32::MAX, u32::MAX));
+        let screen = Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32));
+        bounds
+            .transform(&self.device_transform())
+            .intersects(&screen)
+    }
+
+    /**
+     * The area of the screen which can be drawn to in device coordinates, grown by a pixel so that clipped lines still reach its edges
+     */
+    fn device_clip(&self) -> Option<Bounds> {
+        let (width, height) = self.canvas.output_size().unwrap_or((u32::MAX, u32::MAX));
+        let screen = Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32));
+
+        let clip = match self.canvas.clip_rect() {
+            Some(clip) => screen.intersection(&Bounds::new(
+                Vec2::new(clip.x() as f32, clip.y() as f32),
+                Vec2::new(clip.right() as f32, clip.bottom() as f32),
+            ))?,
+            None => screen,
+        };
+        match self.clips.last() {
+            Some(mask) => clip.intersection(&mask.bounds()),
+            None => Some(clip),
+        }
+        .map(|bounds| bounds.expand(1.0))
+    }
+
+    /**
+     * Push the current transform, draw color, stroke style and clip region onto the state stack
+     *
+     * Restoring the state also pops any clip shapes and masks pushed since it was saved
+     */
+    pub fn save(&mut self) {
+        self.stack.push(State {
+            transform: self.transform,
+            color: self.canvas.draw_color(),
+            stroke: self.stroke.clone(),
+            clip: self.canvas.clip_rect(),
+            clips: self.clips.len(),
+        })
+    }
+
+    /**
+     * Pop the most recently saved state off of the stack and make it current, doing nothing if the stack is empty
+     */
+    pub fn restore(&mut self) {
+        if let Some(state) = self.stack.pop() {
+            self.transform = state.transform;
+            self.canvas.set_draw_color(state.color);
+            self.stroke = state.stroke;
+            self.canvas.set_clip_rect(state.clip);
+            self.clips.truncate(state.clips);
+        }
+    }
+
+    /**
+     * Draw a singular line from one `Point` to another in the current stroke style
+     */
+    pub fn draw_line<T: Into<Vec2>>(&mut self, start: T, end: T) -> Result<()> {
+        if !self.stroke.is_hairline() {
+            return self.stroke(vec![start.into(), end.into()], false);
+        }
+        let transform = self.device_transform();
+        let (start, end) = (transform.apply(start), transform.apply(end));
+
+        //  Lines are clipped before they are rasterized, so that nothing is spent on what cannot be seen
+        let Some((start, end)) = self
+            .device_clip()
+            .and_then(|clip| liang_barsky(start, end, &clip))
+        else {
+            return Ok(());
+        };
+
+        if self.antialiasing {
+            let color = self.canvas.draw_color();
+            return self.blended(|engine| engine.wu_line(start, end, color));
+        }
+        self.raw_line(start.into(), end.into())
+    }
+
+    /**
+     * Draw a series of connected lines through each `Point` in the current stroke style
+     */
+    pub fn draw_lines<A: Into<Vec2>, T: IntoIterator<Item = A>>(&mut self, iter: T) -> Result<()> {
+        if !self.stroke.is_hairline() {
+            return self.stroke(iter.into_iter().map(Into::into).collect(), false);
+        }
+        let transform = self.device_transform();
+        let points = iter
+            .into_iter()
+            .map(|p| transform.apply(p))
+            .collect::<Vec<Vec2>>();
+        let runs = match self.device_clip() {
+            Some(clip) => clip_polyline(&points, &clip),
+            None => return Ok(()),
+        };
+
+        if self.antialiasing {
+            let color = self.canvas.draw_color();
+            return self.blended(|engine| {
+                runs.iter()
+                    .flat_map(|run| run.windows(2))
+                    .try_for_each(|pair| engine.wu_line(pair[0], pair[1], color))
+            });
+        }
+        runs.into_iter().try_for_each(|run| {
+            let points = run.into_iter().map(Point::from).collect::<Vec<Point>>();
+
+            if !self.clips.is_empty() {
+                return match points[..] {
+                    [p] => self.raw_line(p, p),
+                    _ => points
+                        .windows(2)
+                        .try_for_each(|pair| self.raw_line(pair[0], pair[1])),
+                };
+            }
+            self.canvas
+                .draw_lines(points.as_slice())
+                .map_err(Into::into)
+        })
+    }
+
+    /**
+     * Fill the line through the provided points in the current stroke style with the current draw color
+     *
+     * The overlapping pieces of the line are filled together with the non-zero rule rather than merged into an outline first
+     */
+    fn stroke(&mut self, points: Vec<Vec2>, closed: bool) -> Result<()> {
+        //  Dashes are only built within the drawable area, grown by the furthest a join or cap can reach past the line
+        let reach = self.stroke.width * self.stroke.miter_limit.max(1.0);
+        let visible = self
+            .device_clip()
+            .zip(self.device_transform().inverse())
+            .map(|(clip, inverse)| clip.transform(&inverse).expand(reach));
+
+        let pieces = self.stroke.pieces_within(points, closed, visible.as_ref());
+        self.fill_contours(&pieces, self.canvas.draw_color(), 1, FillRule::NonZero)
+    }
+
+    /**
+     * Draw a line directly onto the canvas, bypassing the current transform
+     */
+    fn raw_line(&mut self, start: Point, end: Point) -> Result<()> {
+        if self.clips.is_empty() {
+            self.canvas.draw_line(start, end).map_err(Into::into)
+        } else {
+            self.masked_line(start, end)
+        }
+    }
+
+    /**
+     * Draw the outline of a potential `Polygon`, or of every contour of a `MultiPolygon`, onto the current viewport in the current stroke style
+     */
+    pub fn draw_polygon<C: Contours + ?Sized>(&mut self, polygon: &C) -> Result<()> {
+        match polygon.bounds() {
+            Some(bounds) if self.visible(&bounds.expand(self.stroke.width)) => {
+                polygon.contours().iter().try_for_each(|contour| {
+                    if self.stroke.is_hairline() {
+                        self.draw_lines(<&[Vec2]>::from(contour).iter().copied())
+                    } else {
+                        self.stroke(contour.vertices().to_vec(), true)
+                    }
+                })
+            }
+            _ => Ok(()),
+        }
     }
 
     /**
      * Fill the area of the location of the provided polygon using the scan line method
+     *
+     * The crossings of every contour are combined on each line, so the contours of a `MultiPolygon` which lie inside of others are left as holes. With anti-aliasing enabled every row is sampled several times and `density` is ignored
+     */
+    pub fn fill_polygon<C: Contours + ?Sized>(
+        &mut self,
+        polygon: &C,
+        color: Color,
+        density: usize,
+    ) -> Result<()> {
+        self.fill_contours(polygon, color, density, FillRule::EvenOdd)
+    }
+
+    /**
+     * Draw every subpath of a `Path` in the current stroke style, joining the ends of the closed ones
      */
-    pub fn fill_polygon(&mut self, polygon: &Polygon, color: Color, density: usize) -> Result<()> {
-        let min = polygon.y_min();
-        let max = polygon.y_max();
+    pub fn stroke_path(&mut self, path: &Path) -> Result<()> {
+        let tolerance = self.tolerance();
+
+        path.flatten(tolerance)
+            .into_iter()
+            .try_for_each(|Subpath { mut points, closed }| {
+                if !self.stroke.is_hairline() {
+                    return self.stroke(points, closed);
+                }
+                if closed {
+                    points.push(points[0])
+                }
+                self.draw_lines(points)
+            })
+    }
+
+    /**
+     * Fill the area of a `Path` according to the provided `FillRule`, treating every subpath as closed
+     */
+    pub fn fill_path(&mut self, path: &Path, color: Color, rule: FillRule) -> Result<()> {
+        let contours = path.to_multi_polygon(self.tolerance());
+        self.fill_contours(&contours, color, 1, rule)
+    }
+
+    /**
+     * Plot a `Function` of `x` over the provided range, such as `(start, end)` or an `Interval`, sampling it every `step` units and connecting the samples with lines in the current stroke style
+     *
+     * The plot is broken wherever the function is undefined, and is limited to the interval of the function and to the part of the range which can reach the screen, so unbounded ranges are safe. The step grows if the range would need more than about a million samples
+     */
+    pub fn plot_function<R: Into<Interval<f32>>>(
+        &mut self,
+        function: &Function,
+        range: R,
+        step: f32,
+    ) -> Result<()> {
+        if step <= 0.0 || !step.is_finite() {
+            return Err("Step must be positive".into());
+        }
+        let visible = self
+            .device_clip()
+            .zip(self.device_transform().inverse())
+            .map(|(clip, inverse)| clip.transform(&inverse));
+        let Some(range) = visible.and_then(|visible| {
+            range
+                .into()
+                .intersection(&function.interval().x)?
+                .intersection(&Interval::closed(visible.min.x, visible.max.x))
+        }) else {
+            return Ok(());
+        };
+        let (start, end) = (range.min(), range.max());
+
+        //  Vertical lines are not functions of `x`, but still describe a polygon edge worth plotting
+        if let FunctionType::Constant {
+            var: Variable::X,
+            val,
+        } = function.function()
+        {
+            let y = function.interval().y;
+            return if range.contains(*val as f32) {
+                self.draw_line((*val as f32, y.min()), (*val as f32, y.max()))
+            } else {
+                Ok(())
+            };
+        }
+
+        let step = step.max((end - start) / MAX_PLOT_SAMPLES as f32);
+        let steps = ((end - start) / step).ceil().max(0.0) as usize;
+        let mut run: Vec<Vec2> = Vec::new();
+
+        for k in 0..=steps {
+            let x = (start + step * k as f32).min(end);
+
+            match function.eval(x) {
+                Some(y) => run.push(Vec2::new(x, y)),
+                None => self.plot_run(&mut run)?,
+            }
+        }
+        self.plot_run(&mut run)
+    }
+
+    /**
+     * Draw a `Plottable` curve such as a `Parametric` or `Polar` curve in the current stroke style, sampled finely enough to stay within a quarter of a pixel of it
+     */
+    pub fn plot_curve<P: Plottable + ?Sized>(&mut self, curve: &P) -> Result<()> {
+        let tolerance = self.tolerance();
+
+        curve
+            .sample(tolerance)
+            .into_iter()
+            .try_for_each(|mut run| self.plot_run(&mut run))
+    }
+
+    /**
+     * Draw the lines along which a sampled field equals `level` in the current stroke style
+     */
+    pub fn draw_isolines(&mut self, field: &ScalarField, level: f32) -> Result<()> {
+        if !self.visible(&field.bounds()) {
+            return Ok(());
+        }
+        field
+            .isolines(level)
+            .into_iter()
+            .try_for_each(|line| self.draw_lines(line))
+    }
+
+    /**
+     * Fill the region where a sampled field lies within `[lower, upper)`
+     */
+    pub fn fill_isoband(
+        &mut self,
+        field: &ScalarField,
+        lower: f32,
+        upper: f32,
+        color: Color,
+    ) -> Result<()> {
+        if !self.visible(&field.bounds()) {
+            return Ok(());
+        }
+        self.fill_polygon(&field.isoband(lower, upper), color, 1)
+    }
+
+    /**
+     * Draw and empty a run of connected samples, leaving a single sample as a point
+     */
+    fn plot_run(&mut self, run: &mut Vec<Vec2>) -> Result<()> {
+        match run.len() {
+            0 => Ok(()),
+            1 => self.draw_line(run[0], run[0]),
+            _ => self.draw_lines(run.iter().copied()),
+        }?;
+        run.clear();
+        Ok(())
+    }
+
+    /**
+     * The largest distance in the current coordinates between a curve and the straight segments drawn for it, which stays within a quarter of a pixel on the screen
+     */
+    fn tolerance(&self) -> f32 {
+        let scale = self.device_transform().determinant().abs().sqrt();
+
+        if scale > 0.0 {
+            TOLERANCE / scale
+        } else {
+            TOLERANCE
+        }
+    }
+
+    /**
+     * Fill contours using the scan line method according to the provided `FillRule`
+     */
+    fn fill_contours<C: Contours + ?Sized>(
+        &mut self,
+        polygon: &C,
+        color: Color,
+        density: usize,
+        rule: FillRule,
+    ) -> Result<()> {
+        match polygon.bounds() {
+            Some(bounds) if self.visible(&bounds) => (),
+            _ => return Ok(()),
+        }
+        let transform = self.device_transform();
+        let contours = MultiPolygon::new(
+            polygon
+                .contours()
+                .iter()
+                .map(|contour| contour.transform(&transform)),
+        );
+        if self.antialiasing {
+            return self.blended(|engine| engine.antialiased_fill(&contours, rule, color));
+        }
+        let Some(bounds) = contours
+            .bounds()
+            .zip(self.device_clip())
+            .and_then(|(bounds, clip)| bounds.intersection(&clip))
+        else {
+            return Ok(());
+        };
 
         let prev = self.canvas.draw_color();
         self.canvas.set_draw_color(color);
-        (min..=max)
-            .step_by(density)
-            .map(|n| {
-                polygon
-                    .intersections_at_y(n)
-                    .chunks_exact(2)
-                    .map(|chunk| self.draw_line(chunk[0], chunk[1]))
-                    .collect::<Result<()>>()
-            })
-            .collect::<Result<()>>()?;
-        Ok(self.canvas.set_draw_color(prev))
+        ((bounds.min.y.floor() as i32)..=(bounds.max.y.ceil() as i32))
+            .step_by(density.max(1))
+            .try_for_each(|y| {
+                contours
+                    .spans_at_y(y as f32, rule)
+                    .into_iter()
+                    .try_for_each(|(start, end)| {
+                        self.raw_span(y, (start.max(bounds.min.x), end.min(bounds.max.x)))
+                    })
+            })?;
+        self.canvas.set_draw_color(prev);
+        Ok(())
+    }
+
+    /**
+     * Draw the pixels of row `y` from the start of the span up to, but excluding, its end
+     */
+    fn raw_span(&mut self, y: i32, (start, end): (f32, f32)) -> Result<()> {
+        let (start, end) = (start.ceil() as i32, end.ceil() as i32 - 1);
+
+        if start <= end {
+            self.raw_line(Point::new(start, y), Point::new(end, y))
+        } else {
+            Ok(())
+        }
+    }
+
+    /**
+     * Fill the area of a single triangle
+     *
+     * With anti-aliasing enabled the triangle is filled as a polygon, so its edges are smoothed
+     */
+    pub fn fill_triangle<T: Into<Vec2>>(&mut self, a: T, b: T, c: T, color: Color) -> Result<()> {
+        let triangle = [a.into(), b.into(), c.into()];
+
+        if self.antialiasing {
+            return match Polygon::new(triangle) {
+                Ok(polygon) => self.fill_polygon(&polygon, color, 1),
+                Err(_) => Ok(()),
+            };
+        }
+        let bounds = Bounds::from_points(triangle).unwrap();
+        let Some(clip) = self.device_clip().filter(|_| self.visible(&bounds)) else {
+            return Ok(());
+        };
+        let transform = self.device_transform();
+
+        let prev = self.canvas.draw_color();
+        self.canvas.set_draw_color(color);
+        let result = self.raw_triangle(triangle.map(|p| transform.apply(p)), &clip);
+        self.canvas.set_draw_color(prev);
+        result
+    }
+
+    /**
+     * Fill the area of the location of the provided polygon by splitting it into triangles, as an alternative to the scan line method
+     *
+     * With anti-aliasing enabled the polygon is filled with `fill_polygon` instead, since the edges shared by neighbouring triangles would otherwise show as seams
+     */
+    pub fn fill_triangulated(&mut self, polygon: &Polygon, color: Color) -> Result<()> {
+        if self.antialiasing {
+            return self.fill_polygon(polygon, color, 1);
+        }
+        let Some(clip) = self
+            .device_clip()
+            .filter(|_| self.visible(&polygon.bounds()))
+        else {
+            return Ok(());
+        };
+        let transform = self.device_transform();
+
+        let prev = self.canvas.draw_color();
+        self.canvas.set_draw_color(color);
+        let result = polygon.triangulate().into_iter().try_for_each(|triangle| {
+            self.raw_triangle(triangle.map(|p| transform.apply(p)), &clip)
+        });
+        self.canvas.set_draw_color(prev);
+        result
+    }
+
+    /**
+     * Fill a triangle in screen coordinates, covering every pixel whose position lies inside of it and within `clip`
+     */
+    fn raw_triangle(&mut self, triangle: [Vec2; 3], clip: &Bounds) -> Result<()> {
+        let Some(bounds) = Bounds::from_points(triangle).and_then(|b| b.intersection(clip)) else {
+            return Ok(());
+        };
+
+        for y in (bounds.min.y.ceil() as i32)..=(bounds.max.y.floor() as i32) {
+            let n = y as f32;
+            let (mut left, mut right) = (f32::INFINITY, f32::NEG_INFINITY);
+
+            for i in 0..3 {
+                let (a, b) = (triangle[i], triangle[(i + 1) % 3]);
+
+                if n < a.y.min(b.y) || n > a.y.max(b.y) {
+                    continue;
+                }
+                let xs = if a.y == b.y {
+                    [a.x, b.x]
+                } else {
+                    let x = a.x + (n - a.y) / (b.y - a.y) * (b.x - a.x);
+                    [x, x]
+                };
+                left = left.min(xs[0].min(xs[1]));
+                right = right.max(xs[0].max(xs[1]));
+            }
+            let (left, right) = (
+                left.max(bounds.min.x).ceil() as i32,
+                right.min(bounds.max.x).floor() as i32,
+            );
+
+            if left <= right {
+                self.raw_line(Point::new(left, y), Point::new(right, y))?
+            }
+        }
+        Ok(())
     }
 
     /**
@@ -150,7 +788,8 @@ impl Engine {
             .map_err(Error::from)?;
 
         self.clear();
-        Ok(self.flush())
+        self.flush();
+        Ok(())
     }
 
     /**
@@ -174,6 +813,12 @@ impl Engine {
             .map(EventType::try_from)
             .filter_map(Result::ok)
         {
+            if let Some(camera) = self.camera.as_mut() {
+                camera.handle_event(&event);
+            }
+            if let Some(plot) = self.plot.as_mut() {
+                plot.handle_event(&event);
+            }
             event_handler(self, event, Self::stop)?;
             if !self.running {
                 break;
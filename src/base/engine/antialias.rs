@@ -0,0 +1,173 @@
+use {
+    super::Engine,
+    crate::{
+        error::Result,
+        mask::row_coverage,
+        math::Vec2,
+        polygon::{Contours, FillRule, MultiPolygon},
+    },
+    sdl2::{pixels::Color, rect::Point, render::BlendMode},
+};
+
+/**
+ * The fractional part of a value
+ */
+fn fpart(n: f32) -> f32 {
+    n - n.floor()
+}
+
+/**
+ * One minus the fractional part of a value
+ */
+fn rfpart(n: f32) -> f32 {
+    1.0 - fpart(n)
+}
+
+impl Engine {
+    /**
+     * Run the provided closure with alpha blending enabled, restoring the previous blend mode and draw color afterwards
+     */
+    pub(super) fn blended(&mut self, draw: impl FnOnce(&mut Self) -> Result<()>) -> Result<()> {
+        let (mode, color) = (self.canvas.blend_mode(), self.canvas.draw_color());
+        self.canvas.set_blend_mode(BlendMode::Blend);
+
+        let result = draw(self);
+
+        self.canvas.set_blend_mode(mode);
+        self.canvas.set_draw_color(color);
+        result
+    }
+
+    /**
+     * Draw a single pixel of the provided color with its alpha scaled by `coverage` and by the current mask
+     */
+    pub(super) fn blend_pixel(
+        &mut self,
+        x: i32,
+        y: i32,
+        color: Color,
+        coverage: f32,
+    ) -> Result<()> {
+        let coverage = coverage * self.mask_coverage(x, y);
+
+        if coverage <= 0.0 {
+            return Ok(());
+        }
+        let alpha = (color.a as f32 * coverage.min(1.0)).round() as u8;
+        self.canvas
+            .set_draw_color(Color::RGBA(color.r, color.g, color.b, alpha));
+        self.canvas.draw_point(Point::new(x, y)).map_err(Into::into)
+    }
+
+    /**
+     * Draw a line in screen coordinates using Xiaolin Wu's algorithm, shading the two pixels nearest to the line by their distance from it
+     */
+    pub(super) fn wu_line(&mut self, start: Vec2, end: Vec2, color: Color) -> Result<()> {
+        let steep = (end.y - start.y).abs() > (end.x - start.x).abs();
+
+        //  Walk along the major axis from left to right
+        let (mut a, mut b) = if steep {
+            (Vec2::new(start.y, start.x), Vec2::new(end.y, end.x))
+        } else {
+            (start, end)
+        };
+        if a.x > b.x {
+            std::mem::swap(&mut a, &mut b)
+        }
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let gradient = if dx == 0.0 { 1.0 } else { dy / dx };
+
+        let plot = |engine: &mut Self, x: i32, y: i32, coverage: f32| {
+            if steep {
+                engine.blend_pixel(y, x, color, coverage)
+            } else {
+                engine.blend_pixel(x, y, color, coverage)
+            }
+        };
+
+        //  The endpoints are shaded by how much of their pixel the line covers
+        let endpoint = |engine: &mut Self, p: Vec2, gap: f32| -> Result<(i32, f32)> {
+            let x = p.x.round();
+            let y = p.y + gradient * (x - p.x);
+            let (px, py) = (x as i32, y.floor() as i32);
+
+            plot(engine, px, py, rfpart(y) * gap)?;
+            plot(engine, px, py + 1, fpart(y) * gap)?;
+            Ok((px, y))
+        };
+        let (first, y) = endpoint(self, a, rfpart(a.x + 0.5))?;
+        let (last, _) = endpoint(self, b, fpart(b.x + 0.5))?;
+
+        let mut intersection = y + gradient;
+        for x in (first + 1)..last {
+            let y = intersection.floor() as i32;
+
+            plot(self, x, y, rfpart(intersection))?;
+            plot(self, x, y + 1, fpart(intersection))?;
+            intersection += gradient;
+        }
+        Ok(())
+    }
+
+    /**
+     * Fill contours in screen coordinates, shading the pixels along their edges by how much of each pixel is covered
+     */
+    pub(super) fn antialiased_fill(
+        &mut self,
+        contours: &MultiPolygon,
+        rule: FillRule,
+        color: Color,
+    ) -> Result<()> {
+        //  Only the part which can be drawn is rasterized, however far the contours reach beyond it
+        let Some(bounds) = contours
+            .bounds()
+            .zip(self.device_clip())
+            .and_then(|(bounds, clip)| bounds.intersection(&clip))
+        else {
+            return Ok(());
+        };
+        let left = bounds.min.x.floor() as i32 - 1;
+        let mut coverage = vec![0.0f32; (bounds.max.x.ceil() as i32 + 2 - left) as usize];
+
+        for y in (bounds.min.y.round() as i32)..=(bounds.max.y.round() as i32) {
+            row_coverage(contours, rule, y, left, &mut coverage);
+
+            //  Fully covered runs are drawn as lines and the rest as individual pixels
+            let mut x = 0;
+            while x < coverage.len() {
+                if coverage[x] >= 0.999 {
+                    let start = x;
+                    while x + 1 < coverage.len() && coverage[x + 1] >= 0.999 {
+                        x += 1
+                    }
+                    self.canvas.set_draw_color(color);
+                    self.raw_line(
+                        Point::new(left + start as i32, y),
+                        Point::new(left + x as i32, y),
+                    )?;
+                } else {
+                    self.blend_pixel(left + x as i32, y, color, coverage[x])?;
+                }
+                x += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractional_parts() {
+        assert_eq!(fpart(2.25), 0.25);
+        assert_eq!(rfpart(2.25), 0.75);
+
+        //  Negative values are split towards negative infinity, so the parts stay within [0, 1]
+        assert_eq!(fpart(-1.25), 0.75);
+        assert_eq!(rfpart(-1.25), 0.25);
+        assert_eq!(fpart(3.0), 0.0);
+        assert_eq!(rfpart(3.0), 1.0);
+    }
+}
@@ -0,0 +1,217 @@
+use {
+    super::Engine,
+    crate::{
+        error::Result,
+        mask::AlphaMask,
+        math::{Bounds, Vec2},
+        polygon::{Contours, FillRule, MultiPolygon},
+        shape::Rect,
+    },
+    sdl2::{
+        pixels::Color,
+        rect::{Point, Rect as SdlRect},
+        render::BlendMode,
+    },
+};
+
+impl Engine {
+    /**
+     * The rectangle of the screen which drawing is limited to, if there is one
+     */
+    pub fn clip_rect(&self) -> Option<Rect> {
+        self.canvas.clip_rect().map(Rect::from)
+    }
+
+    /**
+     * Limit drawing to a rectangle of the screen in pixels, ignoring the current transform and camera, or remove the limit with `None`
+     *
+     * This uses the clip rectangle of SDL, so it applies to everything drawn, and is saved and restored with the rest of the drawing state
+     */
+    pub fn set_clip_rect(&mut self, rect: Option<Rect>) {
+        self.canvas.set_clip_rect(rect.map(|rect| {
+            SdlRect::new(
+                rect.x.round() as i32,
+                rect.y.round() as i32,
+                rect.width.round().max(1.0) as u32,
+                rect.height.round().max(1.0) as u32,
+            )
+        }))
+    }
+
+    /**
+     * Limit subsequent drawing to the area of a shape in the current coordinates, within any clip pushed before it, until it is popped with `pop_clip`
+     *
+     * The shape is filled with the even-odd rule like `fill_polygon`, and its edges are softened when anti-aliasing is enabled
+     */
+    pub fn push_clip_polygon<C: Contours + ?Sized>(&mut self, polygon: &C) -> Result<()> {
+        let transform = self.device_transform();
+        let contours = MultiPolygon::new(
+            polygon
+                .contours()
+                .iter()
+                .map(|contour| contour.transform(&transform)),
+        );
+        let (width, height) = self.canvas.output_size()?;
+        let screen = Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32));
+
+        let mask = AlphaMask::rasterize(&contours, FillRule::EvenOdd, self.antialiasing, &screen);
+        self.push_mask(mask);
+        Ok(())
+    }
+
+    /**
+     * Scale the coverage of subsequent drawing by an `AlphaMask` in screen pixels, within any clip pushed before it, until it is popped with `pop_clip`
+     *
+     * Partial coverage blends what is drawn when anti-aliasing is enabled, and otherwise shows only the pixels which are at least half covered
+     */
+    pub fn push_mask(&mut self, mask: AlphaMask) {
+        let mask = match self.clips.last() {
+            Some(top) => top.intersection(&mask),
+            None => mask,
+        };
+        self.clips.push(mask)
+    }
+
+    /**
+     * Remove the most recently pushed clip shape or mask, returning the combined mask it applied
+     */
+    pub fn pop_clip(&mut self) -> Option<AlphaMask> {
+        self.clips.pop()
+    }
+
+    /**
+     * The combination of every pushed clip shape and mask which currently limits drawing, if there are any
+     */
+    pub fn mask(&self) -> Option<&AlphaMask> {
+        self.clips.last()
+    }
+
+    /**
+     * How much of the pixel at `(x, y)` the current mask lets through, from `0` to `1`
+     */
+    pub(super) fn mask_coverage(&self, x: i32, y: i32) -> f32 {
+        match self.clips.last() {
+            Some(mask) => mask.get(x, y) as f32 / 255.0,
+            None => 1.0,
+        }
+    }
+
+    /**
+     * Draw a single pixel in the current draw color through the current mask
+     */
+    fn masked_point(&mut self, x: i32, y: i32) -> Result<()> {
+        let coverage = self.mask_coverage(x, y);
+        let point = Point::new(x, y);
+
+        if coverage >= 1.0 {
+            self.canvas.draw_point(point)?
+        } else if self.canvas.blend_mode() == BlendMode::Blend {
+            let color = self.canvas.draw_color();
+            let alpha = (color.a as f32 * coverage).round() as u8;
+
+            if alpha > 0 {
+                self.canvas
+                    .set_draw_color(Color::RGBA(color.r, color.g, color.b, alpha));
+                let result = self.canvas.draw_point(point);
+                self.canvas.set_draw_color(color);
+                result?
+            }
+        } else if coverage >= 0.5 {
+            self.canvas.draw_point(point)?
+        }
+        Ok(())
+    }
+
+    /**
+     * Draw a line of pixels in the current draw color through the current mask, drawing fully covered runs of a row at once
+     */
+    pub(super) fn masked_line(&mut self, start: Point, end: Point) -> Result<()> {
+        if start.y() == end.y() {
+            let y = start.y();
+            let (first, last) = (start.x().min(end.x()), start.x().max(end.x()));
+            let mut x = first;
+
+            while x <= last {
+                if self.mask_coverage(x, y) >= 1.0 {
+                    let run = x;
+                    while x < last && self.mask_coverage(x + 1, y) >= 1.0 {
+                        x += 1
+                    }
+                    self.canvas
+                        .draw_line(Point::new(run, y), Point::new(x, y))?;
+                } else {
+                    self.masked_point(x, y)?;
+                }
+                x += 1;
+            }
+            return Ok(());
+        }
+
+        //  Any other line is walked pixel by pixel with Bresenham's algorithm
+        let (mut x, mut y) = (start.x(), start.y());
+        let (dx, dy) = ((end.x() - x).abs(), -(end.y() - y).abs());
+        let (sx, sy) = ((end.x() - x).signum(), (end.y() - y).signum());
+        let mut error = dx + dy;
+
+        loop {
+            self.masked_point(x, y)?;
+
+            if x == end.x() && y == end.y() {
+                return Ok(());
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /**
+     * Draw individual pixels directly onto the canvas through the current mask
+     */
+    pub(super) fn raw_points(&mut self, points: &[Point]) -> Result<()> {
+        if self.clips.is_empty() {
+            return self.canvas.draw_points(points).map_err(Into::into);
+        }
+        points
+            .iter()
+            .try_for_each(|p| self.masked_point(p.x(), p.y()))
+    }
+
+    /**
+     * Draw the outlines of rectangles directly onto the canvas through the current mask
+     */
+    pub(super) fn raw_rects(&mut self, rects: &[SdlRect]) -> Result<()> {
+        if self.clips.is_empty() {
+            return self.canvas.draw_rects(rects).map_err(Into::into);
+        }
+        rects.iter().try_for_each(|rect| {
+            let (left, top) = (rect.left(), rect.top());
+            let (right, bottom) = (rect.right() - 1, rect.bottom() - 1);
+
+            self.masked_line(Point::new(left, top), Point::new(right, top))?;
+            self.masked_line(Point::new(left, bottom), Point::new(right, bottom))?;
+            self.masked_line(Point::new(left, top), Point::new(left, bottom))?;
+            self.masked_line(Point::new(right, top), Point::new(right, bottom))
+        })
+    }
+
+    /**
+     * Fill rectangles directly onto the canvas through the current mask
+     */
+    pub(super) fn raw_fill_rects(&mut self, rects: &[SdlRect]) -> Result<()> {
+        if self.clips.is_empty() {
+            return self.canvas.fill_rects(rects).map_err(Into::into);
+        }
+        rects.iter().try_for_each(|rect| {
+            (rect.top()..rect.bottom()).try_for_each(|y| {
+                self.masked_line(Point::new(rect.left(), y), Point::new(rect.right() - 1, y))
+            })
+        })
+    }
+}
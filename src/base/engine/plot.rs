@@ -0,0 +1,227 @@
+use {
+    super::Engine,
+    crate::{
+        error::Result,
+        math::{Transform2D, Vec2},
+        plot::{tick_label, ticks, Plot},
+        shape::Rect,
+        stroke::StrokeStyle,
+    },
+};
+
+/**
+ * The length in pixels of tick marks on either side of an axis
+ */
+const TICK: f32 = 3.0;
+
+/**
+ * The space in pixels left between labels and what they label
+ */
+const GAP: f32 = 4.0;
+
+impl Engine {
+    /**
+     * Draw a `Plot` within its viewport, ignoring the current transform and camera
+     *
+     * The grid, axes, functions, labels and legend are drawn in that order, clipped to the viewport
+     */
+    pub fn draw_plot(&mut self, plot: &Plot) -> Result<()> {
+        self.save();
+        let result = self.plot_layers(plot);
+        self.restore();
+        result
+    }
+
+    /**
+     * Draw the attached plot, if there is one
+     */
+    pub fn render_plot(&mut self) -> Result<()> {
+        match self.plot.take() {
+            Some(plot) => {
+                let result = self.draw_plot(&plot);
+                self.plot = Some(plot);
+                result
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn plot_layers(&mut self, plot: &Plot) -> Result<()> {
+        //  Cancel out the camera so that everything below is drawn in screen coordinates
+        let screen = self
+            .camera
+            .as_ref()
+            .and_then(|camera| camera.view().inverse())
+            .unwrap_or(Transform2D::IDENTITY);
+        self.transform = screen;
+        self.stroke = StrokeStyle::default();
+
+        let style = plot.style().clone();
+        let (viewport, world) = (plot.viewport(), plot.world());
+        let to_screen = plot.transform();
+
+        self.set_clip_rect(Some(viewport));
+        if let Some(background) = style.background {
+            self.fill_rect(viewport, background)?;
+        }
+
+        let steps = (
+            plot.major_step(world.width, viewport.width),
+            plot.major_step(world.height, viewport.height),
+        );
+        if style.show_grid {
+            if style.minor_divisions > 1 {
+                let n = style.minor_divisions as f32;
+                self.set_draw_color(style.minor_grid);
+                self.plot_grid(plot, (steps.0 / n, steps.1 / n))?;
+            }
+            self.set_draw_color(style.grid);
+            self.plot_grid(plot, steps)?;
+        }
+
+        //  The axes cross at the origin, or run along the nearest edges when it is out of sight
+        let origin = to_screen.apply(Vec2::new(
+            0f32.clamp(world.x, world.x + world.width),
+            0f32.clamp(world.y, world.y + world.height),
+        ));
+        let (min, max) = (viewport.min(), viewport.max());
+
+        self.set_draw_color(style.axis);
+        self.draw_line((min.x, origin.y), (max.x, origin.y))?;
+        self.draw_line((origin.x, min.y), (origin.x, max.y))?;
+
+        let xs = ticks(world.x, world.x + world.width, steps.0);
+        let ys = ticks(world.y, world.y + world.height, steps.1);
+        for &x in &xs {
+            let sx = to_screen.apply(Vec2::new(x, 0.0)).x;
+            self.draw_line((sx, origin.y - TICK), (sx, origin.y + TICK))?;
+        }
+        for &y in &ys {
+            let sy = to_screen.apply(Vec2::new(0.0, y)).y;
+            self.draw_line((origin.x - TICK, sy), (origin.x + TICK, sy))?;
+        }
+
+        let scale = style.text_scale;
+        self.set_draw_color(style.text);
+
+        //  Tick labels sit below and to the left of the axes, unless that would leave the viewport
+        for &x in &xs {
+            let label = tick_label(x, steps.0);
+            let (w, h) = Self::text_size(&label, scale);
+            let sx = to_screen.apply(Vec2::new(x, 0.0)).x;
+
+            let below = origin.y + TICK + GAP;
+            let y = if below + h as f32 > max.y {
+                origin.y - TICK - GAP - h as f32
+            } else {
+                below
+            };
+            self.draw_text(&label, (sx - w as f32 / 2.0, y), scale)?;
+        }
+        for &y in &ys {
+            //  The origin is already labelled on the x-axis
+            if y == 0.0 && world.x <= 0.0 && world.x + world.width >= 0.0 {
+                continue;
+            }
+            let label = tick_label(y, steps.1);
+            let (w, h) = Self::text_size(&label, scale);
+            let sy = to_screen.apply(Vec2::new(0.0, y)).y;
+
+            let left = origin.x - TICK - GAP - w as f32;
+            let x = if left < min.x {
+                origin.x + TICK + GAP
+            } else {
+                left
+            };
+            self.draw_text(&label, (x, sy - h as f32 / 2.0), scale)?;
+        }
+
+        //  Functions are sampled once per pixel across the viewport
+        self.transform = screen * to_screen;
+        let step = world.width / viewport.width.max(1.0);
+        for series in plot.series() {
+            self.set_draw_color(series.color);
+            self.plot_function(&series.function, (world.x, world.x + world.width), step)?;
+        }
+        self.transform = screen;
+
+        let (x_label, y_label) = plot.labels();
+        self.set_draw_color(style.text);
+        let (w, h) = Self::text_size(x_label, scale);
+        self.draw_text(
+            x_label,
+            (max.x - w as f32 - GAP, origin.y - TICK - GAP - h as f32),
+            scale,
+        )?;
+        self.draw_text(y_label, (origin.x + TICK + GAP, min.y + GAP), scale)?;
+
+        if style.show_legend && !plot.series().is_empty() {
+            self.plot_legend(plot)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Draw vertical and horizontal lines across the viewport at every multiple of the provided steps
+     */
+    fn plot_grid(&mut self, plot: &Plot, (step_x, step_y): (f32, f32)) -> Result<()> {
+        let (viewport, world) = (plot.viewport(), plot.world());
+        let to_screen = plot.transform();
+        let (min, max) = (viewport.min(), viewport.max());
+
+        for x in ticks(world.x, world.x + world.width, step_x) {
+            let sx = to_screen.apply(Vec2::new(x, 0.0)).x;
+            self.draw_line((sx, min.y), (sx, max.y))?;
+        }
+        for y in ticks(world.y, world.y + world.height, step_y) {
+            let sy = to_screen.apply(Vec2::new(0.0, y)).y;
+            self.draw_line((min.x, sy), (max.x, sy))?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Draw a box in the top-right corner of the viewport with a colored swatch and label for every function
+     */
+    fn plot_legend(&mut self, plot: &Plot) -> Result<()> {
+        let style = plot.style();
+        let scale = style.text_scale;
+        let max = plot.viewport().max();
+        let min = plot.viewport().min();
+
+        let sizes = plot
+            .series()
+            .iter()
+            .map(|series| Self::text_size(&series.label, scale))
+            .collect::<Vec<(u32, u32)>>();
+        let swatch = 12.0 * scale as f32;
+        let row = sizes.iter().map(|s| s.1).max().unwrap_or(0) as f32 + GAP;
+        let width = sizes.iter().map(|s| s.0).max().unwrap_or(0) as f32 + swatch + GAP * 3.0;
+        let height = row * sizes.len() as f32 + GAP;
+
+        let legend = Rect::new(max.x - width - GAP * 2.0, min.y + GAP * 2.0, width, height);
+        if let Some(background) = style.background {
+            self.fill_rect(legend, background)?;
+        }
+        self.set_draw_color(style.axis);
+        self.draw_rect(legend)?;
+
+        for (k, series) in plot.series().iter().enumerate() {
+            let top = legend.y + GAP + row * k as f32;
+            let middle = top + (row - GAP) / 2.0;
+
+            self.fill_rect(
+                Rect::new(
+                    legend.x + GAP,
+                    middle - scale as f32,
+                    swatch,
+                    2.0 * scale as f32,
+                ),
+                series.color,
+            )?;
+            self.set_draw_color(style.text);
+            self.draw_text(&series.label, (legend.x + swatch + GAP * 2.0, top), scale)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,528 @@
+use {
+    super::Engine,
+    crate::{
+        error::Result,
+        math::{Bounds, Vec2},
+        shape::{Arc, Circle, Ellipse, Pie, Rect, RoundedRect, Sector, Shape},
+    },
+    sdl2::{
+        pixels::Color,
+        rect::{Point, Rect as SdlRect},
+    },
+    std::f32::consts::TAU,
+};
+
+/**
+ * The largest radius in pixels which is rasterized with the midpoint algorithms, beyond which the outline of an ellipse is drawn with lines and the width of each row of its fill is calculated directly
+ */
+const MIDPOINT_LIMIT: i32 = 1 << 14;
+
+/**
+ * The first quadrant of a circle using the midpoint circle algorithm
+ */
+fn midpoint_circle(r: i32) -> Vec<(i32, i32)> {
+    let mut points = Vec::new();
+    let (mut x, mut y, mut err) = (r, 0, 1 - r);
+
+    while x >= y {
+        points.push((x, y));
+        points.push((y, x));
+        y += 1;
+
+        if err < 0 {
+            err += 2 * y + 1
+        } else {
+            x -= 1;
+            err += 2 * (y - x) + 1
+        }
+    }
+    points
+}
+
+/**
+ * The first quadrant of an axis-aligned ellipse using the midpoint ellipse algorithm
+ */
+fn midpoint_ellipse(rx: i32, ry: i32) -> Vec<(i32, i32)> {
+    if rx == 0 || ry == 0 {
+        return (0..=rx)
+            .flat_map(|x| (0..=ry).map(move |y| (x, y)))
+            .collect();
+    }
+    let (rx2, ry2) = ((rx as f64).powi(2), (ry as f64).powi(2));
+    let mut points = Vec::new();
+    let (mut x, mut y) = (0, ry);
+    let (mut dx, mut dy) = (0.0, 2.0 * rx2 * y as f64);
+
+    //  The region where the slope is shallower than -1 steps along x
+    let mut p = ry2 - rx2 * ry as f64 + rx2 / 4.0;
+    while dx < dy {
+        points.push((x, y));
+        x += 1;
+        dx += 2.0 * ry2;
+
+        if p < 0.0 {
+            p += dx + ry2
+        } else {
+            y -= 1;
+            dy -= 2.0 * rx2;
+            p += dx - dy + ry2
+        }
+    }
+
+    //  The region where the slope is steeper than -1 steps along y
+    let mut p = ry2 * (x as f64 + 0.5).powi(2) + rx2 * (y as f64 - 1.0).powi(2) - rx2 * ry2;
+    while y >= 0 {
+        points.push((x, y));
+        y -= 1;
+        dy -= 2.0 * rx2;
+
+        if p > 0.0 {
+            p += rx2 - dy
+        } else {
+            x += 1;
+            dx += 2.0 * ry2;
+            p += dx - dy + rx2
+        }
+    }
+    points
+}
+
+/**
+ * Return `true` if the ellipse has a finite center and radii, so that it can be drawn
+ */
+fn finite(ellipse: &Ellipse) -> bool {
+    [ellipse.center.x, ellipse.center.y, ellipse.rx, ellipse.ry]
+        .iter()
+        .all(|v| v.is_finite())
+}
+
+impl Engine {
+    /**
+     * Draw the outline of a circle
+     */
+    pub fn draw_circle<T: Into<Vec2>>(&mut self, center: T, radius: f32) -> Result<()> {
+        let c = Circle::new(center, radius);
+        self.draw_ellipse_sector(Ellipse::new(c.center, c.radius, c.radius), 0.0, TAU)
+    }
+
+    /**
+     * Fill the area of a circle
+     */
+    pub fn fill_circle<T: Into<Vec2>>(
+        &mut self,
+        center: T,
+        radius: f32,
+        color: Color,
+    ) -> Result<()> {
+        let c = Circle::new(center, radius);
+        self.fill_ellipse_sector(Ellipse::new(c.center, c.radius, c.radius), 0.0, TAU, color)
+    }
+
+    /**
+     * Draw the outline of an axis-aligned ellipse
+     */
+    pub fn draw_ellipse<T: Into<Vec2>>(&mut self, center: T, rx: f32, ry: f32) -> Result<()> {
+        self.draw_ellipse_sector(Ellipse::new(center, rx, ry), 0.0, TAU)
+    }
+
+    /**
+     * Fill the area of an axis-aligned ellipse
+     */
+    pub fn fill_ellipse<T: Into<Vec2>>(
+        &mut self,
+        center: T,
+        rx: f32,
+        ry: f32,
+        color: Color,
+    ) -> Result<()> {
+        self.fill_ellipse_sector(Ellipse::new(center, rx, ry), 0.0, TAU, color)
+    }
+
+    /**
+     * Draw a circular arc running from `start` to `end`, in radians clockwise from the positive x-axis
+     */
+    pub fn draw_arc<T: Into<Vec2>>(
+        &mut self,
+        center: T,
+        radius: f32,
+        start: f32,
+        end: f32,
+    ) -> Result<()> {
+        let arc = Arc::new(center, radius, start, end);
+        let (start, sweep) = arc.sweep();
+        self.draw_ellipse_sector(
+            Ellipse::new(arc.center, arc.radius, arc.radius),
+            start,
+            sweep,
+        )
+    }
+
+    /**
+     * Fill the slice of a circle between the angles `start` and `end`, in radians clockwise from the positive x-axis
+     */
+    pub fn fill_pie<T: Into<Vec2>>(
+        &mut self,
+        center: T,
+        radius: f32,
+        start: f32,
+        end: f32,
+        color: Color,
+    ) -> Result<()> {
+        let pie = Pie::new(center, radius, start, end);
+        let (start, sweep) = pie.arc.sweep();
+        self.fill_ellipse_sector(
+            Ellipse::new(pie.arc.center, pie.arc.radius, pie.arc.radius),
+            start,
+            sweep,
+            color,
+        )
+    }
+
+    /**
+     * The center and radii of an ellipse in screen coordinates, if the current transform keeps it axis-aligned without mirroring it
+     */
+    fn raster_ellipse(&self, ellipse: &Ellipse) -> Option<(Point, i32, i32)> {
+        let transform = self.device_transform();
+        let [[a, _, _], [_, e, _], _] = transform.matrix();
+
+        (transform.is_axis_aligned() && a > 0.0 && e > 0.0).then(|| {
+            (
+                transform.apply(ellipse.center).into(),
+                (ellipse.rx * a).round() as i32,
+                (ellipse.ry * e).round() as i32,
+            )
+        })
+    }
+
+    /**
+     * The first quadrant of the outline of an ellipse in screen coordinates, using the circle algorithm when both radii match
+     */
+    fn quadrant(rx: i32, ry: i32) -> Vec<(i32, i32)> {
+        if rx == ry {
+            midpoint_circle(rx)
+        } else {
+            midpoint_ellipse(rx, ry)
+        }
+    }
+
+    /**
+     * Draw the part of the outline of an ellipse within the sweep beginning at `start`
+     */
+    fn draw_ellipse_sector(&mut self, ellipse: Ellipse, start: f32, sweep: f32) -> Result<()> {
+        let bounds = ellipse.bounds();
+
+        if !finite(&ellipse) || !self.visible(&bounds.expand(self.stroke.width)) {
+            return Ok(());
+        }
+        let raster = self.raster_ellipse(&ellipse).filter(|&(_, rx, ry)| {
+            !self.antialiasing && self.stroke.is_hairline() && rx.max(ry) <= MIDPOINT_LIMIT
+        });
+
+        let Some((center, rx, ry)) = raster else {
+            //  Anything the midpoint algorithms cannot draw is approximated by lines
+            return if sweep >= TAU {
+                match ellipse.to_polygon() {
+                    Ok(polygon) => self.draw_polygon(&polygon),
+                    Err(_) => Ok(()),
+                }
+            } else {
+                self.draw_lines(ellipse.points(start, sweep))
+            };
+        };
+        let sector = Sector::new(start, sweep);
+
+        let points = Self::quadrant(rx, ry)
+            .into_iter()
+            .flat_map(|(x, y)| [(x, y), (-x, y), (x, -y), (-x, -y)])
+            .filter(|&(x, y)| sector.contains(Vec2::new(x as f32, y as f32)))
+            .map(|(x, y)| Point::new(center.x() + x, center.y() + y))
+            .collect::<Vec<Point>>();
+
+        self.raw_points(&points)
+    }
+
+    /**
+     * Fill the part of an ellipse within the sweep beginning at `start`
+     */
+    fn fill_ellipse_sector(
+        &mut self,
+        ellipse: Ellipse,
+        start: f32,
+        sweep: f32,
+        color: Color,
+    ) -> Result<()> {
+        if !finite(&ellipse) || !self.visible(&ellipse.bounds()) {
+            return Ok(());
+        }
+        let raster = self.raster_ellipse(&ellipse).filter(|_| !self.antialiasing);
+
+        let Some((center, rx, ry)) = raster else {
+            let polygon = if sweep >= TAU {
+                ellipse.to_polygon()
+            } else {
+                Pie::new(ellipse.center, ellipse.rx, start, start + sweep).to_polygon()
+            };
+            return match polygon {
+                Ok(polygon) => self.fill_polygon(&polygon, color, 1),
+                Err(_) => Ok(()),
+            };
+        };
+        let Some(clip) = self.device_clip() else {
+            return Ok(());
+        };
+
+        //  Only the rows and columns on screen are drawn, relative to the center and widened to avoid overflow
+        let (cx, cy) = (center.x() as i64, center.y() as i64);
+        let (top, bottom) = (
+            (-ry as i64).max(clip.min.y.floor() as i64 - cy),
+            (ry as i64).min(clip.max.y.ceil() as i64 - cy),
+        );
+        let (left, right) = (
+            clip.min.x.floor() as i64 - cx,
+            clip.max.x.ceil() as i64 - cx,
+        );
+        if top > bottom {
+            return Ok(());
+        }
+
+        //  The half-width of each row, taken from the outline so the fill matches it exactly unless it is too large to trace
+        let rows = (rx.max(ry) <= MIDPOINT_LIMIT).then(|| {
+            let mut rows = vec![-1; ry as usize + 1];
+            for (x, y) in Self::quadrant(rx, ry) {
+                if y >= 0 && y <= ry {
+                    rows[y as usize] = rows[y as usize].max(x)
+                }
+            }
+            rows
+        });
+        let half = |dy: i64| -> i64 {
+            match &rows {
+                Some(rows) => rows[dy.unsigned_abs() as usize] as i64,
+                None => {
+                    let t = dy as f64 / ry.max(1) as f64;
+                    (rx as f64 * (1.0 - t * t).max(0.0).sqrt()).round() as i64
+                }
+            }
+        };
+        let point = |dx: i64, dy: i64| Point::new((cx + dx) as i32, (cy + dy) as i32);
+        let sector = Sector::new(start, sweep);
+
+        let prev = self.canvas.draw_color();
+        self.canvas.set_draw_color(color);
+
+        for dy in top..=bottom {
+            let half = half(dy);
+            let (first, last) = ((-half).max(left), half.min(right));
+
+            if first > last {
+                continue;
+            }
+            if sweep >= TAU {
+                self.raw_line(point(first, dy), point(last, dy))?;
+                continue;
+            }
+
+            //  Partial sweeps are split into runs of pixels inside of the sector
+            let mut run: Option<i64> = None;
+            for dx in first..=(last + 1) {
+                let inside = dx <= last && sector.contains(Vec2::new(dx as f32, dy as f32));
+
+                match (run, inside) {
+                    (None, true) => run = Some(dx),
+                    (Some(start), false) => {
+                        self.raw_line(point(start, dy), point(dx - 1, dy))?;
+                        run = None
+                    }
+                    _ => (),
+                }
+            }
+        }
+        self.canvas.set_draw_color(prev);
+        Ok(())
+    }
+
+    /**
+     * Draw the outline of a rectangle
+     */
+    pub fn draw_rect(&mut self, rect: Rect) -> Result<()> {
+        self.draw_rects(&[rect])
+    }
+
+    /**
+     * Draw the outlines of several rectangles, batched into a single call when the current transform allows it
+     */
+    pub fn draw_rects(&mut self, rects: &[Rect]) -> Result<()> {
+        let raster = !self.antialiasing && self.stroke.is_hairline();
+
+        match self.raster_rects(rects).filter(|_| raster) {
+            Some(rects) => self.raw_rects(&rects),
+            None => rects.iter().try_for_each(|rect| match rect.to_polygon() {
+                Ok(polygon) => self.draw_polygon(&polygon),
+                Err(_) => Ok(()),
+            }),
+        }
+    }
+
+    /**
+     * Fill the area of a rectangle
+     */
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) -> Result<()> {
+        self.fill_rects(&[rect], color)
+    }
+
+    /**
+     * Fill the areas of several rectangles, batched into a single call when the current transform allows it
+     */
+    pub fn fill_rects(&mut self, rects: &[Rect], color: Color) -> Result<()> {
+        match self.raster_rects(rects).filter(|_| !self.antialiasing) {
+            Some(rects) => self.fill_sdl_rects(&rects, color),
+            None => rects.iter().try_for_each(|rect| match rect.to_polygon() {
+                Ok(polygon) => self.fill_polygon(&polygon, color, 1),
+                Err(_) => Ok(()),
+            }),
+        }
+    }
+
+    /**
+     * Draw the outline of a rectangle with rounded corners
+     */
+    pub fn draw_rounded_rect(&mut self, rect: RoundedRect) -> Result<()> {
+        if rect.radii.iter().all(|&r| r == 0.0) {
+            return self.draw_rect(rect.rect);
+        }
+        match rect.to_polygon() {
+            Ok(polygon) => self.draw_polygon(&polygon),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /**
+     * Fill the area of a rectangle with rounded corners
+     */
+    pub fn fill_rounded_rect(&mut self, rect: RoundedRect, color: Color) -> Result<()> {
+        if !self.visible(&rect.bounds()) {
+            return Ok(());
+        }
+        let transform = self.device_transform();
+        let [[a, _, _], [_, e, _], _] = transform.matrix();
+
+        if self.antialiasing || !transform.is_axis_aligned() || a <= 0.0 || e <= 0.0 {
+            return match rect.to_polygon() {
+                Ok(polygon) => self.fill_polygon(&polygon, color, 1),
+                Err(_) => Ok(()),
+            };
+        }
+        let Some(clip) = self.device_clip() else {
+            return Ok(());
+        };
+        let bounds = rect.bounds().transform(&transform);
+        let (min, max) = (bounds.min, bounds.max);
+        let radii = rect.radii.map(|r| Vec2::new(r * a, r * e));
+
+        //  How far the corner at `radius` pulls the edge in on the row `dy` away from the corner's center
+        let inset = |radius: Vec2, dy: f32| {
+            if dy <= 0.0 || radius.y == 0.0 {
+                0.0
+            } else {
+                radius.x * (1.0 - (1.0 - (dy / radius.y).min(1.0).powi(2)).sqrt())
+            }
+        };
+
+        //  Consecutive rows with the same span are merged into a single rectangle, keeping only the rows and columns on screen
+        let mut rects: Vec<SdlRect> = Vec::new();
+        let (top, bottom) = (min.y.max(clip.min.y), max.y.min(clip.max.y));
+
+        for y in (top.ceil() as i32)..(bottom.ceil() as i32) {
+            let row = y as f32;
+            let [tl, tr, br, bl] = radii;
+
+            let left = inset(tl, min.y + tl.y - row).max(inset(bl, row - (max.y - bl.y)));
+            let right = inset(tr, min.y + tr.y - row).max(inset(br, row - (max.y - br.y)));
+            let (x0, x1) = (
+                (min.x + left).max(clip.min.x).ceil() as i32,
+                (max.x - right).min(clip.max.x).ceil() as i32,
+            );
+
+            if x1 <= x0 {
+                continue;
+            }
+            match rects.last_mut() {
+                Some(last) if last.x() == x0 && last.right() == x1 && last.bottom() == y => {
+                    last.set_height(last.height() + 1)
+                }
+                _ => rects.push(SdlRect::new(x0, y, x1.abs_diff(x0), 1)),
+            }
+        }
+        self.fill_sdl_rects(&rects, color)
+    }
+
+    /**
+     * The rectangles in screen coordinates, if the current transform keeps them axis-aligned
+     *
+     * Each rectangle is cut to the drawable area before it is rounded to pixels, so edges off the screen are never drawn and the size always fits
+     */
+    fn raster_rects(&self, rects: &[Rect]) -> Option<Vec<SdlRect>> {
+        let transform = self.device_transform();
+        let clip = self.device_clip();
+
+        transform.is_axis_aligned().then(|| {
+            rects
+                .iter()
+                .filter_map(|rect| rect.bounds().transform(&transform).intersection(&clip?))
+                .filter_map(|Bounds { min, max }| {
+                    let (x0, y0) = (min.x.ceil() as i32, min.y.ceil() as i32);
+                    let (x1, y1) = (max.x.ceil() as i32, max.y.ceil() as i32);
+                    (x1 > x0 && y1 > y0)
+                        .then(|| SdlRect::new(x0, y0, x1.abs_diff(x0), y1.abs_diff(y0)))
+                })
+                .collect()
+        })
+    }
+
+    fn fill_sdl_rects(&mut self, rects: &[SdlRect], color: Color) -> Result<()> {
+        let prev = self.canvas.draw_color();
+        self.canvas.set_draw_color(color);
+        let result = self.raw_fill_rects(rects);
+        self.canvas.set_draw_color(prev);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn midpoint_circles() {
+        for r in [1, 5, 40] {
+            let points = midpoint_circle(r);
+            assert!(points.contains(&(r, 0)) && points.contains(&(0, r)));
+
+            //  Every pixel lies within half of a pixel of the circle
+            assert!(points
+                .iter()
+                .all(|&(x, y)| { (((x * x + y * y) as f32).sqrt() - r as f32).abs() <= 0.5 }));
+        }
+    }
+
+    #[test]
+    fn midpoint_ellipses() {
+        for (rx, ry) in [(8, 3), (3, 8), (20, 20)] {
+            let points = midpoint_ellipse(rx, ry);
+            assert!(points.contains(&(rx, 0)) && points.contains(&(0, ry)));
+
+            //  The outline is connected, stepping at most one pixel along each axis
+            assert!(points
+                .windows(2)
+                .all(|w| (w[1].0 - w[0].0).abs() <= 1 && (w[1].1 - w[0].1).abs() <= 1));
+
+            let ellipse = Ellipse::new((0.0, 0.0), rx as f32, ry as f32);
+            assert!(points
+                .iter()
+                .all(|&(x, y)| ellipse.distance_to(Vec2::new(x as f32, y as f32)) < 1.0));
+        }
+
+        //  Flat ellipses fill the rows or columns which they collapse into
+        assert_eq!(midpoint_ellipse(2, 0), vec![(0, 0), (1, 0), (2, 0)]);
+    }
+}
@@ -1,4 +1,5 @@
 use {
+    super::{math::ParseError, polygon::PolygonError},
     sdl2::{video::WindowBuildError, IntegerOrSdlError},
     std::{convert::Infallible, fmt::Display},
 };
@@ -10,6 +11,20 @@ enum ErrorKind {
     Abstract,
     Window,
     Canvas,
+    Parse(ParseError),
+    Polygon(PolygonError),
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Abstract => "Abstract",
+            Self::Window => "Window",
+            Self::Canvas => "Canvas",
+            Self::Parse(_) => "Parse",
+            Self::Polygon(_) => "Polygon",
+        })
+    }
 }
 
 impl From<&str> for ErrorKind {
@@ -36,6 +51,18 @@ impl From<IntegerOrSdlError> for ErrorKind {
     }
 }
 
+impl From<ParseError> for ErrorKind {
+    fn from(e: ParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+
+impl From<PolygonError> for ErrorKind {
+    fn from(e: PolygonError) -> Self {
+        Self::Polygon(e)
+    }
+}
+
 impl From<Infallible> for ErrorKind {
     fn from(_: Infallible) -> Self {
         Self::Abstract
@@ -48,9 +75,31 @@ pub struct Error {
     error: String,
 }
 
+impl Error {
+    /**
+     * The problem found while parsing, if this error was caused by one
+     */
+    pub fn parse(&self) -> Option<&ParseError> {
+        match &self.kind {
+            ErrorKind::Parse(e) => Some(e),
+            _ => None,
+        }
+    }
+
+    /**
+     * The problem found with a polygon, if this error was caused by one
+     */
+    pub fn polygon(&self) -> Option<&PolygonError> {
+        match &self.kind {
+            ErrorKind::Polygon(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("{:?}: {}", self.kind, self.error))
+        f.write_str(&format!("{}: {}", self.kind, self.error))
     }
 }
 
@@ -90,6 +139,24 @@ impl From<IntegerOrSdlError> for Error {
     }
 }
 
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Self {
+            error: e.to_string(),
+            kind: e.into(),
+        }
+    }
+}
+
+impl From<PolygonError> for Error {
+    fn from(e: PolygonError) -> Self {
+        Self {
+            error: e.to_string(),
+            kind: e.into(),
+        }
+    }
+}
+
 impl From<Infallible> for Error {
     fn from(e: Infallible) -> Self {
         Self {
@@ -0,0 +1,296 @@
+use {
+    super::{
+        error::Result,
+        math::{Bounds, Vec2},
+        polygon::{Contours, FillRule},
+    },
+    sdl2::rect::Rect,
+};
+
+/**
+ * The number of sub-scanlines sampled within each row of pixels when computing anti-aliased coverage
+ */
+pub(crate) const SAMPLES: usize = 4;
+
+/**
+ * A grid of coverage values over a rectangle of pixels, where `0` hides a pixel, `255` shows it fully and anything outside of the rectangle is hidden
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlphaMask {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl AlphaMask {
+    /**
+     * Initialize a new `AlphaMask` from rows of coverage values, starting from the top-left corner at `(x, y)`
+     */
+    pub fn new(x: i32, y: i32, width: u32, height: u32, data: Vec<u8>) -> Result<Self> {
+        if data.len() != width as usize * height as usize {
+            return Err("Mask data does not match its size".into());
+        }
+        Ok(Self {
+            x,
+            y,
+            width,
+            height,
+            data,
+        })
+    }
+
+    /**
+     * Initialize a new `AlphaMask` from the coverage returned by `f` for each pixel
+     */
+    pub fn from_fn<F: Fn(i32, i32) -> u8>(x: i32, y: i32, width: u32, height: u32, f: F) -> Self {
+        let data = (0..height as i32)
+            .flat_map(|dy| (0..width as i32).map(move |dx| (x + dx, y + dy)))
+            .map(|(px, py)| f(px, py))
+            .collect();
+
+        Self {
+            x,
+            y,
+            width,
+            height,
+            data,
+        }
+    }
+
+    /**
+     * The coverage of the area of a shape according to the provided `FillRule` within a target of the provided size, such as the screen, where pixels along its edges are partly covered if `antialiased` is `true`
+     *
+     * Only the part of the shape within the target is stored, so the mask never grows beyond `width` by `height`
+     */
+    pub fn from_contours<C: Contours + ?Sized>(
+        contours: &C,
+        rule: FillRule,
+        antialiased: bool,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let target = Bounds::new(Vec2::ZERO, Vec2::new(width as f32, height as f32));
+        Self::rasterize(contours, rule, antialiased, &target)
+    }
+
+    /**
+     * The coverage of the area of a shape limited to the pixels within `limit`
+     *
+     * Without anti-aliasing, pixels are covered when their position lies inside of the shape, exactly as a filled polygon covers them
+     */
+    pub(crate) fn rasterize<C: Contours + ?Sized>(
+        contours: &C,
+        rule: FillRule,
+        antialiased: bool,
+        limit: &Bounds,
+    ) -> Self {
+        let Some(Bounds { min, max }) = contours
+            .bounds()
+            .and_then(|bounds| bounds.expand(1.0).intersection(limit))
+        else {
+            return Self::from_fn(0, 0, 0, 0, |_, _| 0);
+        };
+        let (x, y) = (min.x.floor() as i32, min.y.floor() as i32);
+
+        //  The size is found at `i64` precision, so that saturated coordinates cannot overflow
+        let size = |max: f32, min: i32| {
+            (max.ceil() as i64 - min as i64 + 1).clamp(0, u32::MAX as i64) as u32
+        };
+        let (width, height) = (size(max.x, x), size(max.y, y));
+        let mut mask = Self::from_fn(x, y, width, height, |_, _| 0);
+
+        let mut coverage = vec![0.0f32; width as usize];
+        for row in 0..height as i32 {
+            if antialiased {
+                row_coverage(contours, rule, y + row, x, &mut coverage);
+            } else {
+                coverage.iter_mut().for_each(|c| *c = 0.0);
+
+                for (start, end) in contours.spans_at_y((y + row) as f32, rule) {
+                    let first = (start.ceil() as i32 - x).max(0);
+                    let last = (end.ceil() as i32 - 1 - x).min(width as i32 - 1);
+                    (first..=last).for_each(|k| coverage[k as usize] = 1.0);
+                }
+            }
+
+            let offset = row as usize * width as usize;
+            for (value, c) in mask.data[offset..offset + width as usize]
+                .iter_mut()
+                .zip(&coverage)
+            {
+                *value = (c.clamp(0.0, 1.0) * 255.0).round() as u8
+            }
+        }
+        mask
+    }
+
+    /**
+     * The rectangle of pixels the mask covers, if it covers any
+     */
+    pub fn rect(&self) -> Option<Rect> {
+        (self.width > 0 && self.height > 0)
+            .then(|| Rect::new(self.x, self.y, self.width, self.height))
+    }
+
+    /**
+     * The area the mask covers, where each pixel spans from its position to the next
+     */
+    pub fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Vec2::new(self.x as f32, self.y as f32),
+            Vec2::new(
+                (self.x + self.width as i32) as f32,
+                (self.y + self.height as i32) as f32,
+            ),
+        )
+    }
+
+    /**
+     * Return `true` if the mask hides every pixel
+     */
+    pub fn is_empty(&self) -> bool {
+        self.data.iter().all(|&v| v == 0)
+    }
+
+    /**
+     * The coverage of the pixel at `(x, y)`
+     */
+    pub fn get(&self, x: i32, y: i32) -> u8 {
+        let (dx, dy) = (x - self.x, y - self.y);
+
+        if dx < 0 || dy < 0 || dx >= self.width as i32 || dy >= self.height as i32 {
+            0
+        } else {
+            self.data[dy as usize * self.width as usize + dx as usize]
+        }
+    }
+
+    /**
+     * The mask which shows only what both masks show, multiplying their coverage
+     */
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (x, y) = (self.x.max(other.x), self.y.max(other.y));
+        let right = (self.x + self.width as i32).min(other.x + other.width as i32);
+        let bottom = (self.y + self.height as i32).min(other.y + other.height as i32);
+
+        Self::from_fn(
+            x,
+            y,
+            (right - x).max(0) as u32,
+            (bottom - y).max(0) as u32,
+            |px, py| ((self.get(px, py) as u32 * other.get(px, py) as u32 + 127) / 255) as u8,
+        )
+    }
+}
+
+/**
+ * Fill `coverage` with how much of each pixel of row `y` lies inside of the shape, starting from the pixel at `left`
+ *
+ * Each pixel covers the area within half of a pixel of its position, which is sampled along several sub-scanlines
+ */
+pub(crate) fn row_coverage<C: Contours + ?Sized>(
+    contours: &C,
+    rule: FillRule,
+    y: i32,
+    left: i32,
+    coverage: &mut [f32],
+) {
+    coverage.iter_mut().for_each(|c| *c = 0.0);
+
+    for k in 0..SAMPLES {
+        let sample = y as f32 - 0.5 + (k as f32 + 0.5) / SAMPLES as f32;
+
+        for (start, end) in contours.spans_at_y(sample, rule) {
+            let first = ((start + 0.5).floor() as i32).max(left);
+            let last = ((end + 0.5).floor() as i32).min(left + coverage.len() as i32 - 1);
+
+            for x in first..=last {
+                let overlap = end.min(x as f32 + 0.5) - start.max(x as f32 - 0.5);
+
+                if overlap > 0.0 {
+                    coverage[(x - left) as usize] += overlap / SAMPLES as f32;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::polygon::{MultiPolygon, Polygon},
+    };
+
+    fn square(min: f32, max: f32) -> Polygon {
+        Polygon::new([(min, min), (max, min), (max, max), (min, max)]).unwrap()
+    }
+
+    #[test]
+    fn from_contours() {
+        let mask = AlphaMask::from_contours(&square(2.0, 6.0), FillRule::NonZero, false, 100, 100);
+
+        //  Pixels are covered when their position lies inside of the square
+        assert_eq!(mask.get(2, 2), 255);
+        assert_eq!(mask.get(5, 5), 255);
+        assert_eq!(mask.get(6, 6), 0);
+        assert_eq!(mask.get(1, 3), 0);
+
+        let smooth = AlphaMask::from_contours(&square(3.0, 6.0), FillRule::NonZero, true, 100, 100);
+        assert_eq!(smooth.get(4, 4), 255);
+        assert_eq!(smooth.get(2, 4), 0);
+        assert!((100..160).contains(&smooth.get(3, 4)));
+    }
+
+    #[test]
+    fn limited_to_target() {
+        let mask = AlphaMask::from_contours(&square(-3e9, 3e9), FillRule::NonZero, false, 64, 32);
+        let rect = mask.rect().unwrap();
+
+        assert!(rect.width() <= 65 && rect.height() <= 33);
+        assert_eq!(mask.get(10, 10), 255);
+        assert_eq!(mask.get(100, 10), 0);
+
+        let outside =
+            AlphaMask::from_contours(&square(200.0, 300.0), FillRule::NonZero, false, 64, 32);
+        assert!(outside.is_empty());
+    }
+
+    #[test]
+    fn intersection() {
+        let a = AlphaMask::from_fn(0, 0, 4, 4, |_, _| 255);
+        let b = AlphaMask::from_fn(2, 2, 4, 4, |_, _| 128);
+        let both = a.intersection(&b);
+
+        assert_eq!(both.rect(), Some(Rect::new(2, 2, 2, 2)));
+        assert_eq!(both.get(3, 3), 128);
+        assert_eq!(both.get(1, 1), 0);
+        assert!(AlphaMask::new(0, 0, 2, 2, vec![0; 3]).is_err());
+    }
+
+    #[test]
+    fn coverage() {
+        //  Each pixel is covered by the part of the shape within half of a pixel of its position
+        let mut row = [0.0; 6];
+        row_coverage(&square(1.0, 3.25), FillRule::NonZero, 2, 0, &mut row);
+        assert_eq!(row[0], 0.0);
+        assert_eq!(row[1], 0.5);
+        assert_eq!(row[2], 1.0);
+        assert_eq!(row[3], 0.75);
+        assert_eq!(row[4], 0.0);
+
+        //  Holes follow the fill rule
+        let nested = MultiPolygon::new([square(0.0, 10.0), square(3.0, 7.0)]);
+        let even_odd = AlphaMask::from_contours(&nested, FillRule::EvenOdd, false, 16, 16);
+        let non_zero = AlphaMask::from_contours(&nested, FillRule::NonZero, false, 16, 16);
+        assert_eq!((even_odd.get(5, 5), even_odd.get(1, 5)), (0, 255));
+        assert_eq!(non_zero.get(5, 5), 255);
+
+        let mask = AlphaMask::from_fn(-2, 3, 4, 2, |x, y| (x + y) as u8);
+        assert_eq!(mask.bounds(), Bounds::new((-2.0, 3.0), (2.0, 5.0)));
+        assert_eq!(mask.get(1, 4), 5);
+        assert_eq!(mask.get(2, 4), 0);
+    }
+}
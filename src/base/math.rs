@@ -1,4 +1,30 @@
-use {sdl2::rect::Point, std::fmt::Display};
+mod bezier;
+mod bounds;
+mod clip;
+mod curve;
+mod expression;
+mod geometry;
+mod interval;
+mod matrix;
+mod parametric;
+mod quaternion;
+mod spline;
+mod transform;
+mod vector;
+
+use {
+    crate::error::{Error, Result},
+    sdl2::rect::Point,
+    std::{
+        fmt::{Debug, Display},
+        rc::Rc,
+        str::FromStr,
+    },
+};
+pub use {
+    bezier::*, bounds::*, clip::*, curve::*, expression::*, geometry::*, interval::*, matrix::*,
+    parametric::*, quaternion::*, spline::*, transform::*, vector::*,
+};
 
 #[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
 pub enum Variable {
@@ -16,66 +42,158 @@ impl Variable {
     }
 }
 
-impl ToString for Variable {
-    fn to_string(&self) -> String {
-        match self {
+impl Display for Variable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
             Variable::X => "x",
             Variable::Y => "y",
-        }
-        .to_string()
+        })
     }
 }
 
-#[derive(Debug)]
-pub struct Interval {
-    x: (i32, i32),
-    y: (i32, i32),
+/**
+ * A trigonometric function which can be scaled and shifted by `FunctionType::Trigonometric`
+ */
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Trig {
+    Sin,
+    Cos,
+    Tan,
 }
 
-impl Interval {
-    const fn new(x: (i32, i32), y: (i32, i32)) -> Self {
-        Self {
-            x: {
-                if x.0 > x.1 {
-                    (x.1, x.0)
-                } else {
-                    x
-                }
-            },
-            y: {
-                if y.0 > y.1 {
-                    (y.1, y.0)
-                } else {
-                    y
-                }
-            },
+impl Trig {
+    fn apply(&self, x: f32) -> f32 {
+        match self {
+            Self::Sin => x.sin(),
+            Self::Cos => x.cos(),
+            Self::Tan => x.tan(),
         }
     }
+}
 
-    fn within<T>(&self, point: T) -> bool
-    where
-        Point: From<T>,
-    {
-        let point = Point::from(point);
-        let (x, y) = (point.x(), point.y());
-        (x >= self.x.0 && x <= self.x.1) && (y >= self.y.0 && y <= self.y.1)
+impl Display for Trig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Sin => "sin",
+            Self::Cos => "cos",
+            Self::Tan => "tan",
+        })
     }
 }
 
-#[derive(Debug)]
+/**
+ * A function of `x` provided as a closure, for anything the other kinds of `FunctionType` cannot describe
+ */
+#[derive(Clone)]
+pub struct Closure(Rc<dyn Fn(f32) -> f32>);
+
+impl Closure {
+    pub fn new<F: Fn(f32) -> f32 + 'static>(f: F) -> Self {
+        Self(Rc::new(f))
+    }
+}
+
+impl Debug for Closure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Closure")
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum FunctionType {
     //  could create FunctionType as enum then place that as field into Function struct which could also have an interval field
-    Constant { var: Variable, val: i32 },
-    Linear { m: f32, b: f32 },
+    Constant {
+        var: Variable,
+        val: i32,
+    },
+    Linear {
+        m: f32,
+        b: f32,
+    },
+    /** The sum of each coefficient times `x` raised to its index */
+    Polynomial(Vec<f32>),
+    /** The quotient of two polynomials, undefined wherever the denominator is zero */
+    Rational {
+        numerator: Vec<f32>,
+        denominator: Vec<f32>,
+    },
+    /** `amplitude * trig(frequency * x + phase) + offset` */
+    Trigonometric {
+        trig: Trig,
+        amplitude: f32,
+        frequency: f32,
+        phase: f32,
+        offset: f32,
+    },
+    /** `a * e^(k * x) + c` */
+    Exponential {
+        a: f32,
+        k: f32,
+        c: f32,
+    },
+    /** Pieces which each apply from the first up to, but excluding, the second value of their range */
+    Piecewise(Vec<((f32, f32), FunctionType)>),
+    Closure(Closure),
+    /** A parsed expression in `x` */
+    Expression(Expr),
 }
 
 impl FunctionType {
+    /**
+     * The value of the function at `x`, if it is defined there as a function of `x`
+     */
+    pub fn eval(&self, x: f32) -> Option<f32> {
+        let value = match self {
+            Self::Constant { var, val } => var.is_y().then_some(*val as f32)?,
+            Self::Linear { m, b } => m * x + b,
+            Self::Polynomial(coefficients) => polynomial(coefficients, x),
+            Self::Rational {
+                numerator,
+                denominator,
+            } => {
+                let d = polynomial(denominator, x);
+                (d != 0.0).then(|| polynomial(numerator, x) / d)?
+            }
+            Self::Trigonometric {
+                trig,
+                amplitude,
+                frequency,
+                phase,
+                offset,
+            } => amplitude * trig.apply(frequency * x + phase) + offset,
+            Self::Exponential { a, k, c } => a * (k * x).exp() + c,
+            Self::Piecewise(pieces) => pieces
+                .iter()
+                .find(|((start, end), _)| x >= *start && x < *end)
+                .and_then(|(_, piece)| piece.eval(x))?,
+            Self::Closure(f) => (f.0)(x),
+            Self::Expression(e) => e.eval(x, 0.0),
+        };
+        value.is_finite().then_some(value)
+    }
+
+    /**
+     * The value of `x` where the function equals `y`, if there is exactly one
+     */
+    fn inverse(&self, y: f32) -> Option<f32> {
+        let value = match self {
+            Self::Linear { m, b } => (y - b) / m,
+            Self::Polynomial(coefficients) => match coefficients.as_slice() {
+                [b, m, rest @ ..] if rest.iter().all(|c| *c == 0.0) => (y - b) / m,
+                _ => None?,
+            },
+            Self::Exponential { a, k, c } => ((y - c) / a).ln() / k,
+            _ => None?,
+        };
+        value.is_finite().then_some(value)
+    }
+
     /** Return the evalulation of plugging in the provided `i32` value in relation to the specified `Variable` */
     fn solve(&self, v: Variable, n: i32) -> Option<i32> {
         match self {
             Self::Constant { var, val } => {
                 if var == &v {
-                    (val == &n).then(|| n) //  this is technically inf solutions
+                    (val == &n).then_some(n) //  this is technically inf solutions
                 } else {
                     Some(*val)
                 }
@@ -84,6 +202,11 @@ impl FunctionType {
                 Variable::X => m * (n as f32) + b,
                 Variable::Y => ((n as f32) - b) / m,
             } as i32),
+            _ => match v {
+                Variable::X => self.eval(n as f32),
+                Variable::Y => self.inverse(n as f32),
+            }
+            .map(|value| value.round() as i32),
         }
     }
 
@@ -96,18 +219,208 @@ impl FunctionType {
                 Variable::Y => &y == val,
             },
             Self::Linear { m, b } => y == ((m * x as f32) + b) as i32,
+            _ => self
+                .eval(x as f32)
+                .is_some_and(|value| value.round() as i32 == y),
+        }
+    }
+
+    /**
+     * The right hand side of the function, as it is shown after `f(x) = `
+     */
+    fn expression(&self) -> String {
+        match self {
+            Self::Constant { val, .. } => val.to_string(),
+            Self::Linear { m, b } => {
+                let mut data = String::new();
+
+                let b_is_normal = b.is_normal();
+
+                if *m != 0.0 {
+                    if *m != 1.0 {
+                        data.push_str(&m.to_string())
+                    }
+                    data.push('x');
+
+                    if b_is_normal {
+                        data.push_str(&format!(
+                            " {} ",
+                            if b.is_sign_positive() { '+' } else { '-' }
+                        ))
+                    }
+                }
+                if b_is_normal {
+                    data.push_str(&b.abs().to_string());
+                }
+                data
+            }
+            Self::Polynomial(coefficients) => polynomial_string(coefficients),
+            Self::Rational {
+                numerator,
+                denominator,
+            } => format!(
+                "({}) / ({})",
+                polynomial_string(numerator),
+                polynomial_string(denominator)
+            ),
+            Self::Trigonometric {
+                trig,
+                amplitude,
+                frequency,
+                phase,
+                offset,
+            } => {
+                let inner = polynomial_string(&[*phase, *frequency]);
+                offset_string(
+                    format!("{}{}({})", coefficient_string(*amplitude), trig, inner),
+                    *offset,
+                )
+            }
+            Self::Exponential { a, k, c } => offset_string(
+                format!(
+                    "{}e^({})",
+                    coefficient_string(*a),
+                    polynomial_string(&[0.0, *k])
+                ),
+                *c,
+            ),
+            Self::Piecewise(pieces) => format!(
+                "{{ {} }}",
+                pieces
+                    .iter()
+                    .map(|((start, end), piece)| format!(
+                        "{} for {} <= x < {}",
+                        piece.expression(),
+                        start,
+                        end
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            Self::Closure(_) => "<closure>".to_owned(),
+            Self::Expression(e) => e.to_string(),
+        }
+    }
+}
+
+impl FromStr for FunctionType {
+    type Err = Error;
+
+    /**
+     * Parse a function of `x` written as `y = ...`, `f(x) = ...` or as just the expression
+     */
+    fn from_str(s: &str) -> Result<Self> {
+        let (offset, rhs) = match s.split_once('=') {
+            Some((lhs, rhs)) => {
+                let lhs = lhs.split_whitespace().collect::<String>().to_lowercase();
+
+                if lhs != "y" && lhs != "f(x)" {
+                    return Err(ParseError {
+                        message: "Expected 'y' or 'f(x)' before '='".to_owned(),
+                        position: 0,
+                    }
+                    .into());
+                }
+                (s.len() - rhs.len(), rhs)
+            }
+            None => (0, s),
+        };
+
+        //  Report positions within the whole source rather than the right hand side
+        let expr = Expr::parse(rhs).map_err(|e| ParseError {
+            position: e.position + s[..offset].chars().count(),
+            ..e
+        })?;
+
+        if expr.uses(Variable::Y) {
+            return Err(ParseError {
+                message: "Expected a function of x, but found y".to_owned(),
+                position: s[..offset].chars().count(),
+            }
+            .into());
         }
+        Ok(Self::Expression(expr))
     }
 }
 
+impl Display for FunctionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Constant { var, val } => f.write_str(&format!("{} = {}", var, val)),
+            _ => f.write_str(&format!("f(x) = {}", self.expression())),
+        }
+    }
+}
+
+/**
+ * Evaluate a polynomial with the provided coefficients, lowest degree first, using Horner's method
+ */
+fn polynomial(coefficients: &[f32], x: f32) -> f32 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/**
+ * A coefficient written before a term, leaving out ones
+ */
+fn coefficient_string(c: f32) -> String {
+    match c {
+        _ if c == 1.0 => String::new(),
+        _ if c == -1.0 => "-".to_owned(),
+        _ => c.to_string(),
+    }
+}
+
+/**
+ * A term followed by a constant offset, leaving out zeros
+ */
+fn offset_string(term: String, offset: f32) -> String {
+    match offset {
+        _ if offset == 0.0 => term,
+        _ if offset < 0.0 => format!("{} - {}", term, -offset),
+        _ => format!("{} + {}", term, offset),
+    }
+}
+
+/**
+ * A polynomial written from its highest degree down, leaving out zero terms
+ */
+fn polynomial_string(coefficients: &[f32]) -> String {
+    let mut data = String::new();
+
+    for (degree, &c) in coefficients.iter().enumerate().rev() {
+        if c == 0.0 {
+            continue;
+        }
+        if data.is_empty() {
+            if c < 0.0 {
+                data.push('-')
+            }
+        } else {
+            data.push_str(if c < 0.0 { " - " } else { " + " })
+        }
+        if c.abs() != 1.0 || degree == 0 {
+            data.push_str(&c.abs().to_string())
+        }
+        match degree {
+            0 => (),
+            1 => data.push('x'),
+            _ => data.push_str(&format!("x^{}", degree)),
+        }
+    }
+    if data.is_empty() {
+        data.push('0')
+    }
+    data
+}
+
 #[derive(Debug)]
 pub struct Function {
     function: FunctionType,
-    interval: Interval,
+    interval: Interval2<f32>,
 }
 
 impl Function {
-    const fn new(function: FunctionType, interval: Interval) -> Self {
+    pub const fn new(function: FunctionType, interval: Interval2<f32>) -> Self {
         Self { function, interval }
     }
 
@@ -115,7 +428,10 @@ impl Function {
         &self.function
     }
 
-    pub const fn interval(&self) -> &Interval {
+    /**
+     * The region of the plane the function is restricted to
+     */
+    pub const fn interval(&self) -> &Interval2<f32> {
         &self.interval
     }
 
@@ -123,7 +439,8 @@ impl Function {
     where
         Point: From<T>,
     {
-        self.interval.within(point)
+        let point = Point::from(point);
+        self.interval.contains((point.x() as f32, point.y() as f32))
     }
 
     pub fn solve(&self, v: Variable, n: i32) -> Option<i32> {
@@ -133,46 +450,52 @@ impl Function {
     pub fn equal<T: Into<(i32, i32)>>(&self, point: T) -> bool {
         self.function.equal(point)
     }
+
+    /**
+     * The value of the function at `x`, if it is defined there and `x` lies within the interval
+     */
+    pub fn eval(&self, x: f32) -> Option<f32> {
+        self.interval
+            .x
+            .contains(x)
+            .then(|| self.function.eval(x))
+            .flatten()
+    }
 }
 
 impl Display for Function {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(&format!(
             "{} , [{} < x < {}] , [{} < y < {}]",
-            match self.function {
-                FunctionType::Constant { var, val } => format!("{} = {}", var.to_string(), val),
-                FunctionType::Linear { m, b } => {
-                    let mut data = "f(x) = ".to_owned();
-
-                    let b_is_normal = b.is_normal();
-
-                    if m != 0.0 {
-                        if m != 1.0 {
-                            data.push_str(&m.to_string())
-                        }
-                        data.push('x');
-
-                        if b_is_normal {
-                            data.push_str(&format!(
-                                " {} ",
-                                if b.is_sign_positive() { '+' } else { '-' }
-                            ))
-                        }
-                    }
-                    if b_is_normal {
-                        data.push_str(&b.abs().to_string());
-                    }
-                    data
-                }
-            },
-            self.interval.x.0,
-            self.interval.x.1,
-            self.interval.y.0,
-            self.interval.y.1
+            self.function,
+            self.interval.x.min(),
+            self.interval.x.max(),
+            self.interval.y.min(),
+            self.interval.y.max()
         ))
     }
 }
 
+impl FromStr for Function {
+    type Err = Error;
+
+    /**
+     * Parse a function of `x` which is not restricted to any interval
+     */
+    fn from_str(s: &str) -> Result<Self> {
+        s.parse::<FunctionType>().map(Self::from)
+    }
+}
+
+impl From<FunctionType> for Function {
+    /**
+     * A function which is not restricted to any interval
+     */
+    fn from(function: FunctionType) -> Self {
+        Self::new(function, Interval2::unbounded())
+    }
+}
+
 impl<T: Into<Point>> From<(T, T)> for Function {
     fn from(pair: (T, T)) -> Self {
         let (p1, p2) = (pair.0.into(), pair.1.into());
@@ -197,7 +520,151 @@ impl<T: Into<Point>> From<(T, T)> for Function {
 
                 FunctionType::Linear { m, b }
             },
-            Interval::new((x1, x2), (y1, y2)),
+            Interval2::new((x1 as f32, x2 as f32), (y1 as f32, y2 as f32)),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_display() {
+        let function = Function::from(((0, 1), (4, 9)));
+        assert_eq!(
+            function.to_string(),
+            "f(x) = 2x + 1 , [0 < x < 4] , [1 < y < 9]"
+        );
+    }
+
+    #[test]
+    fn eval() {
+        let cubic = FunctionType::Polynomial(vec![1.0, 0.0, -2.0, 1.0]);
+        assert_eq!(cubic.eval(2.0), Some(1.0));
+        assert_eq!(cubic.eval(-1.0), Some(-2.0));
+
+        //  Rational functions are undefined wherever their denominator is zero
+        let rational = FunctionType::Rational {
+            numerator: vec![1.0],
+            denominator: vec![-1.0, 1.0],
+        };
+        assert_eq!(rational.eval(3.0), Some(0.5));
+        assert_eq!(rational.eval(1.0), None);
+
+        let trig = FunctionType::Trigonometric {
+            trig: Trig::Sin,
+            amplitude: 2.0,
+            frequency: 0.5,
+            phase: 0.0,
+            offset: 1.0,
+        };
+        assert!((trig.eval(std::f32::consts::PI).unwrap() - 3.0).abs() < 1e-6);
+
+        let exponential = FunctionType::Exponential {
+            a: 2.0,
+            k: 1.0,
+            c: -1.0,
+        };
+        assert_eq!(exponential.eval(0.0), Some(1.0));
+        assert_eq!(exponential.eval(1000.0), None);
+
+        //  Pieces apply up to, but excluding, the end of their range
+        let piecewise = FunctionType::Piecewise(vec![
+            ((0.0, 1.0), FunctionType::Linear { m: 1.0, b: 0.0 }),
+            ((1.0, 2.0), FunctionType::Polynomial(vec![5.0])),
+        ]);
+        assert_eq!(piecewise.eval(0.5), Some(0.5));
+        assert_eq!(piecewise.eval(1.0), Some(5.0));
+        assert_eq!(piecewise.eval(2.0), None);
+
+        let closure = FunctionType::Closure(Closure::new(|x| x.sqrt()));
+        assert_eq!(closure.eval(9.0), Some(3.0));
+        assert_eq!(closure.eval(-1.0), None);
+
+        //  Vertical lines are not functions of `x`
+        let vertical = FunctionType::Constant {
+            var: Variable::X,
+            val: 3,
+        };
+        assert_eq!(vertical.eval(3.0), None);
+    }
+
+    #[test]
+    fn solve() {
+        let line = FunctionType::Polynomial(vec![1.0, 2.0]);
+        assert_eq!(line.solve(Variable::X, 3), Some(7));
+        assert_eq!(line.solve(Variable::Y, 7), Some(3));
+
+        let exponential = FunctionType::Exponential {
+            a: 1.0,
+            k: 1.0,
+            c: 0.0,
+        };
+        assert_eq!(exponential.solve(Variable::Y, 20), Some(3));
+        assert_eq!(exponential.solve(Variable::Y, -1), None);
+
+        //  Curves with several values of `x` for some `y` are only solved for `y`
+        let parabola = FunctionType::Polynomial(vec![0.0, 0.0, 1.0]);
+        assert_eq!(parabola.solve(Variable::X, -3), Some(9));
+        assert_eq!(parabola.solve(Variable::Y, 9), None);
+
+        assert!(parabola.equal((3, 9)));
+        assert!(!parabola.equal((3, 8)));
+        assert!(FunctionType::Constant {
+            var: Variable::X,
+            val: 2
+        }
+        .equal((2, 100)));
+    }
+
+    #[test]
+    fn display() {
+        let display = |function: FunctionType| function.to_string();
+
+        assert_eq!(
+            display(FunctionType::Polynomial(vec![-1.0, 0.0, 3.0, -1.0])),
+            "f(x) = -x^3 + 3x^2 - 1"
+        );
+        assert_eq!(display(FunctionType::Polynomial(vec![])), "f(x) = 0");
+        assert_eq!(
+            display(FunctionType::Rational {
+                numerator: vec![1.0],
+                denominator: vec![0.0, 2.0],
+            }),
+            "f(x) = (1) / (2x)"
+        );
+        assert_eq!(
+            display(FunctionType::Trigonometric {
+                trig: Trig::Cos,
+                amplitude: -1.0,
+                frequency: 2.0,
+                phase: 1.5,
+                offset: -0.5,
+            }),
+            "f(x) = -cos(2x + 1.5) - 0.5"
+        );
+        assert_eq!(
+            display(FunctionType::Exponential {
+                a: 3.0,
+                k: -1.0,
+                c: 2.0,
+            }),
+            "f(x) = 3e^(-x) + 2"
+        );
+        assert_eq!(
+            display(FunctionType::Piecewise(vec![(
+                (0.0, 1.0),
+                FunctionType::Linear { m: 1.0, b: 0.0 }
+            )])),
+            "f(x) = { x for 0 <= x < 1 }"
+        );
+        assert_eq!(
+            display(FunctionType::Constant {
+                var: Variable::Y,
+                val: 4
+            }),
+            "y = 4"
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use super::{Transform2D, Vec2};
+
+/**
+ * The deepest subdivision used when flattening, which bounds the output of degenerate curves
+ */
+const MAX_DEPTH: u32 = 16;
+
+/**
+ * A quadratic Bézier curve from `p0` to `p2`, pulled towards the control point `p1`
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct QuadBezier {
+    pub p0: Vec2,
+    pub p1: Vec2,
+    pub p2: Vec2,
+}
+
+impl QuadBezier {
+    pub fn new<T: Into<Vec2>>(p0: T, p1: T, p2: T) -> Self {
+        Self {
+            p0: p0.into(),
+            p1: p1.into(),
+            p2: p2.into(),
+        }
+    }
+
+    /**
+     * The point on the curve at the parameter `t`, where `0` is the start and `1` is the end
+     */
+    pub fn eval(&self, t: f32) -> Vec2 {
+        let u = 1.0 - t;
+        self.p0 * (u * u) + self.p1 * (2.0 * u * t) + self.p2 * (t * t)
+    }
+
+    /**
+     * The tangent of the curve at the parameter `t`
+     */
+    pub fn derivative(&self, t: f32) -> Vec2 {
+        (self.p1 - self.p0) * (2.0 * (1.0 - t)) + (self.p2 - self.p1) * (2.0 * t)
+    }
+
+    /**
+     * Split the curve into two halves at the parameter `t` using de Casteljau's algorithm
+     */
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let a = lerp(self.p0, self.p1, t);
+        let b = lerp(self.p1, self.p2, t);
+        let m = lerp(a, b, t);
+        (Self::new(self.p0, a, m), Self::new(m, b, self.p2))
+    }
+
+    /**
+     * Raise the degree of the curve without changing its shape
+     */
+    pub fn to_cubic(&self) -> CubicBezier {
+        CubicBezier::new(
+            self.p0,
+            self.p0 + (self.p1 - self.p0) * (2.0 / 3.0),
+            self.p2 + (self.p1 - self.p2) * (2.0 / 3.0),
+            self.p2,
+        )
+    }
+
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        Self::new(
+            transform.apply(self.p0),
+            transform.apply(self.p1),
+            transform.apply(self.p2),
+        )
+    }
+
+    /**
+     * Approximate the curve with straight segments which stay within `tolerance` of it, from `p0` to `p2` inclusive
+     */
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        let mut points = vec![self.p0];
+        self.flatten_into(tolerance, MAX_DEPTH, &mut points);
+        points
+    }
+
+    fn flatten_into(&self, tolerance: f32, depth: u32, points: &mut Vec<Vec2>) {
+        //  The curve strays at most half of the way to the control point from the midpoint of its chord
+        let deviation = (self.p0 - self.p1 * 2.0 + self.p2).length() / 4.0;
+
+        if depth == 0 || deviation <= tolerance {
+            points.push(self.p2)
+        } else {
+            let (first, second) = self.split(0.5);
+            first.flatten_into(tolerance, depth - 1, points);
+            second.flatten_into(tolerance, depth - 1, points);
+        }
+    }
+}
+
+/**
+ * A cubic Bézier curve from `p0` to `p3`, leaving towards `p1` and arriving from `p2`
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CubicBezier {
+    pub p0: Vec2,
+    pub p1: Vec2,
+    pub p2: Vec2,
+    pub p3: Vec2,
+}
+
+impl CubicBezier {
+    pub fn new<T: Into<Vec2>>(p0: T, p1: T, p2: T, p3: T) -> Self {
+        Self {
+            p0: p0.into(),
+            p1: p1.into(),
+            p2: p2.into(),
+            p3: p3.into(),
+        }
+    }
+
+    /**
+     * The point on the curve at the parameter `t`, where `0` is the start and `1` is the end
+     */
+    pub fn eval(&self, t: f32) -> Vec2 {
+        let u = 1.0 - t;
+        self.p0 * (u * u * u)
+            + self.p1 * (3.0 * u * u * t)
+            + self.p2 * (3.0 * u * t * t)
+            + self.p3 * (t * t * t)
+    }
+
+    /**
+     * The tangent of the curve at the parameter `t`
+     */
+    pub fn derivative(&self, t: f32) -> Vec2 {
+        let u = 1.0 - t;
+        (self.p1 - self.p0) * (3.0 * u * u)
+            + (self.p2 - self.p1) * (6.0 * u * t)
+            + (self.p3 - self.p2) * (3.0 * t * t)
+    }
+
+    /**
+     * Split the curve into two halves at the parameter `t` using de Casteljau's algorithm
+     */
+    pub fn split(&self, t: f32) -> (Self, Self) {
+        let a = lerp(self.p0, self.p1, t);
+        let b = lerp(self.p1, self.p2, t);
+        let c = lerp(self.p2, self.p3, t);
+        let ab = lerp(a, b, t);
+        let bc = lerp(b, c, t);
+        let m = lerp(ab, bc, t);
+        (Self::new(self.p0, a, ab, m), Self::new(m, bc, c, self.p3))
+    }
+
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        Self::new(
+            transform.apply(self.p0),
+            transform.apply(self.p1),
+            transform.apply(self.p2),
+            transform.apply(self.p3),
+        )
+    }
+
+    /**
+     * Approximate the curve with straight segments which stay within `tolerance` of it, from `p0` to `p3` inclusive
+     */
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        let mut points = vec![self.p0];
+        self.flatten_into(tolerance, MAX_DEPTH, &mut points);
+        points
+    }
+
+    fn flatten_into(&self, tolerance: f32, depth: u32, points: &mut Vec<Vec2>) {
+        //  A bound on the distance between the curve and its chord, from the second differences of the control points
+        let deviation = (self.p0 - self.p1 * 2.0 + self.p2)
+            .length()
+            .max((self.p1 - self.p2 * 2.0 + self.p3).length())
+            * 0.75;
+
+        if depth == 0 || deviation <= tolerance {
+            points.push(self.p3)
+        } else {
+            let (first, second) = self.split(0.5);
+            first.flatten_into(tolerance, depth - 1, points);
+            second.flatten_into(tolerance, depth - 1, points);
+        }
+    }
+}
+
+fn lerp(a: Vec2, b: Vec2, t: f32) -> Vec2 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * The distance from a point to the nearest segment of a polyline
+     */
+    fn distance(points: &[Vec2], p: Vec2) -> f32 {
+        points
+            .windows(2)
+            .map(|w| {
+                let d = w[1] - w[0];
+                let t = ((p - w[0]).dot(d) / d.length_squared()).clamp(0.0, 1.0);
+                p.distance(w[0] + d * t)
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    #[test]
+    fn quadratic() {
+        let curve = QuadBezier::new((0.0, 0.0), (2.0, 4.0), (4.0, 0.0));
+        assert_eq!(curve.eval(0.0), curve.p0);
+        assert_eq!(curve.eval(1.0), curve.p2);
+        assert_eq!(curve.eval(0.5), Vec2::new(2.0, 2.0));
+        assert_eq!(curve.derivative(0.0), Vec2::new(4.0, 8.0));
+        assert_eq!(curve.derivative(0.5), Vec2::new(4.0, 0.0));
+
+        //  Both halves of a split follow the original curve
+        let (first, second) = curve.split(0.25);
+        assert_eq!(first.p2, second.p0);
+        assert!((first.eval(0.5) - curve.eval(0.125)).length() < 1e-5);
+        assert!((second.eval(0.5) - curve.eval(0.625)).length() < 1e-5);
+
+        let cubic = curve.to_cubic();
+        for t in [0.0, 0.3, 0.5, 0.9] {
+            assert!((cubic.eval(t) - curve.eval(t)).length() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn cubic() {
+        let curve = CubicBezier::new((0.0, 0.0), (0.0, 3.0), (3.0, 3.0), (3.0, 0.0));
+        assert_eq!(curve.eval(0.5), Vec2::new(1.5, 2.25));
+        assert_eq!(curve.derivative(0.0), Vec2::new(0.0, 9.0));
+        assert_eq!(curve.derivative(1.0), Vec2::new(0.0, -9.0));
+
+        let (first, second) = curve.split(0.5);
+        assert_eq!(first.p3, curve.eval(0.5));
+        assert!((second.eval(0.5) - curve.eval(0.75)).length() < 1e-5);
+
+        let moved = curve.transform(&Transform2D::translation(1.0, 2.0));
+        assert_eq!(moved.eval(0.5), Vec2::new(2.5, 4.25));
+    }
+
+    #[test]
+    fn flatten() {
+        let quad = QuadBezier::new((0.0, 0.0), (50.0, 100.0), (100.0, 0.0));
+        let cubic = CubicBezier::new((0.0, 0.0), (0.0, 100.0), (100.0, -100.0), (100.0, 0.0));
+
+        for tolerance in [0.25, 2.0] {
+            let points = quad.flatten(tolerance);
+            assert_eq!((points[0], *points.last().unwrap()), (quad.p0, quad.p2));
+            assert!((0..=100).all(|k| distance(&points, quad.eval(k as f32 / 100.0)) <= tolerance));
+
+            let points = cubic.flatten(tolerance);
+            assert_eq!((points[0], *points.last().unwrap()), (cubic.p0, cubic.p3));
+            assert!((0..=100).all(|k| distance(&points, cubic.eval(k as f32 / 100.0)) <= tolerance));
+        }
+
+        //  Finer tolerances need more segments, but straight curves need only one
+        assert!(quad.flatten(0.25).len() > quad.flatten(2.0).len());
+        let straight = CubicBezier::new((0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0));
+        assert_eq!(straight.flatten(0.25).len(), 2);
+
+        //  Degenerate curves stop subdividing at the deepest level
+        let huge = QuadBezier::new((0.0, 0.0), (f32::MAX, 0.0), (0.0, 1.0));
+        assert!(huge.flatten(0.25).len() <= (1 << MAX_DEPTH) + 1);
+    }
+}
@@ -0,0 +1,111 @@
+use super::{Transform2D, Vec2};
+
+/**
+ * An axis-aligned bounding box described by its minimum and maximum corners
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Bounds {
+    pub fn new<T: Into<Vec2>>(a: T, b: T) -> Self {
+        let (a, b) = (a.into(), b.into());
+        Self {
+            min: Vec2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Vec2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    /**
+     * The smallest bounds containing every provided point, if there are any
+     */
+    pub fn from_points<A: Into<Vec2>, T: IntoIterator<Item = A>>(iter: T) -> Option<Self> {
+        let mut iter = iter.into_iter().map(Into::into);
+        let first = iter.next()?;
+
+        Some(iter.fold(Self::new(first, first), |bounds, p| {
+            bounds.union(&Self::new(p, p))
+        }))
+    }
+
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    /**
+     * The four corners, clockwise from the minimum in a y-down space
+     */
+    pub fn corners(&self) -> [Vec2; 4] {
+        [
+            self.min,
+            Vec2::new(self.max.x, self.min.y),
+            self.max,
+            Vec2::new(self.min.x, self.max.y),
+        ]
+    }
+
+    /**
+     * Return `true` if the point lies inside of the bounds or on its border
+     */
+    pub fn contains<T: Into<Vec2>>(&self, point: T) -> bool {
+        let p = point.into();
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /**
+     * Return `true` if the two bounds overlap or touch
+     */
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.x <= other.max.x
+            && self.max.x >= other.min.x
+            && self.min.y <= other.max.y
+            && self.max.y >= other.min.y
+    }
+
+    /**
+     * The smallest bounds containing both bounds
+     */
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: Vec2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            max: Vec2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        }
+    }
+
+    /**
+     * The bounds of the area shared by both bounds, if they overlap
+     */
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        self.intersects(other).then(|| Self {
+            min: Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            max: Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        })
+    }
+
+    /**
+     * Return the bounds grown by `amount` on every side
+     */
+    pub fn expand(&self, amount: f32) -> Self {
+        Self {
+            min: self.min - Vec2::new(amount, amount),
+            max: self.max + Vec2::new(amount, amount),
+        }
+    }
+
+    /**
+     * The bounds of the corners after passing through the provided transform
+     */
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        Self::from_points(self.corners().map(|c| transform.apply(c))).unwrap()
+    }
+}
@@ -0,0 +1,208 @@
+use super::{Bounds, Vec2};
+
+const LEFT: u8 = 1;
+const RIGHT: u8 = 2;
+const BOTTOM: u8 = 4;
+const TOP: u8 = 8;
+
+/**
+ * The sides of the bounds which a point lies beyond, as used by the Cohen-Sutherland algorithm
+ */
+fn outcode(p: Vec2, bounds: &Bounds) -> u8 {
+    let mut code = 0;
+
+    if p.x < bounds.min.x {
+        code |= LEFT
+    } else if p.x > bounds.max.x {
+        code |= RIGHT
+    }
+    if p.y < bounds.min.y {
+        code |= BOTTOM
+    } else if p.y > bounds.max.y {
+        code |= TOP
+    }
+    code
+}
+
+/**
+ * Clip the line from `a` to `b` to the provided bounds using the Cohen-Sutherland algorithm, returning `None` if none of it lies within them
+ *
+ * Lines entirely inside or outside of the bounds are settled from the outcodes of their ends alone, which makes this quick when most lines are one or the other
+ */
+pub fn cohen_sutherland(mut a: Vec2, mut b: Vec2, bounds: &Bounds) -> Option<(Vec2, Vec2)> {
+    let (mut code_a, mut code_b) = (outcode(a, bounds), outcode(b, bounds));
+
+    loop {
+        if code_a | code_b == 0 {
+            return Some((a, b));
+        }
+        if code_a & code_b != 0 {
+            return None;
+        }
+
+        //  Move whichever end lies outside onto the first side it lies beyond
+        let code = if code_a != 0 { code_a } else { code_b };
+        let d = b - a;
+        let p = if code & TOP != 0 {
+            Vec2::new(a.x + d.x * (bounds.max.y - a.y) / d.y, bounds.max.y)
+        } else if code & BOTTOM != 0 {
+            Vec2::new(a.x + d.x * (bounds.min.y - a.y) / d.y, bounds.min.y)
+        } else if code & RIGHT != 0 {
+            Vec2::new(bounds.max.x, a.y + d.y * (bounds.max.x - a.x) / d.x)
+        } else {
+            Vec2::new(bounds.min.x, a.y + d.y * (bounds.min.x - a.x) / d.x)
+        };
+
+        if code == code_a {
+            a = p;
+            code_a = outcode(a, bounds);
+        } else {
+            b = p;
+            code_b = outcode(b, bounds);
+        }
+    }
+}
+
+/**
+ * Clip the line from `a` to `b` to the provided bounds using the Liang-Barsky algorithm, returning `None` if none of it lies within them
+ *
+ * Ends which lie within the bounds are returned exactly as they were provided
+ */
+pub fn liang_barsky(a: Vec2, b: Vec2, bounds: &Bounds) -> Option<(Vec2, Vec2)> {
+    let d = b - a;
+    let (mut enter, mut exit) = (0f32, 1f32);
+
+    //  Each side bounds the parameter along the line from one direction, depending on which way the line crosses it
+    for (p, q) in [
+        (-d.x, a.x - bounds.min.x),
+        (d.x, bounds.max.x - a.x),
+        (-d.y, a.y - bounds.min.y),
+        (d.y, bounds.max.y - a.y),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else {
+            let t = q / p;
+
+            if p < 0.0 {
+                enter = enter.max(t)
+            } else {
+                exit = exit.min(t)
+            }
+        }
+    }
+
+    if enter > exit {
+        return None;
+    }
+    Some((
+        if enter > 0.0 { a + d * enter } else { a },
+        if exit < 1.0 { a + d * exit } else { b },
+    ))
+}
+
+/**
+ * Clip a series of connected lines to the provided bounds, returning the runs of connected points which remain within them
+ */
+pub fn clip_polyline(points: &[Vec2], bounds: &Bounds) -> Vec<Vec<Vec2>> {
+    let mut runs: Vec<Vec<Vec2>> = Vec::new();
+    let mut connected = false;
+
+    for pair in points.windows(2) {
+        match liang_barsky(pair[0], pair[1], bounds) {
+            Some((start, end)) => {
+                //  A line continues the current run only if its start was left where the last line ended
+                match runs.last_mut() {
+                    Some(run) if connected && start == pair[0] => run.push(end),
+                    _ => runs.push(vec![start, end]),
+                }
+                connected = end == pair[1];
+            }
+            None => connected = false,
+        }
+    }
+
+    //  A single point is a line which starts and ends in the same place
+    if let [p] = points {
+        if bounds.contains(*p) {
+            runs.push(vec![*p])
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds() -> Bounds {
+        Bounds::new((0.0, 0.0), (10.0, 10.0))
+    }
+
+    #[test]
+    fn lines() {
+        let p = |x, y| Vec2::new(x, y);
+        let bounds = bounds();
+
+        for clip in [cohen_sutherland, liang_barsky] {
+            //  Lines inside are kept as they are and lines beyond a side are dropped
+            assert_eq!(
+                clip(p(1.0, 1.0), p(9.0, 2.0), &bounds),
+                Some((p(1.0, 1.0), p(9.0, 2.0)))
+            );
+            assert_eq!(clip(p(-5.0, 1.0), p(-1.0, 9.0), &bounds), None);
+            assert_eq!(clip(p(-5.0, 8.0), p(3.0, 16.0), &bounds), None);
+
+            assert_eq!(
+                clip(p(-5.0, 5.0), p(15.0, 5.0), &bounds),
+                Some((p(0.0, 5.0), p(10.0, 5.0)))
+            );
+            assert_eq!(
+                clip(p(5.0, 5.0), p(5.0, -5.0), &bounds),
+                Some((p(5.0, 5.0), p(5.0, 0.0)))
+            );
+
+            let (a, b) = clip(p(-2.0, 1.0), p(12.0, 8.0), &bounds).unwrap();
+            assert!(a.distance(p(0.0, 2.0)) < 1e-5 && b.distance(p(10.0, 7.0)) < 1e-5);
+
+            //  Lines along a side are inside of the bounds
+            assert_eq!(
+                clip(p(-1.0, 0.0), p(4.0, 0.0), &bounds),
+                Some((p(0.0, 0.0), p(4.0, 0.0)))
+            );
+        }
+    }
+
+    #[test]
+    fn polylines() {
+        let p = |x, y| Vec2::new(x, y);
+        let bounds = bounds();
+
+        //  Leaving and coming back splits the polyline into separate runs
+        let runs = clip_polyline(
+            &[
+                p(1.0, 1.0),
+                p(5.0, 1.0),
+                p(5.0, 15.0),
+                p(8.0, 15.0),
+                p(8.0, 5.0),
+                p(9.0, 5.0),
+            ],
+            &bounds,
+        );
+        assert_eq!(
+            runs,
+            [
+                vec![p(1.0, 1.0), p(5.0, 1.0), p(5.0, 10.0)],
+                vec![p(8.0, 10.0), p(8.0, 5.0), p(9.0, 5.0)],
+            ]
+        );
+
+        assert!(clip_polyline(&[p(-1.0, -1.0), p(-5.0, 20.0)], &bounds).is_empty());
+        assert_eq!(clip_polyline(&[p(3.0, 3.0)], &bounds), [vec![p(3.0, 3.0)]]);
+        assert!(clip_polyline(&[p(30.0, 3.0)], &bounds).is_empty());
+        assert!(clip_polyline(&[], &bounds).is_empty());
+    }
+}
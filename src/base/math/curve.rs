@@ -0,0 +1,233 @@
+use super::{CubicBezier, QuadBezier, Vec2};
+
+/**
+ * The deepest subdivision used when flattening or measuring, which bounds the work done on degenerate curves
+ */
+const MAX_DEPTH: u32 = 16;
+
+/**
+ * The nodes and weights of five point Gauss-Legendre quadrature on `[-1, 1]`
+ */
+const GAUSS: [(f32, f32); 5] = [
+    (0.0, 0.568_888_9),
+    (-0.538_469_3, 0.478_628_7),
+    (0.538_469_3, 0.478_628_7),
+    (-0.906_179_8, 0.236_926_9),
+    (0.906_179_8, 0.236_926_9),
+];
+
+/**
+ * A parametric curve in the plane, defined over a closed range of its parameter
+ */
+pub trait Curve {
+    /**
+     * The first and last values of the parameter
+     */
+    fn domain(&self) -> (f32, f32);
+
+    /**
+     * The point on the curve at the parameter `t`
+     */
+    fn eval(&self, t: f32) -> Vec2;
+
+    /**
+     * The tangent of the curve at the parameter `t`
+     */
+    fn derivative(&self, t: f32) -> Vec2;
+
+    /**
+     * The length of the curve
+     */
+    fn arc_length(&self) -> f32 {
+        let (start, end) = self.domain();
+        self.length_between(start, end)
+    }
+
+    /**
+     * The length of the curve between two values of the parameter
+     */
+    fn length_between(&self, a: f32, b: f32) -> f32 {
+        let estimate = gauss(self, a, b);
+        adaptive_length(self, a, b, estimate, MAX_DEPTH)
+    }
+
+    /**
+     * The parameter of the point which lies `length` along the curve from its start, clamped to the domain
+     */
+    fn param_at_length(&self, length: f32) -> f32 {
+        let (start, end) = self.domain();
+        let total = self.arc_length();
+
+        if length <= 0.0 || total <= 0.0 {
+            return start;
+        } else if length >= total {
+            return end;
+        }
+
+        //  Newton's method on the length, falling back to bisection whenever a step leaves the bracket
+        let (mut lo, mut hi) = (start, end);
+        let mut t = start + (end - start) * length / total;
+
+        for _ in 0..MAX_DEPTH {
+            let error = self.length_between(start, t) - length;
+
+            if error.abs() <= total * 1e-5 {
+                break;
+            } else if error > 0.0 {
+                hi = t
+            } else {
+                lo = t
+            }
+            let speed = self.derivative(t).length();
+            let next = t - error / speed;
+
+            t = if speed > 0.0 && next > lo && next < hi {
+                next
+            } else {
+                (lo + hi) / 2.0
+            };
+        }
+        t
+    }
+
+    /**
+     * The point which lies `length` along the curve from its start
+     */
+    fn eval_at_length(&self, length: f32) -> Vec2 {
+        self.eval(self.param_at_length(length))
+    }
+
+    /**
+     * Approximate the curve with straight segments which stay within `tolerance` of it, from its start to its end inclusive
+     */
+    fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        let (start, end) = self.domain();
+        let mut points = vec![self.eval(start)];
+
+        //  Begin from a few pieces so that curves which cross their own chord are not mistaken for lines
+        const PIECES: usize = 8;
+        for k in 0..PIECES {
+            let a = start + (end - start) * k as f32 / PIECES as f32;
+            let b = start + (end - start) * (k + 1) as f32 / PIECES as f32;
+            subdivide(self, a, b, tolerance, MAX_DEPTH, &mut points);
+        }
+        points
+    }
+}
+
+/**
+ * The length of the curve over `[a, b]` using five point Gauss-Legendre quadrature
+ */
+fn gauss<C: Curve + ?Sized>(curve: &C, a: f32, b: f32) -> f32 {
+    let (half, mid) = ((b - a) / 2.0, (a + b) / 2.0);
+    GAUSS
+        .iter()
+        .map(|&(x, w)| w * curve.derivative(mid + half * x).length())
+        .sum::<f32>()
+        * half
+}
+
+/**
+ * Refine the length of the curve over `[a, b]` until halving the interval no longer changes it
+ */
+fn adaptive_length<C: Curve + ?Sized>(curve: &C, a: f32, b: f32, whole: f32, depth: u32) -> f32 {
+    let mid = (a + b) / 2.0;
+    let (left, right) = (gauss(curve, a, mid), gauss(curve, mid, b));
+
+    if depth == 0 || (left + right - whole).abs() <= 1e-5 * (left + right).max(1.0) {
+        left + right
+    } else {
+        adaptive_length(curve, a, mid, left, depth - 1)
+            + adaptive_length(curve, mid, b, right, depth - 1)
+    }
+}
+
+/**
+ * Push the end of `[a, b]`, after splitting it for as long as its middle strays further than `tolerance` from the chord
+ */
+fn subdivide<C: Curve + ?Sized>(
+    curve: &C,
+    a: f32,
+    b: f32,
+    tolerance: f32,
+    depth: u32,
+    points: &mut Vec<Vec2>,
+) {
+    let (p, q) = (curve.eval(a), curve.eval(b));
+    let mid = (a + b) / 2.0;
+    let m = curve.eval(mid);
+
+    let chord = q - p;
+    let deviation = if chord.length_squared() > 0.0 {
+        (m - p).cross(chord).abs() / chord.length()
+    } else {
+        m.distance(p)
+    };
+
+    if depth == 0 || deviation <= tolerance {
+        points.push(q)
+    } else {
+        subdivide(curve, a, mid, tolerance, depth - 1, points);
+        subdivide(curve, mid, b, tolerance, depth - 1, points);
+    }
+}
+
+impl Curve for QuadBezier {
+    fn domain(&self) -> (f32, f32) {
+        (0.0, 1.0)
+    }
+
+    fn eval(&self, t: f32) -> Vec2 {
+        QuadBezier::eval(self, t)
+    }
+
+    fn derivative(&self, t: f32) -> Vec2 {
+        QuadBezier::derivative(self, t)
+    }
+
+    fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        QuadBezier::flatten(self, tolerance)
+    }
+}
+
+impl Curve for CubicBezier {
+    fn domain(&self) -> (f32, f32) {
+        (0.0, 1.0)
+    }
+
+    fn eval(&self, t: f32) -> Vec2 {
+        CubicBezier::eval(self, t)
+    }
+
+    fn derivative(&self, t: f32) -> Vec2 {
+        CubicBezier::derivative(self, t)
+    }
+
+    fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        CubicBezier::flatten(self, tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arc_length() {
+        let curve = CubicBezier::new((0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 4.0));
+        let straight = QuadBezier::new((0.0, 0.0), (1.5, 2.0), (3.0, 4.0));
+        assert!((straight.arc_length() - 5.0).abs() < 1e-4);
+        assert!(curve.arc_length() > 5.0);
+
+        //  The parameter at a length lands on the point that far along the curve
+        for length in [0.0, 1.0, 2.5, 4.9] {
+            let t = curve.param_at_length(length);
+            assert!((curve.length_between(0.0, t) - length).abs() < 1e-3);
+        }
+        assert_eq!(curve.param_at_length(-1.0), 0.0);
+        assert_eq!(curve.param_at_length(100.0), 1.0);
+
+        //  An evenly spaced curve lands halfway along at its middle
+        assert!((straight.eval_at_length(2.5) - Vec2::new(1.5, 2.0)).length() < 1e-3);
+    }
+}
@@ -0,0 +1,645 @@
+use {
+    super::Variable,
+    std::{fmt::Display, str::FromStr},
+};
+
+/**
+ * A problem found while parsing an expression, along with the character position where it was found
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub position: usize,
+}
+
+impl ParseError {
+    fn new<T: Into<String>>(message: T, position: usize) -> Self {
+        Self {
+            message: message.into(),
+            position,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("{} at position {}", self.message, self.position))
+    }
+}
+
+/**
+ * A named constant which can be used in expressions
+ */
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum Constant {
+    Pi,
+    Tau,
+    E,
+}
+
+impl Constant {
+    pub fn value(&self) -> f32 {
+        match self {
+            Self::Pi => std::f32::consts::PI,
+            Self::Tau => std::f32::consts::TAU,
+            Self::E => std::f32::consts::E,
+        }
+    }
+}
+
+/**
+ * A standard function of one argument which can be used in expressions
+ */
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum MathFn {
+    Sin,
+    Cos,
+    Tan,
+    Asin,
+    Acos,
+    Atan,
+    Sinh,
+    Cosh,
+    Tanh,
+    Exp,
+    Ln,
+    Log,
+    Sqrt,
+    Abs,
+    Floor,
+    Ceil,
+}
+
+impl MathFn {
+    pub fn apply(&self, x: f32) -> f32 {
+        match self {
+            Self::Sin => x.sin(),
+            Self::Cos => x.cos(),
+            Self::Tan => x.tan(),
+            Self::Asin => x.asin(),
+            Self::Acos => x.acos(),
+            Self::Atan => x.atan(),
+            Self::Sinh => x.sinh(),
+            Self::Cosh => x.cosh(),
+            Self::Tanh => x.tanh(),
+            Self::Exp => x.exp(),
+            Self::Ln => x.ln(),
+            Self::Log => x.log10(),
+            Self::Sqrt => x.sqrt(),
+            Self::Abs => x.abs(),
+            Self::Floor => x.floor(),
+            Self::Ceil => x.ceil(),
+        }
+    }
+}
+
+/**
+ * Every name recognized in an expression, longest first so that names which begin with others are matched whole
+ */
+const NAMES: [(&str, Name); 19] = [
+    ("asin", Name::Fn(MathFn::Asin)),
+    ("acos", Name::Fn(MathFn::Acos)),
+    ("atan", Name::Fn(MathFn::Atan)),
+    ("sinh", Name::Fn(MathFn::Sinh)),
+    ("cosh", Name::Fn(MathFn::Cosh)),
+    ("tanh", Name::Fn(MathFn::Tanh)),
+    ("sqrt", Name::Fn(MathFn::Sqrt)),
+    ("ceil", Name::Fn(MathFn::Ceil)),
+    ("floor", Name::Fn(MathFn::Floor)),
+    ("sin", Name::Fn(MathFn::Sin)),
+    ("cos", Name::Fn(MathFn::Cos)),
+    ("tan", Name::Fn(MathFn::Tan)),
+    ("exp", Name::Fn(MathFn::Exp)),
+    ("log", Name::Fn(MathFn::Log)),
+    ("abs", Name::Fn(MathFn::Abs)),
+    ("tau", Name::Constant(Constant::Tau)),
+    ("ln", Name::Fn(MathFn::Ln)),
+    ("pi", Name::Constant(Constant::Pi)),
+    ("e", Name::Constant(Constant::E)),
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Name {
+    Fn(MathFn),
+    Constant(Constant),
+}
+
+impl Display for MathFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = NAMES
+            .iter()
+            .find(|(_, name)| name == &Name::Fn(*self))
+            .unwrap()
+            .0;
+        f.write_str(name)
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = NAMES
+            .iter()
+            .find(|(_, name)| name == &Name::Constant(*self))
+            .unwrap()
+            .0;
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+}
+
+impl BinaryOp {
+    /**
+     * How tightly the operator binds its operands, where higher binds tighter
+     */
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Add | Self::Sub => 1,
+            Self::Mul | Self::Div => 2,
+            Self::Pow => 4,
+        }
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+            Self::Pow => '^',
+        }
+    }
+}
+
+/**
+ * The syntax tree of a parsed mathematical expression in `x` and `y`
+ */
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expr {
+    Number(f32),
+    Variable(Variable),
+    Constant(Constant),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(MathFn, Box<Expr>),
+}
+
+impl Expr {
+    /**
+     * Parse an expression such as `2x^2 - 3sin(x)`, where a multiplication may be left out between a value and whatever follows it
+     */
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            index: 0,
+            end: source.chars().count(),
+        };
+        let expr = parser.expr()?;
+
+        match parser.peek() {
+            None => Ok(expr),
+            Some((token, position)) => {
+                Err(ParseError::new(format!("Unexpected {}", token), position))
+            }
+        }
+    }
+
+    /**
+     * Parse an implicit equation such as `x^2 + y^2 = 25` into the expression `lhs - rhs`, which is zero wherever the equation holds
+     *
+     * Without an `=` the whole source is taken to equal zero
+     */
+    pub fn parse_implicit(source: &str) -> Result<Self, ParseError> {
+        let Some((lhs, rhs)) = source.split_once('=') else {
+            return Self::parse(source);
+        };
+        let offset = lhs.chars().count() + 1;
+        let rhs = Self::parse(rhs).map_err(|e| ParseError {
+            position: e.position + offset,
+            ..e
+        })?;
+        Ok(Self::Binary(
+            BinaryOp::Sub,
+            Box::new(Self::parse(lhs)?),
+            Box::new(rhs),
+        ))
+    }
+
+    /**
+     * The value of the expression for the provided values of `x` and `y`
+     */
+    pub fn eval(&self, x: f32, y: f32) -> f32 {
+        match self {
+            Self::Number(n) => *n,
+            Self::Variable(Variable::X) => x,
+            Self::Variable(Variable::Y) => y,
+            Self::Constant(c) => c.value(),
+            Self::Neg(e) => -e.eval(x, y),
+            Self::Binary(op, a, b) => {
+                let (a, b) = (a.eval(x, y), b.eval(x, y));
+                match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Sub => a - b,
+                    BinaryOp::Mul => a * b,
+                    BinaryOp::Div => a / b,
+                    BinaryOp::Pow => a.powf(b),
+                }
+            }
+            Self::Call(f, e) => f.apply(e.eval(x, y)),
+        }
+    }
+
+    /**
+     * Return `true` if the expression refers to the provided `Variable`
+     */
+    pub fn uses(&self, var: Variable) -> bool {
+        match self {
+            Self::Variable(v) => v == &var,
+            Self::Number(_) | Self::Constant(_) => false,
+            Self::Neg(e) | Self::Call(_, e) => e.uses(var),
+            Self::Binary(_, a, b) => a.uses(var) || b.uses(var),
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Self::Binary(op, ..) => op.precedence(),
+            Self::Neg(_) => 3,
+            Self::Number(n) if *n < 0.0 => 3,
+            _ => 5,
+        }
+    }
+
+    /**
+     * Return `true` if the expression can be written directly after a number to multiply it without being misread
+     */
+    fn follows_coefficient(&self) -> bool {
+        match self {
+            Self::Variable(_) | Self::Constant(_) | Self::Call(..) => true,
+            Self::Binary(BinaryOp::Pow, base, _) => {
+                matches!(**base, Self::Variable(_) | Self::Constant(_))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        //  Wrap an operand in parentheses whenever it binds looser than the operator around it
+        let wrap = |e: &Expr, min: u8| {
+            if e.precedence() < min {
+                format!("({})", e)
+            } else {
+                e.to_string()
+            }
+        };
+
+        f.write_str(&match self {
+            Self::Number(n) => n.to_string(),
+            Self::Variable(v) => v.to_string(),
+            Self::Constant(c) => c.to_string(),
+            Self::Neg(e) => format!("-{}", wrap(e, 3)),
+            Self::Call(func, e) => format!("{}({})", func, e),
+            Self::Binary(op, a, b) => {
+                let p = op.precedence();
+
+                match op {
+                    //  Powers group to the right, so only their base needs tighter operands
+                    BinaryOp::Pow => format!("{}^{}", wrap(a, p + 1), wrap(b, p)),
+                    //  Coefficients are written directly before what they multiply, as in `2x^2`
+                    BinaryOp::Mul if matches!(**a, Self::Number(_)) && b.follows_coefficient() => {
+                        format!("{}{}", a, b)
+                    }
+                    _ => format!("{} {} {}", wrap(a, p), op.symbol(), wrap(b, p + 1)),
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Number(f32),
+    Variable(Variable),
+    Name(Name),
+    Op(char),
+    Open,
+    Close,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&match self {
+            Self::Number(n) => format!("number {}", n),
+            Self::Variable(v) => format!("variable {}", v),
+            Self::Name(Name::Fn(func)) => format!("function {}", func),
+            Self::Name(Name::Constant(c)) => format!("constant {}", c),
+            Self::Op(c) => format!("'{}'", c),
+            Self::Open => "'('".to_owned(),
+            Self::Close => "')'".to_owned(),
+        })
+    }
+}
+
+/**
+ * Split the source into tokens paired with the position of their first character
+ */
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ParseError> {
+    let chars = source.chars().collect::<Vec<char>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+
+        let token = if c.is_ascii_digit() || c == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1
+            }
+            let text = chars[start..i].iter().collect::<String>();
+            let n = text
+                .parse()
+                .map_err(|_| ParseError::new(format!("Invalid number '{}'", text), start))?;
+            Token::Number(n)
+        } else if c.is_alphabetic() {
+            let rest = chars[i..].iter().collect::<String>().to_lowercase();
+
+            if let Some((text, name)) = NAMES.iter().find(|(text, _)| rest.starts_with(text)) {
+                i += text.chars().count();
+                Token::Name(*name)
+            } else {
+                i += 1;
+                match c.to_ascii_lowercase() {
+                    'x' => Token::Variable(Variable::X),
+                    'y' => Token::Variable(Variable::Y),
+                    _ => {
+                        let word = chars[start..]
+                            .iter()
+                            .take_while(|c| c.is_alphanumeric())
+                            .collect::<String>();
+                        return Err(ParseError::new(format!("Unknown name '{}'", word), start));
+                    }
+                }
+            }
+        } else {
+            i += 1;
+            match c {
+                '+' | '-' | '*' | '/' | '^' => Token::Op(c),
+                '(' => Token::Open,
+                ')' => Token::Close,
+                _ => return Err(ParseError::new(format!("Unexpected '{}'", c), start)),
+            }
+        };
+        tokens.push((token, start));
+    }
+    Ok(tokens)
+}
+
+/**
+ * A recursive descent parser over a list of tokens
+ */
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    index: usize,
+    end: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<(&Token, usize)> {
+        self.tokens.get(self.index).map(|(t, p)| (t, *p))
+    }
+
+    /**
+     * The position of the next token, or of the end of the source
+     */
+    fn position(&self) -> usize {
+        self.peek().map_or(self.end, |(_, p)| p)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let matched = self.peek().is_some_and(|(t, _)| t == token);
+        if matched {
+            self.index += 1
+        }
+        matched
+    }
+
+    /**
+     * `term (('+' | '-') term)*`
+     */
+    fn expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.term()?;
+
+        loop {
+            let op = if self.eat(&Token::Op('+')) {
+                BinaryOp::Add
+            } else if self.eat(&Token::Op('-')) {
+                BinaryOp::Sub
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(self.term()?));
+        }
+    }
+
+    /**
+     * `unary (('*' | '/') unary | power)*`, where a power directly following a value is an implied multiplication
+     */
+    fn term(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.unary()?;
+
+        loop {
+            let (op, rhs) = if self.eat(&Token::Op('*')) {
+                (BinaryOp::Mul, self.unary()?)
+            } else if self.eat(&Token::Op('/')) {
+                (BinaryOp::Div, self.unary()?)
+            } else if matches!(
+                self.peek(),
+                Some((
+                    Token::Number(_) | Token::Variable(_) | Token::Name(_) | Token::Open,
+                    _
+                ))
+            ) {
+                (BinaryOp::Mul, self.power()?)
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+    }
+
+    /**
+     * `('-' | '+') unary | power`
+     */
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat(&Token::Op('-')) {
+            //  Negative numbers are kept as numbers, so that they print as coefficients
+            Ok(match self.unary()? {
+                Expr::Number(n) => Expr::Number(-n),
+                e => Expr::Neg(Box::new(e)),
+            })
+        } else if self.eat(&Token::Op('+')) {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    /**
+     * `primary ('^' unary)?`, grouping to the right
+     */
+    fn power(&mut self) -> Result<Expr, ParseError> {
+        let base = self.primary()?;
+
+        if self.eat(&Token::Op('^')) {
+            Ok(Expr::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(self.unary()?),
+            ))
+        } else {
+            Ok(base)
+        }
+    }
+
+    /**
+     * A number, variable, constant, function call or parenthesized expression
+     */
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let position = self.position();
+        let Some((token, _)) = self.peek() else {
+            return Err(ParseError::new("Unexpected end of expression", position));
+        };
+        let token = token.clone();
+        self.index += 1;
+
+        match token {
+            Token::Number(n) => Ok(Expr::Number(n)),
+            Token::Variable(v) => Ok(Expr::Variable(v)),
+            Token::Name(Name::Constant(c)) => Ok(Expr::Constant(c)),
+            Token::Name(Name::Fn(func)) => {
+                if !self.eat(&Token::Open) {
+                    return Err(ParseError::new(
+                        format!("Expected '(' after {}", func),
+                        self.position(),
+                    ));
+                }
+                let arg = self.expr()?;
+                self.close()?;
+                Ok(Expr::Call(func, Box::new(arg)))
+            }
+            Token::Open => {
+                let inner = self.expr()?;
+                self.close()?;
+                Ok(inner)
+            }
+            _ => Err(ParseError::new(format!("Unexpected {}", token), position)),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), ParseError> {
+        if self.eat(&Token::Close) {
+            Ok(())
+        } else {
+            Err(ParseError::new("Expected ')'", self.position()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::math::FunctionType};
+
+    fn eval(source: &str, x: f32) -> f32 {
+        Expr::parse(source).unwrap().eval(x, 0.0)
+    }
+
+    fn error(source: &str) -> ParseError {
+        Expr::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(Expr::parse("1 + 2 * 3"), Expr::parse("1 + (2 * 3)"));
+        assert_eq!(Expr::parse("1 - 2 - 3"), Expr::parse("(1 - 2) - 3"));
+        assert_eq!(Expr::parse("8 / 4 / 2"), Expr::parse("(8 / 4) / 2"));
+        assert_eq!(Expr::parse("2 * 3 ^ 2"), Expr::parse("2 * (3 ^ 2)"));
+        assert_eq!(eval("1 + 2 * 3 ^ 2", 0.0), 19.0);
+    }
+
+    #[test]
+    fn power_is_right_associative() {
+        assert_eq!(Expr::parse("2 ^ 3 ^ 2"), Expr::parse("2 ^ (3 ^ 2)"));
+        assert_eq!(eval("2 ^ 3 ^ 2", 0.0), 512.0);
+        assert_eq!(eval("2 ^ -1", 0.0), 0.5);
+    }
+
+    #[test]
+    fn implicit_multiplication() {
+        assert_eq!(Expr::parse("2x"), Expr::parse("2 * x"));
+        assert_eq!(Expr::parse("2x^2"), Expr::parse("2 * (x ^ 2)"));
+        assert_eq!(Expr::parse("3sin(x)"), Expr::parse("3 * sin(x)"));
+        assert_eq!(Expr::parse("2(x + 1)"), Expr::parse("2 * (x + 1)"));
+        assert_eq!(
+            Expr::parse("(x + 1)(x - 1)"),
+            Expr::parse("(x + 1) * (x - 1)")
+        );
+        assert_eq!(eval("2x^2 - 3x", 2.0), 2.0);
+    }
+
+    #[test]
+    fn unary_minus() {
+        assert_eq!(Expr::parse("-2"), Ok(Expr::Number(-2.0)));
+        assert_eq!(Expr::parse("-x^2"), Expr::parse("-(x ^ 2)"));
+        assert_eq!(eval("-x^2", 3.0), -9.0);
+        assert_eq!(eval("--x", 3.0), 3.0);
+        assert_eq!(eval("2 * -x", 3.0), -6.0);
+        assert_eq!(eval("+x - -1", 3.0), 4.0);
+    }
+
+    #[test]
+    fn error_positions() {
+        assert_eq!(error("2 + * 3").position, 4);
+        assert_eq!(error("(x + 1").position, 6);
+        assert_eq!(error("sin x").position, 4);
+        assert_eq!(error("x + ").position, 4);
+        assert_eq!(error("1 + 2)").position, 5);
+        assert_eq!(error("foo(x)").position, 0);
+
+        //  Positions count characters rather than bytes
+        assert_eq!(error("\u{3000}+ $").position, 3);
+        assert_eq!(Expr::parse_implicit("é = 1 +").unwrap_err().position, 7);
+    }
+
+    #[test]
+    fn function_error_positions() {
+        let error = "\u{3000}y = y".parse::<FunctionType>().unwrap_err();
+        assert_eq!(error.parse().map(|e| e.position), Some(4));
+
+        let error = "y = 1 + )".parse::<FunctionType>().unwrap_err();
+        assert_eq!(error.parse(), Some(&ParseError::new("Unexpected ')'", 8)));
+    }
+}
@@ -0,0 +1,382 @@
+use super::Vec2;
+
+/**
+ * The relative error bound of the fast orientation test, from Shewchuk's "Adaptive Precision Floating-Point Arithmetic and Fast Robust Geometric Predicates"
+ */
+const ORIENT_BOUND: f64 = (3.0 + 16.0 * f64::EPSILON) * f64::EPSILON / 2.0;
+
+/**
+ * Which way a path turns at a point, where counter-clockwise is in a y-up space and so appears clockwise on the screen
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+/**
+ * How two segments meet, if they do at all
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SegmentIntersection {
+    None,
+    Point(Vec2),
+    /**
+     * The segments are collinear and share the part between these two points, ordered along the first segment
+     */
+    Overlap(Vec2, Vec2),
+}
+
+/**
+ * Twice the signed area of the triangle `a`, `b`, `c`, which is positive when `c` lies to the left of the line from `a` to `b` in a y-up space
+ *
+ * The sign is always exact. The value is computed in `f64` and only falls back to exact arithmetic when rounding could have changed the sign
+ */
+pub fn orient2d(a: Vec2, b: Vec2, c: Vec2) -> f64 {
+    let v = |p: Vec2| [p.x as f64, p.y as f64];
+    orient2d_f64(v(a), v(b), v(c))
+}
+
+/**
+ * The orientation test on points in `f64` precision, for code which works in it internally
+ */
+pub(crate) fn orient2d_f64(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    let left = (a[0] - c[0]) * (b[1] - c[1]);
+    let right = (a[1] - c[1]) * (b[0] - c[0]);
+    let det = left - right;
+
+    if det.abs() >= ORIENT_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+
+    //  Expanding the products leaves six terms, each of which is kept exactly as a pair of floats
+    let mut expansion = Vec::with_capacity(12);
+    for (x, y, sign) in [
+        (a[0], b[1], 1.0),
+        (a[0], c[1], -1.0),
+        (c[0], b[1], -1.0),
+        (a[1], b[0], -1.0),
+        (a[1], c[0], 1.0),
+        (c[1], b[0], 1.0),
+    ] {
+        let (product, error) = two_product(x * sign, y);
+        grow_expansion(&mut expansion, error);
+        grow_expansion(&mut expansion, product);
+    }
+
+    //  The components never overlap, so summing from the smallest keeps the sign of the largest
+    expansion.iter().sum()
+}
+
+/**
+ * The product of two floats as a rounded result and the exact error of the rounding
+ */
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+/**
+ * The sum of two floats as a rounded result and the exact error of the rounding
+ */
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let bv = sum - a;
+    let av = sum - bv;
+    (sum, (a - av) + (b - bv))
+}
+
+/**
+ * Add a float to an expansion of non-overlapping components ordered by increasing magnitude
+ */
+fn grow_expansion(expansion: &mut Vec<f64>, value: f64) {
+    let mut q = value;
+    for component in expansion.iter_mut() {
+        let (sum, error) = two_sum(q, *component);
+        *component = error;
+        q = sum;
+    }
+    expansion.push(q)
+}
+
+/**
+ * Which way the path from `a` through `b` turns to reach `c`
+ */
+pub fn orientation(a: Vec2, b: Vec2, c: Vec2) -> Orientation {
+    let det = orient2d(a, b, c);
+
+    if det > 0.0 {
+        Orientation::CounterClockwise
+    } else if det < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+/**
+ * Find where the segment from `a1` to `a2` meets the segment from `b1` to `b2`, including where they touch or overlap
+ */
+pub fn segment_intersection(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> SegmentIntersection {
+    if a1 == a2 || b1 == b2 {
+        //  A segment without length is a single point, which meets the other segment if it lies on it
+        let (p, (c, d)) = if a1 == a2 {
+            (a1, (b1, b2))
+        } else {
+            (b1, (a1, a2))
+        };
+        return if on_segment(p, c, d) {
+            SegmentIntersection::Point(p)
+        } else {
+            SegmentIntersection::None
+        };
+    }
+    let (o1, o2) = (orient2d(a1, a2, b1), orient2d(a1, a2, b2));
+    let (o3, o4) = (orient2d(b1, b2, a1), orient2d(b1, b2, a2));
+
+    if o1 == 0.0 && o2 == 0.0 {
+        return collinear_overlap(a1, a2, b1, b2);
+    }
+    if o1 * o2 > 0.0 || o3 * o4 > 0.0 {
+        return SegmentIntersection::None;
+    }
+
+    //  Points which lie exactly on the other segment are returned as they are
+    let point = if o1 == 0.0 {
+        b1
+    } else if o2 == 0.0 {
+        b2
+    } else if o3 == 0.0 {
+        a1
+    } else if o4 == 0.0 {
+        a2
+    } else {
+        let t = o3 / (o3 - o4);
+        let (x, y) = (
+            a1.x as f64 + (a2.x - a1.x) as f64 * t,
+            a1.y as f64 + (a2.y - a1.y) as f64 * t,
+        );
+        Vec2::new(x as f32, y as f32)
+    };
+    SegmentIntersection::Point(point)
+}
+
+/**
+ * The part shared by two collinear segments, measured along the first
+ */
+fn collinear_overlap(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> SegmentIntersection {
+    //  Compare positions along whichever axis the first segment spans the most
+    let d = a2 - a1;
+    let key = |p: Vec2| {
+        if d.x.abs() >= d.y.abs() {
+            (p.x - a1.x) / d.x
+        } else {
+            (p.y - a1.y) / d.y
+        }
+    };
+    let (tb1, tb2) = (key(b1), key(b2));
+    let ((lo, lo_point), (hi, hi_point)) = if tb1 <= tb2 {
+        ((tb1, b1), (tb2, b2))
+    } else {
+        ((tb2, b2), (tb1, b1))
+    };
+
+    let (start, start_point) = if lo > 0.0 { (lo, lo_point) } else { (0.0, a1) };
+    let (end, end_point) = if hi < 1.0 { (hi, hi_point) } else { (1.0, a2) };
+
+    if start > end {
+        SegmentIntersection::None
+    } else if start_point == end_point {
+        SegmentIntersection::Point(start_point)
+    } else {
+        SegmentIntersection::Overlap(start_point, end_point)
+    }
+}
+
+/**
+ * Return `true` if `p` lies exactly on the segment from `a` to `b`
+ */
+pub fn on_segment(p: Vec2, a: Vec2, b: Vec2) -> bool {
+    orient2d(a, b, p) == 0.0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+/**
+ * Find where the infinite line through `a1` and `a2` crosses the one through `b1` and `b2`, if they are not parallel
+ */
+pub fn line_intersection(a1: Vec2, a2: Vec2, b1: Vec2, b2: Vec2) -> Option<Vec2> {
+    let (o3, o4) = (orient2d(b1, b2, a1), orient2d(b1, b2, a2));
+
+    if o3 == o4 || a1 == a2 || b1 == b2 {
+        return None;
+    }
+    let t = o3 / (o3 - o4);
+    Some(Vec2::new(
+        (a1.x as f64 + (a2.x - a1.x) as f64 * t) as f32,
+        (a1.y as f64 + (a2.y - a1.y) as f64 * t) as f32,
+    ))
+}
+
+/**
+ * The parameter along the line from `a` to `b` of the point nearest to `p`, where `0` is `a` and `1` is `b`
+ */
+pub fn project(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    let ab = b - a;
+    let length = ab.length_squared();
+
+    if length > 0.0 {
+        (p - a).dot(ab) / length
+    } else {
+        0.0
+    }
+}
+
+/**
+ * The point on the infinite line through `a` and `b` nearest to `p`
+ */
+pub fn project_onto_line(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    a + (b - a) * project(p, a, b)
+}
+
+/**
+ * The point on the segment from `a` to `b` nearest to `p`
+ */
+pub fn closest_point_on_segment(p: Vec2, a: Vec2, b: Vec2) -> Vec2 {
+    a + (b - a) * project(p, a, b).clamp(0.0, 1.0)
+}
+
+/**
+ * The shortest distance between `p` and the segment from `a` to `b`
+ */
+pub fn point_segment_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    p.distance(closest_point_on_segment(p, a, b))
+}
+
+/**
+ * The shortest distance between `p` and the infinite line through `a` and `b`
+ */
+pub fn point_line_distance(p: Vec2, a: Vec2, b: Vec2) -> f32 {
+    p.distance(project_onto_line(p, a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orientation_signs() {
+        let (a, b) = (Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0));
+        assert_eq!(
+            orientation(a, b, Vec2::new(1.0, 1.0)),
+            Orientation::CounterClockwise
+        );
+        assert_eq!(
+            orientation(a, b, Vec2::new(1.0, -1.0)),
+            Orientation::Clockwise
+        );
+        assert_eq!(
+            orientation(a, b, Vec2::new(9.0, 0.0)),
+            Orientation::Collinear
+        );
+        assert_eq!(orient2d(a, b, Vec2::new(1.0, 1.0)), 4.0);
+    }
+
+    #[test]
+    fn exact_orientation() {
+        //  Points a few units in the last place away from a line, where the rounded determinant gets the sign wrong
+        let unit = 2f64.powi(-53);
+        let (b, c) = ([12.0, 12.0], [24.0, 24.0]);
+
+        for i in 0..16 {
+            for j in 0..16 {
+                let a = [0.5 + i as f64 * unit, 0.5 + j as f64 * unit];
+
+                //  Scaled up by 2^53 every coordinate is an integer, so the determinant can be found exactly
+                let scale = |v: f64| (v / unit) as i128;
+                let (ax, ay, bx, by, cx, cy) = (
+                    scale(a[0]),
+                    scale(a[1]),
+                    scale(b[0]),
+                    scale(b[1]),
+                    scale(c[0]),
+                    scale(c[1]),
+                );
+                let exact = (ax - cx) * (by - cy) - (ay - cy) * (bx - cx);
+
+                assert_eq!(
+                    orient2d_f64(a, b, c).partial_cmp(&0.0),
+                    Some(exact.cmp(&0)),
+                    "{i} {j}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn segments() {
+        let p = |x, y| Vec2::new(x, y);
+
+        assert_eq!(
+            segment_intersection(p(0.0, 0.0), p(4.0, 4.0), p(0.0, 4.0), p(4.0, 0.0)),
+            SegmentIntersection::Point(p(2.0, 2.0))
+        );
+        assert_eq!(
+            segment_intersection(p(0.0, 0.0), p(1.0, 1.0), p(0.0, 4.0), p(4.0, 0.0)),
+            SegmentIntersection::None
+        );
+
+        //  Touching ends are returned exactly
+        assert_eq!(
+            segment_intersection(p(0.0, 0.0), p(2.0, 0.0), p(1.0, 0.0), p(1.0, 5.0)),
+            SegmentIntersection::Point(p(1.0, 0.0))
+        );
+
+        //  Collinear segments share the part between the inner ends, ordered along the first segment
+        assert_eq!(
+            segment_intersection(p(4.0, 0.0), p(0.0, 0.0), p(1.0, 0.0), p(6.0, 0.0)),
+            SegmentIntersection::Overlap(p(4.0, 0.0), p(1.0, 0.0))
+        );
+        assert_eq!(
+            segment_intersection(p(0.0, 0.0), p(0.0, 2.0), p(0.0, 2.0), p(0.0, 3.0)),
+            SegmentIntersection::Point(p(0.0, 2.0))
+        );
+        assert_eq!(
+            segment_intersection(p(0.0, 0.0), p(1.0, 1.0), p(2.0, 2.0), p(3.0, 3.0)),
+            SegmentIntersection::None
+        );
+
+        //  Segments without length are points
+        assert_eq!(
+            segment_intersection(p(1.0, 1.0), p(1.0, 1.0), p(0.0, 0.0), p(2.0, 2.0)),
+            SegmentIntersection::Point(p(1.0, 1.0))
+        );
+        assert!(on_segment(p(1.0, 1.0), p(0.0, 0.0), p(2.0, 2.0)));
+        assert!(!on_segment(p(3.0, 3.0), p(0.0, 0.0), p(2.0, 2.0)));
+    }
+
+    #[test]
+    fn lines_and_projections() {
+        let p = |x, y| Vec2::new(x, y);
+
+        assert_eq!(
+            line_intersection(p(0.0, 0.0), p(1.0, 0.0), p(5.0, 1.0), p(5.0, 2.0)),
+            Some(p(5.0, 0.0))
+        );
+        assert_eq!(
+            line_intersection(p(0.0, 0.0), p(1.0, 0.0), p(0.0, 1.0), p(1.0, 1.0)),
+            None
+        );
+
+        let (a, b) = (p(0.0, 0.0), p(4.0, 0.0));
+        assert_eq!(project(p(6.0, 3.0), a, b), 1.5);
+        assert_eq!(project(p(6.0, 3.0), a, a), 0.0);
+        assert_eq!(project_onto_line(p(6.0, 3.0), a, b), p(6.0, 0.0));
+        assert_eq!(closest_point_on_segment(p(6.0, 3.0), a, b), b);
+        assert_eq!(point_segment_distance(p(7.0, 4.0), a, b), 5.0);
+        assert_eq!(point_line_distance(p(7.0, 4.0), a, b), 4.0);
+    }
+}
@@ -0,0 +1,522 @@
+use {
+    super::{Bounds, Vec2},
+    std::{
+        fmt::{Debug, Display},
+        ops::{Range, RangeInclusive},
+    },
+};
+
+/**
+ * The most values produced by `Interval::steps`
+ */
+pub const MAX_STEPS: usize = 1 << 20;
+
+/**
+ * A number which can bound an `Interval`
+ */
+pub trait Scalar: Copy + PartialOrd + Debug + Display {
+    /**
+     * The lowest value of the type, which bounds intervals without a lower limit
+     */
+    const LOWEST: Self;
+    /**
+     * The highest value of the type, which bounds intervals without an upper limit
+     */
+    const HIGHEST: Self;
+
+    fn to_f64(self) -> f64;
+
+    /**
+     * Convert back from `f64`, rounding and saturating where the type requires it
+     */
+    fn from_f64(value: f64) -> Self;
+}
+
+macro_rules! scalar {
+    ($t:ty, $lowest:expr, $highest:expr, $round:expr) => {
+        impl Scalar for $t {
+            const LOWEST: Self = $lowest;
+            const HIGHEST: Self = $highest;
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_f64(value: f64) -> Self {
+                $round(value) as $t
+            }
+        }
+    };
+}
+
+scalar!(f32, f32::NEG_INFINITY, f32::INFINITY, |v: f64| v);
+scalar!(f64, f64::NEG_INFINITY, f64::INFINITY, |v: f64| v);
+scalar!(i32, i32::MIN, i32::MAX, f64::round);
+
+/**
+ * Whether an end of an `Interval` includes the value it lies at
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum End {
+    Open,
+    #[default]
+    Closed,
+}
+
+impl End {
+    pub fn is_closed(&self) -> bool {
+        self == &Self::Closed
+    }
+
+    pub fn is_open(&self) -> bool {
+        self == &Self::Open
+    }
+
+    /**
+     * The end shared by two intervals meeting at the same value, which includes it only if both do
+     */
+    fn both(self, other: Self) -> Self {
+        if self.is_closed() && other.is_closed() {
+            Self::Closed
+        } else {
+            Self::Open
+        }
+    }
+
+    /**
+     * The end of the union of two intervals meeting at the same value, which includes it if either does
+     */
+    fn either(self, other: Self) -> Self {
+        if self.is_closed() || other.is_closed() {
+            Self::Closed
+        } else {
+            Self::Open
+        }
+    }
+}
+
+/**
+ * A range of values between a lower and upper bound, each of which is either included or excluded
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    min: T,
+    max: T,
+    lower: End,
+    upper: End,
+}
+
+impl<T: Scalar> Interval<T> {
+    /**
+     * Initialize a new `Interval` between two values in either order, along with whether each end is included
+     */
+    pub fn new(a: T, b: T, lower: End, upper: End) -> Self {
+        if b < a {
+            Self {
+                min: b,
+                max: a,
+                lower: upper,
+                upper: lower,
+            }
+        } else {
+            Self {
+                min: a,
+                max: b,
+                lower,
+                upper,
+            }
+        }
+    }
+
+    /**
+     * The interval including both of its ends
+     */
+    pub fn closed(a: T, b: T) -> Self {
+        Self::new(a, b, End::Closed, End::Closed)
+    }
+
+    /**
+     * The interval excluding both of its ends
+     */
+    pub fn open(a: T, b: T) -> Self {
+        Self::new(a, b, End::Open, End::Open)
+    }
+
+    /**
+     * The interval covering every value of the type
+     */
+    pub fn unbounded() -> Self {
+        Self::closed(T::LOWEST, T::HIGHEST)
+    }
+
+    pub fn min(&self) -> T {
+        self.min
+    }
+
+    pub fn max(&self) -> T {
+        self.max
+    }
+
+    /**
+     * Whether the lower end is included
+     */
+    pub fn lower(&self) -> End {
+        self.lower
+    }
+
+    /**
+     * Whether the upper end is included
+     */
+    pub fn upper(&self) -> End {
+        self.upper
+    }
+
+    /**
+     * Return `true` if no value lies within the interval, which happens when its ends meet and either is open
+     */
+    pub fn is_empty(&self) -> bool {
+        self.min == self.max && (self.lower.is_open() || self.upper.is_open())
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let above = match self.lower {
+            End::Open => value > self.min,
+            End::Closed => value >= self.min,
+        };
+        let below = match self.upper {
+            End::Open => value < self.max,
+            End::Closed => value <= self.max,
+        };
+        above && below
+    }
+
+    /**
+     * The distance between the ends, saturating for integers
+     */
+    pub fn length(&self) -> T {
+        T::from_f64(self.max.to_f64() - self.min.to_f64())
+    }
+
+    /**
+     * The value within the interval or at one of its ends nearest to the provided value
+     */
+    pub fn clamp(&self, value: T) -> T {
+        if value < self.min {
+            self.min
+        } else if value > self.max {
+            self.max
+        } else {
+            value
+        }
+    }
+
+    /**
+     * The values lying within both intervals, if there are any
+     */
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let (min, lower) = if self.min > other.min {
+            (self.min, self.lower)
+        } else if other.min > self.min {
+            (other.min, other.lower)
+        } else {
+            (self.min, self.lower.both(other.lower))
+        };
+        let (max, upper) = if self.max < other.max {
+            (self.max, self.upper)
+        } else if other.max < self.max {
+            (other.max, other.upper)
+        } else {
+            (self.max, self.upper.both(other.upper))
+        };
+
+        let interval = Self {
+            min,
+            max,
+            lower,
+            upper,
+        };
+        (min <= max && !interval.is_empty()).then_some(interval)
+    }
+
+    /**
+     * Return `true` if any value lies within both intervals
+     */
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /**
+     * The smallest interval containing both intervals
+     */
+    pub fn hull(&self, other: &Self) -> Self {
+        let (min, lower) = if self.min < other.min {
+            (self.min, self.lower)
+        } else if other.min < self.min {
+            (other.min, other.lower)
+        } else {
+            (self.min, self.lower.either(other.lower))
+        };
+        let (max, upper) = if self.max > other.max {
+            (self.max, self.upper)
+        } else if other.max > self.max {
+            (other.max, other.upper)
+        } else {
+            (self.max, self.upper.either(other.upper))
+        };
+
+        Self {
+            min,
+            max,
+            lower,
+            upper,
+        }
+    }
+
+    /**
+     * The values lying within either interval, if they form a single interval without a gap
+     */
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        let touching =
+            |a: &Self, b: &Self| a.max == b.min && (a.upper.is_closed() || b.lower.is_closed());
+
+        (self.overlaps(other) || touching(self, other) || touching(other, self))
+            .then(|| self.hull(other))
+    }
+
+    /**
+     * The values `min + k * step` which lie within the interval, in increasing order, stopping after `MAX_STEPS` values
+     */
+    pub fn steps(&self, step: T) -> impl Iterator<Item = T> {
+        let (min, max, step) = (self.min.to_f64(), self.max.to_f64(), step.to_f64());
+        let count = if step > 0.0 && (max - min).is_finite() {
+            (((max - min) / step).floor() as usize)
+                .saturating_add(1)
+                .min(MAX_STEPS)
+        } else {
+            0
+        };
+        let interval = *self;
+
+        (0..count)
+            .map(move |k| T::from_f64(min + step * k as f64))
+            .filter(move |&value| interval.contains(value))
+    }
+}
+
+impl Interval<i32> {
+    /**
+     * Every integer within the interval, in increasing order
+     */
+    pub fn iter(&self) -> RangeInclusive<i32> {
+        let start = self.min.checked_add(self.lower.is_open() as i32);
+        let end = self.max.checked_sub(self.upper.is_open() as i32);
+
+        match (start, end) {
+            (Some(start), Some(end)) => start..=end,
+            //  An open end at the limit of the type leaves nothing past it
+            _ => RangeInclusive::new(1, 0),
+        }
+    }
+}
+
+impl IntoIterator for Interval<i32> {
+    type Item = i32;
+    type IntoIter = RangeInclusive<i32>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Scalar> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lower.is_closed() { '[' } else { '(' },
+            self.min,
+            self.max,
+            if self.upper.is_closed() { ']' } else { ')' }
+        )
+    }
+}
+
+impl<T: Scalar> From<(T, T)> for Interval<T> {
+    fn from((a, b): (T, T)) -> Self {
+        Self::closed(a, b)
+    }
+}
+
+impl<T: Scalar> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (a, b) = range.into_inner();
+        Self::closed(a, b)
+    }
+}
+
+impl<T: Scalar> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::new(range.start, range.end, End::Closed, End::Open)
+    }
+}
+
+/**
+ * A rectangular region made of an interval along each axis
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Interval2<T> {
+    pub x: Interval<T>,
+    pub y: Interval<T>,
+}
+
+impl<T: Scalar> Interval2<T> {
+    pub fn new<X: Into<Interval<T>>, Y: Into<Interval<T>>>(x: X, y: Y) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /**
+     * The region covering every point
+     */
+    pub fn unbounded() -> Self {
+        Self::new(Interval::unbounded(), Interval::unbounded())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.x.is_empty() || self.y.is_empty()
+    }
+
+    pub fn contains(&self, (x, y): (T, T)) -> bool {
+        self.x.contains(x) && self.y.contains(y)
+    }
+
+    /**
+     * The point within the region or on its edges nearest to the provided point
+     */
+    pub fn clamp(&self, (x, y): (T, T)) -> (T, T) {
+        (self.x.clamp(x), self.y.clamp(y))
+    }
+
+    /**
+     * The points lying within both regions, if there are any
+     */
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            x: self.x.intersection(&other.x)?,
+            y: self.y.intersection(&other.y)?,
+        })
+    }
+
+    /**
+     * The smallest region containing both regions
+     */
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            x: self.x.hull(&other.x),
+            y: self.y.hull(&other.y),
+        }
+    }
+}
+
+impl<T: Scalar> Display for Interval2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} x {}", self.x, self.y)
+    }
+}
+
+impl From<Bounds> for Interval2<f32> {
+    fn from(bounds: Bounds) -> Self {
+        Self::new((bounds.min.x, bounds.max.x), (bounds.min.y, bounds.max.y))
+    }
+}
+
+impl From<Interval2<f32>> for Bounds {
+    fn from(interval: Interval2<f32>) -> Self {
+        Self::new(
+            Vec2::new(interval.x.min(), interval.y.min()),
+            Vec2::new(interval.x.max(), interval.y.max()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps() {
+        let values = Interval::closed(0.0f32, 1.0)
+            .steps(0.25)
+            .collect::<Vec<f32>>();
+        assert_eq!(values, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+
+        let values = Interval::open(0.0f32, 1.0).steps(0.5).collect::<Vec<f32>>();
+        assert_eq!(values, vec![0.5]);
+
+        //  Counts which do not fit in a `usize` are capped instead of overflowing
+        assert_eq!(
+            Interval::closed(0.0f32, 1e30).steps(1e-30).count(),
+            MAX_STEPS
+        );
+        assert_eq!(Interval::closed(0.0f32, 1.0).steps(0.0).count(), 0);
+    }
+
+    #[test]
+    fn ends() {
+        let interval = Interval::new(5, 1, End::Open, End::Closed);
+        assert_eq!((interval.min(), interval.max()), (1, 5));
+        assert_eq!(
+            (interval.lower(), interval.upper()),
+            (End::Closed, End::Open)
+        );
+
+        assert!(interval.contains(1) && !interval.contains(5));
+        assert_eq!(interval.iter().collect::<Vec<i32>>(), [1, 2, 3, 4]);
+        assert_eq!(interval.to_string(), "[1, 5)");
+        assert_eq!(Interval::from(1..5), interval);
+
+        assert!(Interval::open(2.0, 2.0).is_empty());
+        assert!(!Interval::closed(2.0, 2.0).is_empty());
+        assert_eq!(Interval::closed(3, 7).clamp(9), 7);
+
+        //  Integer lengths saturate rather than overflowing
+        assert_eq!(Interval::<i32>::unbounded().length(), i32::MAX);
+        assert_eq!(Interval::open(i32::MAX, i32::MAX).iter().count(), 0);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Interval::new(0.0, 2.0, End::Closed, End::Open);
+        let b = Interval::closed(1.0, 3.0);
+
+        let both = a.intersection(&b).unwrap();
+        assert_eq!(both, Interval::new(1.0, 2.0, End::Closed, End::Open));
+        assert_eq!(a.hull(&b), Interval::closed(0.0, 3.0));
+
+        //  Intervals meeting at an end only overlap if both include it
+        let c = Interval::closed(2.0, 4.0);
+        assert!(!a.overlaps(&c));
+        assert!(Interval::closed(0.0, 2.0).overlaps(&c));
+        assert_eq!(a.union(&c), Some(Interval::closed(0.0, 4.0)));
+        assert_eq!(a.union(&Interval::open(2.0, 4.0)), None);
+        assert_eq!(a.union(&Interval::closed(5.0, 6.0)), None);
+
+        let region = Interval2::new((0, 4), 0..4);
+        assert!(region.contains((4, 3)) && !region.contains((4, 4)));
+        assert_eq!(region.clamp((-3, 9)), (0, 4));
+        assert_eq!(region.to_string(), "[0, 4] x [0, 4)");
+        assert_eq!(
+            region.intersection(&Interval2::new((2, 9), (3, 9))),
+            Some(Interval2::new((2, 4), 3..4))
+        );
+        assert_eq!(region.intersection(&Interval2::new((5, 9), (0, 1))), None);
+
+        let bounds = Bounds::from(Interval2::new((0.0, 2.0), (1.0, 3.0)));
+        assert_eq!(
+            Interval2::from(bounds),
+            Interval2::new((0.0, 2.0), (1.0, 3.0))
+        );
+    }
+}
@@ -0,0 +1,575 @@
+use {
+    super::{Quat, Transform2D, Vec2, Vec3, Vec4},
+    crate::error::Error,
+    std::ops::{Mul, MulAssign},
+};
+
+/**
+ * A 3x3 matrix stored by rows, used both for linear maps in three dimensions and for projective maps of the plane
+ *
+ * Vectors are treated as columns, so `a * b` applies `b` first and then `a`
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat3 {
+    m: [[f32; 3]; 3],
+}
+
+impl Default for Mat3 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat3 {
+    pub const IDENTITY: Self = Self::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+    pub const ZERO: Self = Self::new([[0.0; 3]; 3]);
+
+    pub const fn new(rows: [[f32; 3]; 3]) -> Self {
+        Self { m: rows }
+    }
+
+    /**
+     * A matrix that moves every point of the plane by the provided offset
+     */
+    pub fn translation(x: f32, y: f32) -> Self {
+        Transform2D::translation(x, y).into()
+    }
+
+    /**
+     * A matrix that rotates every point of the plane about the origin by `angle` radians
+     */
+    pub fn rotation(angle: f32) -> Self {
+        Transform2D::rotation(angle).into()
+    }
+
+    /**
+     * A matrix that scales every point of the plane away from the origin
+     */
+    pub fn scaling(x: f32, y: f32) -> Self {
+        Transform2D::scaling(x, y).into()
+    }
+
+    pub const fn rows(&self) -> [[f32; 3]; 3] {
+        self.m
+    }
+
+    pub fn row(&self, i: usize) -> Vec3 {
+        let [x, y, z] = self.m[i];
+        Vec3::new(x, y, z)
+    }
+
+    pub fn column(&self, j: usize) -> Vec3 {
+        Vec3::new(self.m[0][j], self.m[1][j], self.m[2][j])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = self.m;
+        Self::new([0, 1, 2].map(|j| [m[0][j], m[1][j], m[2][j]]))
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.m;
+        a * (e * i - f * h) - b * (d * i - f * g) + c * (d * h - e * g)
+    }
+
+    /**
+     * The matrix which undoes `self`, if `self` is not degenerate
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let [[a, b, c], [d, e, f], [g, h, i]] = self.m;
+
+        Some(
+            Self::new([
+                [e * i - f * h, c * h - b * i, b * f - c * e],
+                [f * g - d * i, a * i - c * g, c * d - a * f],
+                [d * h - e * g, b * g - a * h, a * e - b * d],
+            ]) * (1.0 / det),
+        )
+    }
+
+    /**
+     * Apply the matrix to a point of the plane in homogeneous coordinates, dividing through by the resulting weight
+     */
+    pub fn apply<T: Into<Vec2>>(&self, point: T) -> Vec2 {
+        let p = *self * point.into().extend(1.0);
+        p.truncate() / p.z
+    }
+
+    /**
+     * Apply the matrix to a direction in the plane, ignoring the translation
+     */
+    pub fn apply_vector<T: Into<Vec2>>(&self, vector: T) -> Vec2 {
+        (*self * vector.into().extend(0.0)).truncate()
+    }
+}
+
+impl Mul for Mat3 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new([0, 1, 2].map(|i| [0, 1, 2].map(|j| self.row(i).dot(rhs.column(j)))))
+    }
+}
+
+impl MulAssign for Mat3 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Mul<Vec3> for Mat3 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        Vec3::new(
+            self.row(0).dot(rhs),
+            self.row(1).dot(rhs),
+            self.row(2).dot(rhs),
+        )
+    }
+}
+
+impl Mul<f32> for Mat3 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.m.map(|row| row.map(|v| v * rhs)))
+    }
+}
+
+impl From<Transform2D> for Mat3 {
+    fn from(transform: Transform2D) -> Self {
+        Self::new(transform.matrix())
+    }
+}
+
+impl TryFrom<Mat3> for Transform2D {
+    type Error = Error;
+
+    /**
+     * Convert a matrix whose bottom row is `[0, 0, 1]`, failing for projective matrices which a `Transform2D` cannot represent
+     */
+    fn try_from(matrix: Mat3) -> Result<Self, Self::Error> {
+        let [[a, b, c], [d, e, f], bottom] = matrix.m;
+
+        if bottom != [0.0, 0.0, 1.0] {
+            return Err("Matrix is not an affine transform".into());
+        }
+        Ok(Self::new(a, b, c, d, e, f))
+    }
+}
+
+impl From<Quat> for Mat3 {
+    /**
+     * The rotation described by a unit quaternion
+     */
+    fn from(q: Quat) -> Self {
+        let Quat { x, y, z, w } = q;
+
+        Self::new([
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ])
+    }
+}
+
+/**
+ * A 4x4 matrix stored by rows, used for transforms and projections in three dimensions
+ *
+ * Vectors are treated as columns, so `a * b` applies `b` first and then `a`
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mat4 {
+    m: [[f32; 4]; 4],
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mat4 {
+    pub const IDENTITY: Self = Self::new([
+        [1.0, 0.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0, 0.0],
+        [0.0, 0.0, 1.0, 0.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    pub const ZERO: Self = Self::new([[0.0; 4]; 4]);
+
+    pub const fn new(rows: [[f32; 4]; 4]) -> Self {
+        Self { m: rows }
+    }
+
+    /**
+     * A transform that moves every point by the provided offset
+     */
+    pub fn translation(x: f32, y: f32, z: f32) -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, x],
+            [0.0, 1.0, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /**
+     * A transform that scales every point away from the origin
+     */
+    pub fn scaling(x: f32, y: f32, z: f32) -> Self {
+        Self::new([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /**
+     * A transform that rotates every point by `angle` radians about `axis`, counter-clockwise when looking back along it
+     */
+    pub fn rotation(axis: Vec3, angle: f32) -> Self {
+        Quat::from_axis_angle(axis, angle).into()
+    }
+
+    /**
+     * A view transform which moves `eye` to the origin and turns `target` onto the negative z-axis, with `up` pointing roughly upwards
+     */
+    pub fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Self {
+        let forward = (target - eye).normalize();
+        let side = forward.cross(up).normalize();
+        let up = side.cross(forward);
+
+        Self::new([
+            [side.x, side.y, side.z, -side.dot(eye)],
+            [up.x, up.y, up.z, -up.dot(eye)],
+            [-forward.x, -forward.y, -forward.z, forward.dot(eye)],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /**
+     * A perspective projection with a vertical field of view of `fov` radians, mapping depths between `near` and `far` onto `[-1, 1]`
+     */
+    pub fn perspective(fov: f32, aspect: f32, near: f32, far: f32) -> Self {
+        let f = 1.0 / (fov / 2.0).tan();
+        let depth = near - far;
+
+        Self::new([
+            [f / aspect, 0.0, 0.0, 0.0],
+            [0.0, f, 0.0, 0.0],
+            [0.0, 0.0, (far + near) / depth, 2.0 * far * near / depth],
+            [0.0, 0.0, -1.0, 0.0],
+        ])
+    }
+
+    /**
+     * An orthographic projection mapping the provided box onto the cube from `-1` to `1`
+     */
+    pub fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Self {
+        let (width, height, depth) = (right - left, top - bottom, far - near);
+
+        Self::new([
+            [2.0 / width, 0.0, 0.0, -(right + left) / width],
+            [0.0, 2.0 / height, 0.0, -(top + bottom) / height],
+            [0.0, 0.0, -2.0 / depth, -(far + near) / depth],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub const fn rows(&self) -> [[f32; 4]; 4] {
+        self.m
+    }
+
+    pub fn row(&self, i: usize) -> Vec4 {
+        let [x, y, z, w] = self.m[i];
+        Vec4::new(x, y, z, w)
+    }
+
+    pub fn column(&self, j: usize) -> Vec4 {
+        Vec4::new(self.m[0][j], self.m[1][j], self.m[2][j], self.m[3][j])
+    }
+
+    pub fn transpose(&self) -> Self {
+        let m = self.m;
+        Self::new([0, 1, 2, 3].map(|j| [m[0][j], m[1][j], m[2][j], m[3][j]]))
+    }
+
+    /**
+     * The determinants of the 2x2 blocks of the top and bottom pairs of rows, from which the determinant and inverse are built
+     */
+    fn blocks(&self) -> ([f32; 6], [f32; 6]) {
+        let m = self.m;
+        let pair = |r: usize, a: usize, b: usize| m[r][a] * m[r + 1][b] - m[r + 1][a] * m[r][b];
+
+        (
+            [
+                pair(0, 0, 1),
+                pair(0, 0, 2),
+                pair(0, 0, 3),
+                pair(0, 1, 2),
+                pair(0, 1, 3),
+                pair(0, 2, 3),
+            ],
+            [
+                pair(2, 0, 1),
+                pair(2, 0, 2),
+                pair(2, 0, 3),
+                pair(2, 1, 2),
+                pair(2, 1, 3),
+                pair(2, 2, 3),
+            ],
+        )
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let (s, c) = self.blocks();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+
+    /**
+     * The matrix which undoes `self`, if `self` is not degenerate
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let m = self.m;
+        let (s, c) = self.blocks();
+
+        Some(
+            Self::new([
+                [
+                    m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                    -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3],
+                    m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                    -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3],
+                ],
+                [
+                    -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1],
+                    m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                    -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1],
+                    m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+                ],
+                [
+                    m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                    -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0],
+                    m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                    -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0],
+                ],
+                [
+                    -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0],
+                    m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                    -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0],
+                    m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+                ],
+            ]) * (1.0 / det),
+        )
+    }
+
+    /**
+     * Apply the matrix to a point in homogeneous coordinates, dividing through by the resulting weight as a projection requires
+     */
+    pub fn apply(&self, point: Vec3) -> Vec3 {
+        let p = *self * point.extend(1.0);
+        p.truncate() / p.w
+    }
+
+    /**
+     * Apply the matrix to a direction, ignoring the translation
+     */
+    pub fn apply_vector(&self, vector: Vec3) -> Vec3 {
+        (*self * vector.extend(0.0)).truncate()
+    }
+
+    /**
+     * Project a point onto a viewport of the screen, where `y` points down, or `None` if it lies behind the viewer
+     */
+    pub fn project(&self, point: Vec3, (width, height): (f32, f32)) -> Option<Vec2> {
+        let p = *self * point.extend(1.0);
+
+        if p.w <= 0.0 {
+            return None;
+        }
+        let ndc = p.truncate() / p.w;
+        Some(Vec2::new(
+            (ndc.x + 1.0) / 2.0 * width,
+            (1.0 - ndc.y) / 2.0 * height,
+        ))
+    }
+}
+
+impl Mul for Mat4 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new([0, 1, 2, 3].map(|i| [0, 1, 2, 3].map(|j| self.row(i).dot(rhs.column(j)))))
+    }
+}
+
+impl MulAssign for Mat4 {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Mul<Vec4> for Mat4 {
+    type Output = Vec4;
+
+    fn mul(self, rhs: Vec4) -> Self::Output {
+        Vec4::new(
+            self.row(0).dot(rhs),
+            self.row(1).dot(rhs),
+            self.row(2).dot(rhs),
+            self.row(3).dot(rhs),
+        )
+    }
+}
+
+impl Mul<f32> for Mat4 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.m.map(|row| row.map(|v| v * rhs)))
+    }
+}
+
+impl From<Mat3> for Mat4 {
+    /**
+     * Embed a linear map of three dimensions, without any translation
+     */
+    fn from(matrix: Mat3) -> Self {
+        let m = matrix.rows();
+
+        Self::new([
+            [m[0][0], m[0][1], m[0][2], 0.0],
+            [m[1][0], m[1][1], m[1][2], 0.0],
+            [m[2][0], m[2][1], m[2][2], 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+impl From<Quat> for Mat4 {
+    /**
+     * The rotation described by a unit quaternion
+     */
+    fn from(q: Quat) -> Self {
+        Mat3::from(q).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::f32::consts::FRAC_PI_2};
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        a.distance(b) < 1e-5
+    }
+
+    #[test]
+    fn mat3() {
+        let m = Mat3::translation(3.0, 1.0) * Mat3::rotation(FRAC_PI_2) * Mat3::scaling(2.0, 2.0);
+        assert!(m.apply((1.0, 0.0)).distance(Vec2::new(3.0, 3.0)) < 1e-5);
+        assert!((m.determinant() - 4.0).abs() < 1e-5);
+
+        let inverse = m.inverse().unwrap();
+        assert!(
+            inverse
+                .apply(m.apply((5.0, -2.0)))
+                .distance(Vec2::new(5.0, -2.0))
+                < 1e-5
+        );
+        assert_eq!(Mat3::scaling(0.0, 1.0).inverse(), None);
+
+        assert_eq!(m.transpose().transpose(), m);
+        assert_eq!(m.row(0), m.transpose().column(0));
+        let offset = m.apply((1.0, 0.0)) - m.apply((0.0, 0.0));
+        assert!(m.apply_vector((1.0, 0.0)).distance(offset) < 1e-5);
+
+        //  Only affine matrices convert back into a `Transform2D`
+        let transform = Transform2D::try_from(m).unwrap();
+        assert_eq!(Mat3::from(transform), m);
+        let projective = Mat3::new([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.5, 0.0, 1.0]]);
+        assert!(Transform2D::try_from(projective).is_err());
+        assert_eq!(projective.apply((2.0, 4.0)), Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn mat4() {
+        let m = Mat4::translation(1.0, 2.0, 3.0)
+            * Mat4::rotation(Vec3::Z, FRAC_PI_2)
+            * Mat4::scaling(2.0, 1.0, 1.0);
+        assert!(close(m.apply(Vec3::X), Vec3::new(1.0, 4.0, 3.0)));
+        assert!(close(m.apply_vector(Vec3::X), Vec3::new(0.0, 2.0, 0.0)));
+        assert!((m.determinant() - 2.0).abs() < 1e-5);
+
+        let inverse = m.inverse().unwrap();
+        assert!(close(
+            inverse.apply(m.apply(Vec3::new(4.0, 5.0, 6.0))),
+            Vec3::new(4.0, 5.0, 6.0)
+        ));
+        assert_eq!(Mat4::scaling(1.0, 0.0, 1.0).inverse(), None);
+        assert_eq!(m.transpose().row(3), m.column(3));
+
+        let embedded = Mat4::from(Mat3::scaling(2.0, 3.0));
+        assert!(close(
+            embedded.apply(Vec3::new(1.0, 1.0, 1.0)),
+            Vec3::new(2.0, 3.0, 1.0)
+        ));
+    }
+
+    #[test]
+    fn projections() {
+        //  The view turns the target onto the negative z-axis
+        let eye = Vec3::new(0.0, 0.0, 5.0);
+        let view = Mat4::look_at(eye, Vec3::ZERO, Vec3::Y);
+        assert!(close(view.apply(Vec3::ZERO), Vec3::new(0.0, 0.0, -5.0)));
+        assert!(close(view.apply(Vec3::Y), Vec3::new(0.0, 1.0, -5.0)));
+
+        //  The near and far planes map onto the ends of the depth range
+        let projection = Mat4::perspective(FRAC_PI_2, 2.0, 1.0, 10.0);
+        assert!((projection.apply(Vec3::new(0.0, 0.0, -1.0)).z + 1.0).abs() < 1e-5);
+        assert!((projection.apply(Vec3::new(0.0, 0.0, -10.0)).z - 1.0).abs() < 1e-5);
+
+        let camera = projection * view;
+        let center = camera.project(Vec3::ZERO, (800.0, 600.0)).unwrap();
+        assert!(center.distance(Vec2::new(400.0, 300.0)) < 1e-3);
+        let above = camera
+            .project(Vec3::new(0.0, 1.0, 0.0), (800.0, 600.0))
+            .unwrap();
+        assert!(above.y < 300.0);
+        assert_eq!(
+            camera.project(Vec3::new(0.0, 0.0, 10.0), (800.0, 600.0)),
+            None
+        );
+
+        let ortho = Mat4::orthographic(0.0, 800.0, 0.0, 600.0, 0.0, 1.0);
+        assert!(close(
+            ortho.apply(Vec3::new(800.0, 600.0, -1.0)),
+            Vec3::new(1.0, 1.0, 1.0)
+        ));
+        assert!(close(ortho.apply(Vec3::ZERO), Vec3::new(-1.0, -1.0, -1.0)));
+    }
+}
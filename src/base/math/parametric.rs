@@ -0,0 +1,280 @@
+use super::{FunctionType, Vec2};
+
+/**
+ * The number of even pieces the domain is split into before any refinement
+ */
+const PIECES: usize = 64;
+
+/**
+ * The deepest refinement of each piece, which bounds the work done near discontinuities
+ */
+const MAX_DEPTH: u32 = 12;
+
+/**
+ * The largest turn in radians allowed between neighbouring segments before they are refined
+ */
+const MAX_TURN: f32 = 0.1;
+
+/**
+ * Anything which can be sampled into runs of connected points for drawing, such as a `Parametric` or a `Polar` curve
+ */
+pub trait Plottable {
+    /**
+     * Sample the curve into runs of points which stay within `tolerance` of it, starting a new run wherever it is undefined or jumps
+     */
+    fn sample(&self, tolerance: f32) -> Vec<Vec<Vec2>>;
+}
+
+/**
+ * A curve tracing `(x(t), y(t))` as `t` runs over its domain
+ */
+#[derive(Debug, Clone)]
+pub struct Parametric {
+    pub x: FunctionType,
+    pub y: FunctionType,
+    pub domain: (f32, f32),
+}
+
+impl Parametric {
+    pub fn new(x: FunctionType, y: FunctionType, domain: (f32, f32)) -> Self {
+        Self { x, y, domain }
+    }
+
+    /**
+     * The point at the parameter `t`, if both components are defined there
+     */
+    pub fn eval(&self, t: f32) -> Option<Vec2> {
+        Some(Vec2::new(self.x.eval(t)?, self.y.eval(t)?))
+    }
+}
+
+impl Plottable for Parametric {
+    fn sample(&self, tolerance: f32) -> Vec<Vec<Vec2>> {
+        sample(|t| self.eval(t), self.domain, tolerance)
+    }
+}
+
+/**
+ * A curve tracing the distance `r(θ)` from the origin as the angle `θ` runs over its domain, measured from the positive x-axis
+ */
+#[derive(Debug, Clone)]
+pub struct Polar {
+    pub r: FunctionType,
+    pub domain: (f32, f32),
+}
+
+impl Polar {
+    pub fn new(r: FunctionType, domain: (f32, f32)) -> Self {
+        Self { r, domain }
+    }
+
+    /**
+     * The point at the angle `theta`, if the radius is defined there
+     */
+    pub fn eval(&self, theta: f32) -> Option<Vec2> {
+        let r = self.r.eval(theta)?;
+        Some(Vec2::new(r * theta.cos(), r * theta.sin()))
+    }
+}
+
+impl Plottable for Polar {
+    fn sample(&self, tolerance: f32) -> Vec<Vec<Vec2>> {
+        sample(|theta| self.eval(theta), self.domain, tolerance)
+    }
+}
+
+/**
+ * Adaptively sample a curve over its domain into runs of connected points
+ *
+ * Every piece is halved for as long as its middle strays from the chord or the curve turns sharply. Pieces which are still long at the deepest refinement are taken to be jumps, and end the run
+ */
+pub(crate) fn sample<F: Fn(f32) -> Option<Vec2>>(
+    f: F,
+    (start, end): (f32, f32),
+    tolerance: f32,
+) -> Vec<Vec<Vec2>> {
+    let tolerance = tolerance.max(f32::EPSILON);
+    let mut sampler = Sampler {
+        f,
+        tolerance,
+        runs: Vec::new(),
+        run: Vec::new(),
+    };
+
+    let step = (end - start) / PIECES as f32;
+    let mut prev = (start, sampler.eval(start));
+    sampler.push(prev.1);
+
+    for k in 1..=PIECES {
+        let t = if k == PIECES {
+            end
+        } else {
+            start + step * k as f32
+        };
+        let next = (t, sampler.eval(t));
+        sampler.refine(prev, next, MAX_DEPTH);
+        prev = next;
+    }
+    sampler.finish()
+}
+
+struct Sampler<F> {
+    f: F,
+    tolerance: f32,
+    runs: Vec<Vec<Vec2>>,
+    run: Vec<Vec2>,
+}
+
+impl<F: Fn(f32) -> Option<Vec2>> Sampler<F> {
+    fn eval(&self, t: f32) -> Option<Vec2> {
+        (self.f)(t).filter(|p| p.x.is_finite() && p.y.is_finite())
+    }
+
+    /**
+     * Extend the current run with a point, or end it if the curve is undefined there
+     */
+    fn push(&mut self, point: Option<Vec2>) {
+        match point {
+            Some(p) => self.run.push(p),
+            None => self.split(),
+        }
+    }
+
+    fn split(&mut self) {
+        if !self.run.is_empty() {
+            self.runs.push(std::mem::take(&mut self.run))
+        }
+    }
+
+    /**
+     * Add the samples between two parameters, the first of which has already been added
+     */
+    fn refine(&mut self, (a, pa): (f32, Option<Vec2>), (b, pb): (f32, Option<Vec2>), depth: u32) {
+        let m = (a + b) / 2.0;
+        let pm = self.eval(m);
+
+        let smooth = match (pa, pm, pb) {
+            (Some(pa), Some(pm), Some(pb)) => {
+                let chord = pb - pa;
+                let deviation = if chord.length_squared() > 0.0 {
+                    (pm - pa).cross(chord).abs() / chord.length()
+                } else {
+                    pm.distance(pa)
+                };
+                let (u, v) = (pm - pa, pb - pm);
+                let turn = u.cross(v).atan2(u.dot(v)).abs();
+
+                deviation <= self.tolerance
+                    && (turn <= MAX_TURN || chord.length() <= self.tolerance)
+            }
+            //  Pieces without any defined points have nothing to find
+            (None, None, None) => true,
+            _ => false,
+        };
+
+        if smooth {
+            return self.push(pb);
+        }
+        if depth == 0 {
+            //  Whatever is left at the deepest level is either undefined or a jump
+            if let (Some(pa), Some(pb)) = (pa, pb) {
+                if pa.distance(pb) > self.tolerance * 16.0 {
+                    self.split()
+                }
+            }
+            return self.push(pb);
+        }
+        self.refine((a, pa), (m, pm), depth - 1);
+        self.refine((m, pm), (b, pb), depth - 1);
+    }
+
+    fn finish(mut self) -> Vec<Vec<Vec2>> {
+        self.split();
+        self.runs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::math::{Closure, Trig},
+        std::f32::consts::{PI, TAU},
+    };
+
+    fn identity() -> FunctionType {
+        FunctionType::Linear { m: 1.0, b: 0.0 }
+    }
+
+    fn trig(trig: Trig, amplitude: f32) -> FunctionType {
+        FunctionType::Trigonometric {
+            trig,
+            amplitude,
+            frequency: 1.0,
+            phase: 0.0,
+            offset: 0.0,
+        }
+    }
+
+    #[test]
+    fn parametric() {
+        let circle = Parametric::new(trig(Trig::Cos, 10.0), trig(Trig::Sin, 10.0), (0.0, TAU));
+        let runs = circle.sample(0.1);
+        assert_eq!(runs.len(), 1);
+
+        //  Every sample lies on the circle and the chords between them stay close to it
+        let run = &runs[0];
+        assert!(run.len() > 16);
+        assert!(run.iter().all(|p| (p.length() - 10.0).abs() < 1e-4));
+        assert!(run
+            .windows(2)
+            .all(|w| 10.0 - ((w[0] + w[1]) / 2.0).length() <= 0.1));
+        assert!(run[0].distance(Vec2::new(10.0, 0.0)) < 1e-4);
+        assert!(run.last().unwrap().distance(Vec2::new(10.0, 0.0)) < 1e-3);
+
+        //  Straight curves need no refinement
+        let line = Parametric::new(identity(), identity(), (0.0, 1.0));
+        assert_eq!(line.sample(0.1)[0].len(), PIECES + 1);
+    }
+
+    #[test]
+    fn polar() {
+        let circle = Polar::new(FunctionType::Polynomial(vec![2.0]), (0.0, PI));
+        assert!(circle.eval(PI / 2.0).unwrap().distance(Vec2::new(0.0, 2.0)) < 1e-6);
+
+        let runs = circle.sample(0.01);
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0].iter().all(|p| (p.length() - 2.0).abs() < 1e-5));
+    }
+
+    #[test]
+    fn discontinuities() {
+        //  The jumps of the tangent split the curve into separate runs
+        let tangent = Parametric::new(identity(), trig(Trig::Tan, 1.0), (-PI, PI));
+        assert_eq!(tangent.sample(0.05).len(), 3);
+
+        let step = Parametric::new(
+            identity(),
+            FunctionType::Piecewise(vec![
+                ((-1.0, 0.3), FunctionType::Polynomial(vec![0.0])),
+                ((0.3, 2.0), FunctionType::Polynomial(vec![5.0])),
+            ]),
+            (-1.0, 1.0),
+        );
+        let runs = step.sample(0.05);
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].iter().all(|p| p.y == 0.0));
+        assert!(runs[1].iter().all(|p| p.y == 5.0));
+
+        //  Only the part of the curve where it is defined is sampled
+        let root = Parametric::new(
+            identity(),
+            FunctionType::Closure(Closure::new(|x| x.sqrt())),
+            (-1.0, 1.0),
+        );
+        let runs = root.sample(0.05);
+        assert_eq!(runs.len(), 1);
+        assert!(runs[0][0].x.abs() < 1e-3);
+        assert_eq!(*runs[0].last().unwrap(), Vec2::new(1.0, 1.0));
+    }
+}
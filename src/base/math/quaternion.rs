@@ -0,0 +1,253 @@
+use {
+    super::Vec3,
+    std::ops::{Mul, MulAssign, Neg},
+};
+
+/**
+ * A quaternion `w + xi + yj + zk`, used with a length of one to describe rotations in three dimensions
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quat {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Quat {
+    pub const IDENTITY: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /**
+     * The rotation by `angle` radians about `axis`, counter-clockwise when looking back along it
+     */
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let (sin, cos) = (angle / 2.0).sin_cos();
+        let axis = axis.normalize() * sin;
+        Self::new(axis.x, axis.y, axis.z, cos)
+    }
+
+    /**
+     * The axis and angle in radians of the rotation, where the identity gives the x-axis and no angle
+     */
+    pub fn to_axis_angle(self) -> (Vec3, f32) {
+        let q = self.normalize();
+        let axis = q.vector().normalize();
+
+        if axis == Vec3::ZERO {
+            (Vec3::X, 0.0)
+        } else {
+            (axis, 2.0 * q.vector().length().atan2(q.w))
+        }
+    }
+
+    /**
+     * The imaginary part of the quaternion
+     */
+    pub fn vector(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    pub fn length(self) -> f32 {
+        self.dot(self).sqrt()
+    }
+
+    /**
+     * The quaternion scaled to a length of one, or the identity if it has no length
+     */
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+
+        if length > 0.0 {
+            Self::new(
+                self.x / length,
+                self.y / length,
+                self.z / length,
+                self.w / length,
+            )
+        } else {
+            Self::IDENTITY
+        }
+    }
+
+    /**
+     * The quaternion with its imaginary part negated, which undoes the rotation of a unit quaternion
+     */
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /**
+     * The quaternion which undoes `self`, if it has any length
+     */
+    pub fn inverse(self) -> Option<Self> {
+        let length = self.dot(self);
+
+        if length == 0.0 || !length.is_finite() {
+            return None;
+        }
+        let c = self.conjugate();
+        Some(Self::new(
+            c.x / length,
+            c.y / length,
+            c.z / length,
+            c.w / length,
+        ))
+    }
+
+    /**
+     * Rotate a vector by a unit quaternion
+     */
+    pub fn rotate(self, v: Vec3) -> Vec3 {
+        let u = self.vector();
+        let t = u.cross(v) * 2.0;
+        v + t * self.w + u.cross(t)
+    }
+
+    /**
+     * Interpolate between two rotations at a constant angular speed, taking the shorter way around
+     */
+    pub fn slerp(self, other: Self, t: f32) -> Self {
+        let (a, mut b) = (self.normalize(), other.normalize());
+        let mut cos = a.dot(b);
+
+        if cos < 0.0 {
+            b = -b;
+            cos = -cos;
+        }
+
+        //  Nearly equal rotations are interpolated linearly, where the angle is too small to divide by
+        let (wa, wb) = if cos > 0.9995 {
+            (1.0 - t, t)
+        } else {
+            let angle = cos.acos();
+            let sin = angle.sin();
+            (((1.0 - t) * angle).sin() / sin, (t * angle).sin() / sin)
+        };
+        Self::new(
+            a.x * wa + b.x * wb,
+            a.y * wa + b.y * wb,
+            a.z * wa + b.z * wb,
+            a.w * wa + b.w * wb,
+        )
+        .normalize()
+    }
+}
+
+impl Mul for Quat {
+    type Output = Self;
+
+    /**
+     * Compose two rotations, applying `rhs` first and then `self`
+     */
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (a, b) = (self.vector(), rhs.vector());
+        let v = b * self.w + a * rhs.w + a.cross(b);
+        Self::new(v.x, v.y, v.z, self.w * rhs.w - a.dot(b))
+    }
+}
+
+impl MulAssign for Quat {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Mul<Vec3> for Quat {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Self::Output {
+        self.rotate(rhs)
+    }
+}
+
+impl Neg for Quat {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z, -self.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::math::Mat3,
+        std::f32::consts::{FRAC_PI_2, PI},
+    };
+
+    fn close(a: Vec3, b: Vec3) -> bool {
+        a.distance(b) < 1e-5
+    }
+
+    #[test]
+    fn rotations() {
+        let quarter = Quat::from_axis_angle(Vec3::Z, FRAC_PI_2);
+        assert!(close(quarter.rotate(Vec3::X), Vec3::Y));
+        assert!(close(quarter * Vec3::Z, Vec3::Z));
+        assert!((quarter.length() - 1.0).abs() < 1e-6);
+
+        //  The matrix of a rotation agrees with rotating directly
+        let q = Quat::from_axis_angle(Vec3::new(1.0, 2.0, -1.0), 0.7);
+        let v = Vec3::new(0.3, -2.0, 4.0);
+        assert!(close(Mat3::from(q) * v, q.rotate(v)));
+
+        let (axis, angle) = q.to_axis_angle();
+        assert!(close(axis, Vec3::new(1.0, 2.0, -1.0).normalize()));
+        assert!((angle - 0.7).abs() < 1e-5);
+        assert_eq!(Quat::IDENTITY.to_axis_angle(), (Vec3::X, 0.0));
+    }
+
+    #[test]
+    fn composition() {
+        let (a, b) = (
+            Quat::from_axis_angle(Vec3::Z, FRAC_PI_2),
+            Quat::from_axis_angle(Vec3::X, FRAC_PI_2),
+        );
+
+        //  The right hand rotation is applied first
+        let v = Vec3::Y;
+        assert!(close((a * b).rotate(v), a.rotate(b.rotate(v))));
+        assert!(close((a * b).rotate(v), Vec3::new(0.0, 0.0, 1.0)));
+
+        let inverse = b.inverse().unwrap();
+        assert!(close((b * inverse).vector(), Vec3::ZERO));
+        assert!(close(inverse.rotate(b.rotate(v)), v));
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).inverse(), None);
+        assert_eq!(Quat::new(0.0, 0.0, 0.0, 0.0).normalize(), Quat::IDENTITY);
+    }
+
+    #[test]
+    fn slerp() {
+        let (a, b) = (Quat::IDENTITY, Quat::from_axis_angle(Vec3::Z, FRAC_PI_2));
+
+        //  Halfway is half of the angle, at any speed
+        let (axis, angle) = a.slerp(b, 0.5).to_axis_angle();
+        assert!(close(axis, Vec3::Z));
+        assert!((angle - FRAC_PI_2 / 2.0).abs() < 1e-5);
+        assert!(close(a.slerp(b, 1.0).rotate(Vec3::X), Vec3::Y));
+
+        //  The shorter way around is taken even when the signs differ
+        let turned = Quat::from_axis_angle(Vec3::Z, PI * 0.9);
+        let halfway = a.slerp(-turned, 0.5);
+        assert!((halfway.to_axis_angle().1 - PI * 0.45).abs() < 1e-4);
+
+        //  Nearly equal rotations are interpolated without dividing by a vanishing angle
+        let tiny = Quat::from_axis_angle(Vec3::Z, 1e-4);
+        assert!(a.slerp(tiny, 0.5).length().is_finite());
+    }
+}
@@ -0,0 +1,520 @@
+use {
+    super::{CubicBezier, Curve, Vec2},
+    crate::error::Result,
+};
+
+/**
+ * A curve passing through every one of its points, built from cubic segments whose tangents follow the neighbouring points
+ *
+ * The `alpha` of the parametrisation decides how the spacing of the points shapes the curve: `0` is uniform, `0.5` is centripetal and `1` is chordal. Centripetal curves never form cusps or loops within a segment
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatmullRom {
+    points: Vec<Vec2>,
+    alpha: f32,
+    segments: Vec<CubicBezier>,
+}
+
+impl CatmullRom {
+    /**
+     * Create a centripetal Catmull-Rom spline through the provided points
+     */
+    pub fn new<A: Into<Vec2>, T: IntoIterator<Item = A>>(iter: T) -> Result<Self> {
+        Self::with_alpha(iter, 0.5)
+    }
+
+    /**
+     * Create a Catmull-Rom spline through the provided points with the provided parametrisation
+     */
+    pub fn with_alpha<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        iter: T,
+        alpha: f32,
+    ) -> Result<Self> {
+        let mut points = iter.into_iter().map(Into::into).collect::<Vec<Vec2>>();
+        points.dedup();
+
+        let n = points.len();
+        if n < 2 {
+            return Err("Not enough points (Required 2 or more)".into());
+        }
+
+        //  Reflect the neighbours of each end so the curve leaves and arrives in a straight line
+        let first = points[0] * 2.0 - points[1];
+        let last = points[n - 1] * 2.0 - points[n - 2];
+        let padded = [&[first][..], &points, &[last]].concat();
+
+        let segments = padded
+            .windows(4)
+            .map(|w| {
+                let (p0, p1, p2, p3) = (w[0], w[1], w[2], w[3]);
+                let knot = |a: Vec2, b: Vec2| a.distance(b).powf(alpha);
+                let (t01, t12, t23) = (knot(p0, p1), knot(p1, p2), knot(p2, p3));
+
+                //  The tangents at both ends of the segment, scaled to a parameter running over `[0, 1]`
+                let m1 = (p2 - p1) + ((p1 - p0) / t01 - (p2 - p0) / (t01 + t12)) * t12;
+                let m2 = (p2 - p1) + ((p3 - p2) / t23 - (p3 - p1) / (t12 + t23)) * t12;
+                CubicBezier::new(p1, p1 + m1 / 3.0, p2 - m2 / 3.0, p2)
+            })
+            .collect();
+
+        Ok(Self {
+            points,
+            alpha,
+            segments,
+        })
+    }
+
+    /**
+     * The points the curve passes through
+     */
+    pub fn points(&self) -> &[Vec2] {
+        &self.points
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.alpha
+    }
+
+    /**
+     * The cubic Bézier curves making up each segment between neighbouring points
+     */
+    pub fn segments(&self) -> &[CubicBezier] {
+        &self.segments
+    }
+
+    /**
+     * The segment containing the parameter `t`, and the parameter within it
+     */
+    fn locate(&self, t: f32) -> (&CubicBezier, f32) {
+        let last = self.segments.len() - 1;
+        let k = (t.max(0.0).floor() as usize).min(last);
+        (&self.segments[k], (t - k as f32).clamp(0.0, 1.0))
+    }
+}
+
+impl Curve for CatmullRom {
+    /**
+     * The parameter runs from `0` at the first point, increasing by one at each following point
+     */
+    fn domain(&self) -> (f32, f32) {
+        (0.0, self.segments.len() as f32)
+    }
+
+    fn eval(&self, t: f32) -> Vec2 {
+        let (segment, u) = self.locate(t);
+        segment.eval(u)
+    }
+
+    fn derivative(&self, t: f32) -> Vec2 {
+        let (segment, u) = self.locate(t);
+        segment.derivative(u)
+    }
+
+    fn flatten(&self, tolerance: f32) -> Vec<Vec2> {
+        let mut points = vec![self.points[0]];
+        for segment in &self.segments {
+            points.extend(segment.flatten(tolerance).into_iter().skip(1))
+        }
+        points
+    }
+}
+
+/**
+ * A B-spline in homogeneous coordinates, which is shared by the rational and non-rational curves
+ */
+#[derive(Clone, Debug, PartialEq)]
+struct Homogeneous {
+    degree: usize,
+    knots: Vec<f32>,
+    points: Vec<[f32; 3]>,
+}
+
+impl Homogeneous {
+    fn new(degree: usize, knots: Vec<f32>, points: Vec<[f32; 3]>) -> Result<Self> {
+        let n = points.len();
+
+        if n <= degree {
+            Err(format!(
+                "Not enough control points (Required {} or more)",
+                degree + 1
+            )
+            .into())
+        } else if knots.len() != n + degree + 1 {
+            Err(format!("Wrong number of knots (Required {})", n + degree + 1).into())
+        } else if knots.windows(2).any(|w| w[1] < w[0]) {
+            Err("Knots must never decrease".into())
+        } else if knots[degree] >= knots[n] {
+            Err("Knots must span a range of parameters".into())
+        } else {
+            Ok(Self {
+                degree,
+                knots,
+                points,
+            })
+        }
+    }
+
+    fn domain(&self) -> (f32, f32) {
+        (self.knots[self.degree], self.knots[self.points.len()])
+    }
+
+    /**
+     * The point at the parameter `t` using de Boor's algorithm
+     */
+    fn eval(&self, t: f32) -> [f32; 3] {
+        let (p, n) = (self.degree, self.points.len());
+        let (start, end) = self.domain();
+        let t = t.clamp(start, end);
+
+        //  The knot span containing `t`, where the end of the domain belongs to the last span
+        let k = (p..n)
+            .rfind(|&k| self.knots[k] <= t && self.knots[k] < self.knots[k + 1])
+            .unwrap_or(p);
+
+        let mut d = self.points[k - p..=k].to_vec();
+        for r in 1..=p {
+            for j in (r..=p).rev() {
+                let (lo, hi) = (self.knots[j + k - p], self.knots[j + 1 + k - r]);
+                let alpha = if hi > lo { (t - lo) / (hi - lo) } else { 0.0 };
+                d[j] = [0, 1, 2].map(|i| (1.0 - alpha) * d[j - 1][i] + alpha * d[j][i]);
+            }
+        }
+        d[p]
+    }
+
+    /**
+     * The B-spline of one lower degree describing the derivative of this one, if this one is not constant
+     */
+    fn derivative(&self) -> Option<Self> {
+        let p = self.degree;
+        (p > 0).then(|| Self {
+            degree: p - 1,
+            knots: self.knots[1..self.knots.len() - 1].to_vec(),
+            points: self
+                .points
+                .windows(2)
+                .enumerate()
+                .map(|(i, w)| {
+                    let span = self.knots[i + p + 1] - self.knots[i + 1];
+                    let scale = if span > 0.0 { p as f32 / span } else { 0.0 };
+                    [0, 1, 2].map(|c| (w[1][c] - w[0][c]) * scale)
+                })
+                .collect(),
+        })
+    }
+
+    /**
+     * The point at `t` once projected back into the plane
+     */
+    fn point(&self, t: f32) -> Vec2 {
+        let [x, y, w] = self.eval(t);
+        Vec2::new(x / w, y / w)
+    }
+
+    /**
+     * The tangent at `t`, from the quotient rule applied to the projection
+     */
+    fn tangent(&self, derivative: &Option<Self>, t: f32) -> Vec2 {
+        let Some(derivative) = derivative else {
+            return Vec2::ZERO;
+        };
+        let [x, y, w] = self.eval(t);
+        let [dx, dy, dw] = derivative.eval(t);
+        let point = Vec2::new(x / w, y / w);
+        (Vec2::new(dx, dy) - point * dw) / w
+    }
+}
+
+/**
+ * Knots for `n` control points which are evenly spaced, except for being repeated at each end so the curve begins and ends on its first and last control points
+ */
+fn clamped_knots(n: usize, degree: usize) -> Vec<f32> {
+    let spans = n.saturating_sub(degree).max(1);
+    (0..n + degree + 1)
+        .map(|i| (i.saturating_sub(degree).min(spans)) as f32 / spans as f32)
+        .collect()
+}
+
+/**
+ * A piecewise polynomial curve which is pulled towards, but does not generally pass through, its control points
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct BSpline {
+    curve: Homogeneous,
+    derivative: Option<Homogeneous>,
+}
+
+impl BSpline {
+    /**
+     * Create a B-spline of the provided degree with a knot vector, which must hold exactly `degree + 1` more knots than there are control points
+     */
+    pub fn new<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        control: T,
+        degree: usize,
+        knots: Vec<f32>,
+    ) -> Result<Self> {
+        let points = control
+            .into_iter()
+            .map(|p| {
+                let p = p.into();
+                [p.x, p.y, 1.0]
+            })
+            .collect();
+        let curve = Homogeneous::new(degree, knots, points)?;
+
+        Ok(Self {
+            derivative: curve.derivative(),
+            curve,
+        })
+    }
+
+    /**
+     * Create a B-spline of the provided degree with evenly spaced knots, clamped so that it begins and ends on its first and last control points
+     */
+    pub fn uniform<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        control: T,
+        degree: usize,
+    ) -> Result<Self> {
+        let control = control.into_iter().map(Into::into).collect::<Vec<Vec2>>();
+        let knots = clamped_knots(control.len(), degree);
+        Self::new(control, degree, knots)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.curve.degree
+    }
+
+    pub fn knots(&self) -> &[f32] {
+        &self.curve.knots
+    }
+
+    pub fn control_points(&self) -> Vec<Vec2> {
+        self.curve
+            .points
+            .iter()
+            .map(|&[x, y, _]| Vec2::new(x, y))
+            .collect()
+    }
+}
+
+impl Curve for BSpline {
+    fn domain(&self) -> (f32, f32) {
+        self.curve.domain()
+    }
+
+    fn eval(&self, t: f32) -> Vec2 {
+        self.curve.point(t)
+    }
+
+    fn derivative(&self, t: f32) -> Vec2 {
+        self.curve.tangent(&self.derivative, t)
+    }
+}
+
+/**
+ * A non-uniform rational B-spline, where each control point has a weight deciding how strongly it pulls on the curve
+ *
+ * Unlike plain B-splines these can describe conic sections such as circles exactly
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Nurbs {
+    curve: Homogeneous,
+    derivative: Option<Homogeneous>,
+}
+
+impl Nurbs {
+    /**
+     * Create a NURBS curve of the provided degree from control points paired with positive weights, and a knot vector which must hold exactly `degree + 1` more knots than there are control points
+     */
+    pub fn new<A: Into<Vec2>, T: IntoIterator<Item = (A, f32)>>(
+        control: T,
+        degree: usize,
+        knots: Vec<f32>,
+    ) -> Result<Self> {
+        let points = control
+            .into_iter()
+            .map(|(p, w)| {
+                let p = p.into();
+                [p.x * w, p.y * w, w]
+            })
+            .collect::<Vec<[f32; 3]>>();
+
+        if points.iter().any(|p| p[2] <= 0.0) {
+            return Err("Weights must be positive".into());
+        }
+        let curve = Homogeneous::new(degree, knots, points)?;
+
+        Ok(Self {
+            derivative: curve.derivative(),
+            curve,
+        })
+    }
+
+    /**
+     * Create a NURBS curve of the provided degree with evenly spaced knots, clamped so that it begins and ends on its first and last control points
+     */
+    pub fn uniform<A: Into<Vec2>, T: IntoIterator<Item = (A, f32)>>(
+        control: T,
+        degree: usize,
+    ) -> Result<Self> {
+        let control = control
+            .into_iter()
+            .map(|(p, w)| (p.into(), w))
+            .collect::<Vec<(Vec2, f32)>>();
+        let knots = clamped_knots(control.len(), degree);
+        Self::new(control, degree, knots)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.curve.degree
+    }
+
+    pub fn knots(&self) -> &[f32] {
+        &self.curve.knots
+    }
+
+    /**
+     * The control points paired with their weights
+     */
+    pub fn control_points(&self) -> Vec<(Vec2, f32)> {
+        self.curve
+            .points
+            .iter()
+            .map(|&[x, y, w]| (Vec2::new(x / w, y / w), w))
+            .collect()
+    }
+}
+
+impl Curve for Nurbs {
+    fn domain(&self) -> (f32, f32) {
+        self.curve.domain()
+    }
+
+    fn eval(&self, t: f32) -> Vec2 {
+        self.curve.point(t)
+    }
+
+    fn derivative(&self, t: f32) -> Vec2 {
+        self.curve.tangent(&self.derivative, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::f32::consts::FRAC_1_SQRT_2};
+
+    /**
+     * Compare the derivative of a curve with a central difference of its points
+     */
+    fn check_derivative<C: Curve>(curve: &C, t: f32) {
+        let h = 1e-3;
+        let difference = (curve.eval(t + h) - curve.eval(t - h)) / (2.0 * h);
+        let derivative = curve.derivative(t);
+        assert!(
+            (difference - derivative).length() <= 1e-2 * derivative.length().max(1.0),
+            "{difference:?} != {derivative:?} at {t}"
+        );
+    }
+
+    #[test]
+    fn catmull_rom() {
+        let points = [(0.0, 0.0), (1.0, 2.0), (1.0, 2.0), (3.0, 3.0), (6.0, 0.0)];
+        let spline = CatmullRom::new(points).unwrap();
+
+        //  Repeated points are dropped, and the curve passes through every remaining one
+        assert_eq!(spline.points().len(), 4);
+        assert_eq!(spline.domain(), (0.0, 3.0));
+        for (k, &p) in spline.points().iter().enumerate() {
+            assert!((spline.eval(k as f32) - p).length() < 1e-5);
+        }
+        for t in [0.5, 1.25, 2.9] {
+            check_derivative(&spline, t);
+        }
+
+        //  Parameters beyond the domain are clamped to its ends
+        assert_eq!(spline.eval(-1.0), spline.eval(0.0));
+        assert_eq!(spline.eval(7.0), Vec2::new(6.0, 0.0));
+
+        let flat = spline.flatten(0.1);
+        assert_eq!(flat[0], Vec2::ZERO);
+        assert_eq!(*flat.last().unwrap(), Vec2::new(6.0, 0.0));
+
+        //  Collinear points give a straight line regardless of the parametrisation
+        let line = CatmullRom::with_alpha([(0.0, 0.0), (1.0, 1.0), (4.0, 4.0)], 1.0).unwrap();
+        assert!((0..=20)
+            .map(|k| line.eval(k as f32 / 10.0))
+            .all(|p| (p.x - p.y).abs() < 1e-4));
+
+        assert!(CatmullRom::new([(1.0, 1.0), (1.0, 1.0)]).is_err());
+    }
+
+    #[test]
+    fn b_spline() {
+        let control = [(0.0, 0.0), (1.0, 3.0), (3.0, 3.0), (4.0, 0.0), (6.0, 1.0)];
+        let spline = BSpline::uniform(control, 3).unwrap();
+        assert_eq!(
+            spline.knots(),
+            [0.0, 0.0, 0.0, 0.0, 0.5, 1.0, 1.0, 1.0, 1.0]
+        );
+
+        //  Clamped knots pin the curve to its first and last control points, heading towards their neighbours
+        assert_eq!(spline.eval(0.0), Vec2::ZERO);
+        assert!((spline.eval(1.0) - Vec2::new(6.0, 1.0)).length() < 1e-5);
+        assert!((spline.derivative(0.0) - Vec2::new(6.0, 18.0)).length() < 1e-3);
+        for t in [0.2, 0.5, 0.8] {
+            check_derivative(&spline, t);
+        }
+
+        //  The curve stays within the convex hull of its control points
+        assert!((0..=50)
+            .map(|k| spline.eval(k as f32 / 50.0))
+            .all(|p| p.x >= 0.0 && p.x <= 6.0 && p.y >= 0.0 && p.y <= 3.0));
+
+        //  A first degree B-spline is the polyline through its control points
+        let polyline = BSpline::uniform([(0.0, 0.0), (2.0, 0.0), (2.0, 2.0)], 1).unwrap();
+        assert_eq!(polyline.eval(0.25), Vec2::new(1.0, 0.0));
+        assert_eq!(polyline.eval(0.75), Vec2::new(2.0, 1.0));
+        assert!((polyline.arc_length() - 4.0).abs() < 1e-3);
+
+        assert!(BSpline::uniform([(0.0, 0.0), (1.0, 1.0)], 2).is_err());
+        assert!(BSpline::new([(0.0, 0.0), (1.0, 1.0)], 1, vec![0.0, 0.0, 1.0]).is_err());
+        assert!(BSpline::new([(0.0, 0.0), (1.0, 1.0)], 1, vec![0.0, 1.0, 0.5, 1.0]).is_err());
+        assert!(BSpline::new([(0.0, 0.0), (1.0, 1.0)], 1, vec![0.0, 0.0, 0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn nurbs() {
+        //  A quarter of a unit circle is described exactly by a rational quadratic curve
+        let quarter = Nurbs::uniform(
+            [
+                ((1.0, 0.0), 1.0),
+                ((1.0, 1.0), FRAC_1_SQRT_2),
+                ((0.0, 1.0), 1.0),
+            ],
+            2,
+        )
+        .unwrap();
+        assert!((0..=20)
+            .map(|k| quarter.eval(k as f32 / 20.0))
+            .all(|p| (p.length() - 1.0).abs() < 1e-5));
+
+        //  The tangent is perpendicular to the radius
+        for t in [0.1, 0.5, 0.7] {
+            assert!(quarter.eval(t).dot(quarter.derivative(t)).abs() < 1e-4);
+            check_derivative(&quarter, t);
+        }
+        assert!((quarter.arc_length() - std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+
+        let (point, weight) = quarter.control_points()[1];
+        assert!((point - Vec2::new(1.0, 1.0)).length() < 1e-6);
+        assert_eq!(weight, FRAC_1_SQRT_2);
+
+        //  Equal weights reduce to the plain B-spline
+        let control = [(0.0, 0.0), (1.0, 3.0), (3.0, 3.0), (4.0, 0.0)];
+        let plain = BSpline::uniform(control, 2).unwrap();
+        let weighted = Nurbs::uniform(control.map(|p| (p, 2.0)), 2).unwrap();
+        assert!((plain.eval(0.3) - weighted.eval(0.3)).length() < 1e-5);
+
+        assert!(Nurbs::uniform([((0.0, 0.0), 1.0), ((1.0, 1.0), 0.0)], 1).is_err());
+    }
+}
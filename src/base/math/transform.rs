@@ -0,0 +1,261 @@
+use {
+    super::Vec2,
+    std::ops::{Mul, MulAssign},
+};
+
+/**
+ * A 3x3 affine transformation matrix whose bottom row is always `[0, 0, 1]`
+ *
+ * Points are treated as column vectors, so `a * b` applies `b` first and then `a`
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform2D {
+    m: [[f32; 3]; 2],
+}
+
+impl Default for Transform2D {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transform2D {
+    pub const IDENTITY: Self = Self::new(1.0, 0.0, 0.0, 0.0, 1.0, 0.0);
+
+    /**
+     * Construct a transform from the top two rows of the matrix
+     */
+    pub const fn new(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> Self {
+        Self {
+            m: [[a, b, c], [d, e, f]],
+        }
+    }
+
+    /**
+     * A transform that moves every point by the provided offset
+     */
+    pub fn translation(x: f32, y: f32) -> Self {
+        Self::new(1.0, 0.0, x, 0.0, 1.0, y)
+    }
+
+    /**
+     * A transform that rotates every point about the origin by `angle` radians
+     */
+    pub fn rotation(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(cos, -sin, 0.0, sin, cos, 0.0)
+    }
+
+    /**
+     * A transform that scales every point away from the origin
+     */
+    pub fn scaling(x: f32, y: f32) -> Self {
+        Self::new(x, 0.0, 0.0, 0.0, y, 0.0)
+    }
+
+    /**
+     * A transform that skews every point by the provided angles (in radians) along each axis
+     */
+    pub fn skewing(x: f32, y: f32) -> Self {
+        Self::new(1.0, x.tan(), 0.0, y.tan(), 1.0, 0.0)
+    }
+
+    /**
+     * A transform that rotates every point about `center` by `angle` radians
+     */
+    pub fn rotation_about<T: Into<Vec2>>(center: T, angle: f32) -> Self {
+        let c = center.into();
+        Self::translation(c.x, c.y) * Self::rotation(angle) * Self::translation(-c.x, -c.y)
+    }
+
+    /**
+     * A transform that scales every point away from `center`
+     */
+    pub fn scaling_about<T: Into<Vec2>>(center: T, x: f32, y: f32) -> Self {
+        let c = center.into();
+        Self::translation(c.x, c.y) * Self::scaling(x, y) * Self::translation(-c.x, -c.y)
+    }
+
+    /**
+     * The full 3x3 matrix
+     */
+    pub const fn matrix(&self) -> [[f32; 3]; 3] {
+        [self.m[0], self.m[1], [0.0, 0.0, 1.0]]
+    }
+
+    /**
+     * Return the transform which applies `self` and then `other`
+     */
+    pub fn then(&self, other: &Self) -> Self {
+        *other * *self
+    }
+
+    /**
+     * The determinant of the linear part of the transform
+     */
+    pub fn determinant(&self) -> f32 {
+        self.m[0][0] * self.m[1][1] - self.m[0][1] * self.m[1][0]
+    }
+
+    /**
+     * The transform which undoes `self`, if `self` is not degenerate
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let [[a, b, c], [d, e, f]] = self.m;
+
+        Some(Self::new(
+            e / det,
+            -b / det,
+            (b * f - c * e) / det,
+            -d / det,
+            a / det,
+            (c * d - a * f) / det,
+        ))
+    }
+
+    /**
+     * Apply the transform to a point, including the translation
+     */
+    pub fn apply<T: Into<Vec2>>(&self, point: T) -> Vec2 {
+        let p = point.into();
+        let [[a, b, c], [d, e, f]] = self.m;
+        Vec2::new(a * p.x + b * p.y + c, d * p.x + e * p.y + f)
+    }
+
+    /**
+     * Apply the transform to a direction, ignoring the translation
+     */
+    pub fn apply_vector<T: Into<Vec2>>(&self, vector: T) -> Vec2 {
+        let v = vector.into();
+        let [[a, b, _], [d, e, _]] = self.m;
+        Vec2::new(a * v.x + b * v.y, d * v.x + e * v.y)
+    }
+
+    /**
+     * Return `true` if the transform only translates and scales, keeping axis-aligned lines axis-aligned
+     */
+    pub fn is_axis_aligned(&self) -> bool {
+        self.m[0][1] == 0.0 && self.m[1][0] == 0.0
+    }
+}
+
+impl Mul for Transform2D {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let [[a, b, c], [d, e, f]] = self.m;
+        let [[g, h, i], [j, k, l]] = rhs.m;
+
+        Self::new(
+            a * g + b * j,
+            a * h + b * k,
+            a * i + b * l + c,
+            d * g + e * j,
+            d * h + e * k,
+            d * i + e * l + f,
+        )
+    }
+}
+
+impl MulAssign for Transform2D {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl Mul<Vec2> for Transform2D {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Self::Output {
+        self.apply(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::f32::consts::FRAC_PI_2};
+
+    fn close(a: Vec2, b: Vec2) -> bool {
+        a.distance(b) < 1e-5
+    }
+
+    #[test]
+    fn apply() {
+        assert_eq!(
+            Transform2D::translation(2.0, 3.0).apply((1.0, 1.0)),
+            Vec2::new(3.0, 4.0)
+        );
+        assert_eq!(
+            Transform2D::scaling(2.0, -1.0).apply((1.0, 1.0)),
+            Vec2::new(2.0, -1.0)
+        );
+        assert!(close(
+            Transform2D::rotation(FRAC_PI_2).apply((1.0, 0.0)),
+            Vec2::new(0.0, 1.0)
+        ));
+        assert!(close(
+            Transform2D::rotation_about((1.0, 1.0), FRAC_PI_2).apply((2.0, 1.0)),
+            Vec2::new(1.0, 2.0)
+        ));
+
+        //  Directions ignore the translation
+        let transform = Transform2D::translation(5.0, 5.0) * Transform2D::scaling(2.0, 2.0);
+        assert_eq!(transform.apply_vector((1.0, 0.0)), Vec2::new(2.0, 0.0));
+    }
+
+    #[test]
+    fn composition() {
+        let (move_by, scale) = (
+            Transform2D::translation(1.0, 0.0),
+            Transform2D::scaling(2.0, 2.0),
+        );
+
+        //  `a * b` applies `b` first, which `then` spells out in order
+        assert_eq!((move_by * scale).apply((1.0, 1.0)), Vec2::new(3.0, 2.0));
+        assert_eq!((scale * move_by).apply((1.0, 1.0)), Vec2::new(4.0, 2.0));
+        assert_eq!(scale.then(&move_by), move_by * scale);
+
+        let mut transform = Transform2D::IDENTITY;
+        transform *= move_by;
+        assert_eq!(transform, move_by);
+    }
+
+    #[test]
+    fn nested_local_coordinates() {
+        //  Each step is applied in the local coordinates of the ones before it, as `Engine::translate`, `rotate` and `scale` do
+        let mut transform = Transform2D::IDENTITY;
+        transform *= Transform2D::translation(10.0, 0.0);
+        transform *= Transform2D::rotation(FRAC_PI_2);
+        transform *= Transform2D::scaling(2.0, 2.0);
+
+        assert!(close(transform.apply((1.0, 0.0)), Vec2::new(10.0, 2.0)));
+        assert!(close(transform.apply((0.0, 1.0)), Vec2::new(8.0, 0.0)));
+    }
+
+    #[test]
+    fn inverse() {
+        let transform =
+            Transform2D::rotation_about((3.0, -2.0), 0.7) * Transform2D::scaling(2.0, 0.5);
+        let inverse = transform.inverse().unwrap();
+
+        let p = Vec2::new(-4.0, 9.0);
+        assert!(close(inverse.apply(transform.apply(p)), p));
+        assert!((transform.determinant() - 1.0).abs() < 1e-6);
+        assert!(Transform2D::scaling(0.0, 1.0).inverse().is_none());
+    }
+
+    #[test]
+    fn axis_aligned() {
+        assert!(
+            (Transform2D::translation(1.0, 2.0) * Transform2D::scaling(3.0, -1.0))
+                .is_axis_aligned()
+        );
+        assert!(!Transform2D::rotation(0.1).is_axis_aligned());
+        assert!(!Transform2D::skewing(0.1, 0.0).is_axis_aligned());
+    }
+}
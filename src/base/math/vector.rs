@@ -0,0 +1,444 @@
+use {
+    sdl2::rect::Point,
+    std::{
+        fmt::Display,
+        ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+    },
+};
+
+/**
+ * A two dimensional vector of `f32` components, used wherever precision must be kept until drawing
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vec2 {
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    /**
+     * The dot product of two vectors
+     */
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y
+    }
+
+    /**
+     * The z-component of the cross product of two vectors, which is positive when `other` is counter-clockwise of `self` in a y-up space
+     */
+    pub fn cross(self, other: Self) -> f32 {
+        self.x * other.y - self.y * other.x
+    }
+
+    /**
+     * The squared length of the vector
+     */
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /**
+     * The length of the vector
+     */
+    pub fn length(self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /**
+     * The distance between two points
+     */
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+
+    /**
+     * The vector scaled to a length of one, or zero if it has no length
+     */
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+
+        if length > 0.0 {
+            self / length
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /**
+     * Interpolate linearly between two vectors, where `t` of `0` gives `self` and `1` gives `other`
+     */
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+
+    /**
+     * The vector rotated a quarter turn counter-clockwise in a y-up space
+     */
+    pub fn perp(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /**
+     * Extend the vector into three dimensions
+     */
+    pub fn extend(self, z: f32) -> Vec3 {
+        Vec3::new(self.x, self.y, z)
+    }
+
+    /**
+     * Round each component to the nearest integer
+     */
+    pub fn round(self) -> Self {
+        Self::new(self.x.round(), self.y.round())
+    }
+}
+
+impl Display for Vec2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("({}, {})", self.x, self.y))
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Vec2 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Div<f32> for Vec2 {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Self::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl From<Point> for Vec2 {
+    fn from(point: Point) -> Self {
+        Self::new(point.x() as f32, point.y() as f32)
+    }
+}
+
+impl From<(i32, i32)> for Vec2 {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x as f32, y as f32)
+    }
+}
+
+impl From<(f32, f32)> for Vec2 {
+    fn from((x, y): (f32, f32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Vec2> for Point {
+    fn from(v: Vec2) -> Self {
+        Point::new(v.x.round() as i32, v.y.round() as i32)
+    }
+}
+
+impl From<Vec2> for (f32, f32) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+/**
+ * A three dimensional vector of `f32` components
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vec3 {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0);
+    pub const X: Self = Self::new(1.0, 0.0, 0.0);
+    pub const Y: Self = Self::new(0.0, 1.0, 0.0);
+    pub const Z: Self = Self::new(0.0, 0.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /**
+     * The cross product of two vectors, which is perpendicular to both following the right-hand rule
+     */
+    pub fn cross(self, other: Self) -> Self {
+        Self::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /**
+     * Extend the vector into four dimensions
+     */
+    pub fn extend(self, w: f32) -> Vec4 {
+        Vec4::new(self.x, self.y, self.z, w)
+    }
+
+    /**
+     * The vector without its `z` component
+     */
+    pub fn truncate(self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+}
+
+/**
+ * A four dimensional vector of `f32` components, such as a point in homogeneous coordinates
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Vec4 {
+    pub const ZERO: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /**
+     * The vector without its `w` component
+     */
+    pub fn truncate(self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+}
+
+/**
+ * The methods and operators shared by every vector beyond `Vec2`, which work the same way on each component
+ */
+macro_rules! vector {
+    ($t:ident, $($c:ident),+) => {
+        impl $t {
+            /**
+             * The dot product of two vectors
+             */
+            pub fn dot(self, other: Self) -> f32 {
+                0.0 $(+ self.$c * other.$c)+
+            }
+
+            /**
+             * The squared length of the vector
+             */
+            pub fn length_squared(self) -> f32 {
+                self.dot(self)
+            }
+
+            /**
+             * The length of the vector
+             */
+            pub fn length(self) -> f32 {
+                self.length_squared().sqrt()
+            }
+
+            /**
+             * The distance between two points
+             */
+            pub fn distance(self, other: Self) -> f32 {
+                (other - self).length()
+            }
+
+            /**
+             * The vector scaled to a length of one, or zero if it has no length
+             */
+            pub fn normalize(self) -> Self {
+                let length = self.length();
+
+                if length > 0.0 {
+                    self / length
+                } else {
+                    Self::ZERO
+                }
+            }
+
+            /**
+             * Interpolate linearly between two vectors, where `t` of `0` gives `self` and `1` gives `other`
+             */
+            pub fn lerp(self, other: Self, t: f32) -> Self {
+                self + (other - self) * t
+            }
+        }
+
+        impl Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let components = [$(self.$c.to_string()),+];
+                f.write_str(&format!("({})", components.join(", ")))
+            }
+        }
+
+        impl Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs
+            }
+        }
+
+        impl Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs
+            }
+        }
+
+        impl Mul<f32> for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: f32) -> Self::Output {
+                Self { $($c: self.$c * rhs),+ }
+            }
+        }
+
+        impl Div<f32> for $t {
+            type Output = Self;
+
+            fn div(self, rhs: f32) -> Self::Output {
+                Self { $($c: self.$c / rhs),+ }
+            }
+        }
+
+        impl Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self { $($c: -self.$c),+ }
+            }
+        }
+    };
+}
+
+vector!(Vec3, x, y, z);
+vector!(Vec4, x, y, z, w);
+
+impl From<(f32, f32, f32)> for Vec3 {
+    fn from((x, y, z): (f32, f32, f32)) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<Vec3> for (f32, f32, f32) {
+    fn from(v: Vec3) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl From<(f32, f32, f32, f32)> for Vec4 {
+    fn from((x, y, z, w): (f32, f32, f32, f32)) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
+impl From<Vec4> for (f32, f32, f32, f32) {
+    fn from(v: Vec4) -> Self {
+        (v.x, v.y, v.z, v.w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vec3() {
+        let (a, b) = (Vec3::new(1.0, 2.0, 2.0), Vec3::new(3.0, 0.0, -1.0));
+        assert_eq!(a + b, Vec3::new(4.0, 2.0, 1.0));
+        assert_eq!(a - b, Vec3::new(-2.0, 2.0, 3.0));
+        assert_eq!(-a * 2.0, Vec3::new(-2.0, -4.0, -4.0));
+        assert_eq!(a.dot(b), 1.0);
+        assert_eq!(a.length(), 3.0);
+        assert_eq!(a.distance(a + Vec3::Z * 5.0), 5.0);
+        assert_eq!((a * 2.0).normalize(), a / 3.0);
+        assert_eq!(Vec3::ZERO.normalize(), Vec3::ZERO);
+        assert_eq!(a.lerp(b, 0.5), Vec3::new(2.0, 1.0, 0.5));
+
+        //  The cross product follows the right-hand rule
+        assert_eq!(Vec3::X.cross(Vec3::Y), Vec3::Z);
+        assert_eq!(Vec3::Y.cross(Vec3::X), -Vec3::Z);
+        assert_eq!(a.cross(b).dot(a), 0.0);
+
+        assert_eq!(a.to_string(), "(1, 2, 2)");
+        assert_eq!(Vec3::from((1.0, 2.0, 2.0)), a);
+    }
+
+    #[test]
+    fn conversions() {
+        let v = Vec2::new(1.5, -2.0);
+        assert_eq!(v.extend(3.0).extend(1.0), Vec4::new(1.5, -2.0, 3.0, 1.0));
+        assert_eq!(v.extend(3.0).extend(1.0).truncate().truncate(), v);
+        assert_eq!(v.perp(), Vec2::new(2.0, 1.5));
+        assert_eq!(v.round(), Vec2::new(2.0, -2.0));
+
+        let w = Vec4::new(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(w.length(), 2.0);
+        assert_eq!(w.to_string(), "(1, 1, 1, 1)");
+        assert_eq!(<(f32, f32, f32, f32)>::from(w), (1.0, 1.0, 1.0, 1.0));
+    }
+}
@@ -1,4 +1,11 @@
+pub mod camera;
+pub mod contour;
 pub mod engine;
 pub mod error;
+pub mod mask;
 pub mod math;
+pub mod path;
+pub mod plot;
 pub mod polygon;
+pub mod shape;
+pub mod stroke;
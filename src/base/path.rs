@@ -0,0 +1,366 @@
+use {
+    super::{
+        math::{Bounds, CubicBezier, QuadBezier, Transform2D, Vec2},
+        polygon::{MultiPolygon, Polygon},
+    },
+    std::f32::consts::FRAC_PI_2,
+};
+
+/**
+ * A single instruction of a `Path`
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathCommand {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+/**
+ * The points of one subpath of a flattened `Path`
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Subpath {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+}
+
+/**
+ * A series of straight and curved subpaths, built up in the same way as an HTML canvas path
+ *
+ * Every command which needs a starting point begins a new subpath at its first point when there is no current point
+ */
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+    start: Option<Vec2>,
+    current: Option<Vec2>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * The commands making up the path
+     */
+    pub fn commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /**
+     * The point where the next command will begin, if there is one
+     */
+    pub fn current(&self) -> Option<Vec2> {
+        self.current
+    }
+
+    /**
+     * Return `true` if the path has no commands
+     */
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /**
+     * Begin a new subpath at the provided point
+     */
+    pub fn move_to<T: Into<Vec2>>(&mut self, point: T) -> &mut Self {
+        let point = point.into();
+        self.commands.push(PathCommand::MoveTo(point));
+        self.start = Some(point);
+        self.current = Some(point);
+        self
+    }
+
+    /**
+     * Add a straight line from the current point to the provided point
+     */
+    pub fn line_to<T: Into<Vec2>>(&mut self, point: T) -> &mut Self {
+        let point = point.into();
+
+        if self.ensure(point) {
+            self.commands.push(PathCommand::LineTo(point));
+            self.current = Some(point);
+        }
+        self
+    }
+
+    /**
+     * Add a quadratic Bézier curve from the current point to `point`, pulled towards `control`
+     */
+    pub fn quad_to<T: Into<Vec2>>(&mut self, control: T, point: T) -> &mut Self {
+        let (control, point) = (control.into(), point.into());
+        self.ensure(control);
+        self.commands.push(PathCommand::QuadTo(control, point));
+        self.current = Some(point);
+        self
+    }
+
+    /**
+     * Add a cubic Bézier curve from the current point to `point`, leaving towards `c1` and arriving from `c2`
+     */
+    pub fn cubic_to<T: Into<Vec2>>(&mut self, c1: T, c2: T, point: T) -> &mut Self {
+        let (c1, c2, point) = (c1.into(), c2.into(), point.into());
+        self.ensure(c1);
+        self.commands.push(PathCommand::CubicTo(c1, c2, point));
+        self.current = Some(point);
+        self
+    }
+
+    /**
+     * Add a circular arc of the provided radius which is tangent to both the line from the current point to `p1`, and the line from `p1` to `p2`
+     *
+     * A straight line joins the current point to the start of the arc, and the arc ends where it meets the second line. When the lines are parallel or the radius is zero, a straight line to `p1` is added instead
+     */
+    pub fn arc_to<T: Into<Vec2>>(&mut self, p1: T, p2: T, radius: f32) -> &mut Self {
+        let (p1, p2) = (p1.into(), p2.into());
+
+        let Some(p0) = self.current else {
+            return self.move_to(p1);
+        };
+        let (u, v) = (p0 - p1, p2 - p1);
+        let (lu, lv) = (u.length(), v.length());
+
+        if radius <= 0.0 || lu == 0.0 || lv == 0.0 || u.cross(v).abs() <= f32::EPSILON * lu * lv {
+            return self.line_to(p1);
+        }
+        let (u, v) = (u / lu, v / lv);
+
+        //  The angle between the two lines, and the distance from their corner to where the arc touches each of them
+        let angle = u.dot(v).clamp(-1.0, 1.0).acos();
+        let tangent = radius / (angle / 2.0).tan();
+        let bisector = (u + v) / (u + v).length();
+        let center = p1 + bisector * (radius / (angle / 2.0).sin());
+
+        let (t1, t2) = (p1 + u * tangent, p1 + v * tangent);
+        let (d1, d2) = (t1 - center, t2 - center);
+        let start = d1.y.atan2(d1.x);
+        let sweep = d1.cross(d2).atan2(d1.dot(d2));
+
+        self.line_to(t1);
+        self.arc(center, radius, start, sweep);
+        self
+    }
+
+    /**
+     * Close the current subpath with a straight line back to its first point
+     */
+    pub fn close(&mut self) -> &mut Self {
+        if self.current.is_some() {
+            self.commands.push(PathCommand::Close);
+            self.current = self.start;
+        }
+        self
+    }
+
+    /**
+     * Add an arc around `center` from the angle `start` through `sweep` radians as cubic curves, which keeps the path free of any flattening until it is drawn
+     */
+    fn arc(&mut self, center: Vec2, radius: f32, start: f32, sweep: f32) {
+        let n = (sweep.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+        let step = sweep / n as f32;
+        let k = 4.0 / 3.0 * (step / 4.0).tan() * radius;
+
+        for i in 0..n {
+            let (a0, a1) = (start + step * i as f32, start + step * (i + 1) as f32);
+            let (d0, d1) = (Vec2::new(a0.cos(), a0.sin()), Vec2::new(a1.cos(), a1.sin()));
+
+            self.cubic_to(
+                center + d0 * radius + Vec2::new(-d0.y, d0.x) * k,
+                center + d1 * radius - Vec2::new(-d1.y, d1.x) * k,
+                center + d1 * radius,
+            );
+        }
+    }
+
+    /**
+     * Begin a subpath at the provided point when there is no current point, returning `true` if there already was one
+     */
+    fn ensure(&mut self, point: Vec2) -> bool {
+        if self.current.is_some() {
+            true
+        } else {
+            self.move_to(point);
+            false
+        }
+    }
+
+    /**
+     * The smallest axis-aligned box containing every point and control point of the path
+     */
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::from_points(self.commands.iter().flat_map(|command| match *command {
+            PathCommand::MoveTo(p) | PathCommand::LineTo(p) => vec![p],
+            PathCommand::QuadTo(c, p) => vec![c, p],
+            PathCommand::CubicTo(c1, c2, p) => vec![c1, c2, p],
+            PathCommand::Close => vec![],
+        }))
+    }
+
+    /**
+     * Return a new path with every point passed through the provided transform
+     */
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        let t = |p: Vec2| transform.apply(p);
+
+        Self {
+            commands: self
+                .commands
+                .iter()
+                .map(|command| match *command {
+                    PathCommand::MoveTo(p) => PathCommand::MoveTo(t(p)),
+                    PathCommand::LineTo(p) => PathCommand::LineTo(t(p)),
+                    PathCommand::QuadTo(c, p) => PathCommand::QuadTo(t(c), t(p)),
+                    PathCommand::CubicTo(c1, c2, p) => PathCommand::CubicTo(t(c1), t(c2), t(p)),
+                    PathCommand::Close => PathCommand::Close,
+                })
+                .collect(),
+            start: self.start.map(t),
+            current: self.current.map(t),
+        }
+    }
+
+    /**
+     * Approximate every subpath with straight segments which stay within `tolerance` of its curves
+     */
+    pub fn flatten(&self, tolerance: f32) -> Vec<Subpath> {
+        let mut subpaths: Vec<Subpath> = Vec::new();
+        let mut current = Subpath::default();
+
+        for command in &self.commands {
+            let last = current.points.last().copied();
+
+            match (*command, last) {
+                (PathCommand::MoveTo(p), _) => {
+                    subpaths.push(std::mem::take(&mut current));
+                    current.points.push(p)
+                }
+                (PathCommand::LineTo(p), Some(_)) => current.points.push(p),
+                (PathCommand::QuadTo(c, p), Some(last)) => current.points.extend(
+                    QuadBezier::new(last, c, p)
+                        .flatten(tolerance)
+                        .into_iter()
+                        .skip(1),
+                ),
+                (PathCommand::CubicTo(c1, c2, p), Some(last)) => current.points.extend(
+                    CubicBezier::new(last, c1, c2, p)
+                        .flatten(tolerance)
+                        .into_iter()
+                        .skip(1),
+                ),
+                (PathCommand::Close, Some(_)) => {
+                    //  Drawing continues from the start of a closed subpath
+                    let start = current.points[0];
+                    current.closed = true;
+                    subpaths.push(std::mem::take(&mut current));
+                    current.points.push(start)
+                }
+                _ => (),
+            }
+        }
+        subpaths.push(current);
+
+        //  Drop the single points left behind by moves and closes
+        subpaths.retain(|subpath| subpath.points.len() > 1);
+        subpaths
+    }
+
+    /**
+     * Flatten the path into contours for filling, implicitly closing every subpath
+     */
+    pub fn to_multi_polygon(&self, tolerance: f32) -> MultiPolygon {
+        MultiPolygon::new(
+            self.flatten(tolerance)
+                .into_iter()
+                .filter_map(|subpath| Polygon::new(subpath.points).ok()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn building() {
+        let mut path = Path::new();
+        assert!(path.is_empty() && path.current().is_none());
+
+        //  Commands without a current point begin a subpath instead
+        path.line_to((1.0, 1.0));
+        assert_eq!(path.commands(), [PathCommand::MoveTo(Vec2::new(1.0, 1.0))]);
+
+        path.line_to((4.0, 1.0))
+            .quad_to((5.0, 3.0), (4.0, 5.0))
+            .close();
+        assert_eq!(path.current(), Some(Vec2::new(1.0, 1.0)));
+        assert_eq!(path.commands().len(), 4);
+
+        let bounds = path.bounds().unwrap();
+        assert_eq!(
+            (bounds.min, bounds.max),
+            (Vec2::new(1.0, 1.0), Vec2::new(5.0, 5.0))
+        );
+
+        let moved = path.transform(&Transform2D::translation(1.0, 0.0));
+        assert_eq!(moved.current(), Some(Vec2::new(2.0, 1.0)));
+        assert_eq!(
+            moved.commands()[1],
+            PathCommand::LineTo(Vec2::new(5.0, 1.0))
+        );
+    }
+
+    #[test]
+    fn arc_to() {
+        //  Rounding the corner of a right angle
+        let mut path = Path::new();
+        path.move_to((0.0, 0.0))
+            .arc_to((10.0, 0.0), (10.0, 10.0), 2.0);
+
+        assert_eq!(path.commands()[1], PathCommand::LineTo(Vec2::new(8.0, 0.0)));
+        let end = path.current().unwrap();
+        assert!((end - Vec2::new(10.0, 2.0)).length() < 1e-5);
+
+        let center = Vec2::new(8.0, 2.0);
+        let points = &path.flatten(0.01)[0].points;
+        assert!(points[1..]
+            .iter()
+            .all(|p| (p.distance(center) - 2.0).abs() < 0.02));
+
+        //  Parallel lines fall back to a straight line to the corner
+        let mut straight = Path::new();
+        straight
+            .move_to((0.0, 0.0))
+            .arc_to((5.0, 0.0), (10.0, 0.0), 2.0);
+        assert_eq!(straight.current(), Some(Vec2::new(5.0, 0.0)));
+    }
+
+    #[test]
+    fn flatten() {
+        let mut path = Path::new();
+        path.move_to((0.0, 0.0))
+            .line_to((4.0, 0.0))
+            .line_to((4.0, 4.0))
+            .line_to((0.0, 4.0))
+            .close()
+            .move_to((10.0, 0.0))
+            .move_to((1.0, 1.0))
+            .cubic_to((1.0, 2.0), (2.0, 3.0), (3.0, 3.0));
+
+        //  Lone moves are dropped, and closed subpaths are marked as such
+        let subpaths = path.flatten(0.25);
+        assert_eq!(subpaths.len(), 2);
+        assert!(subpaths[0].closed && !subpaths[1].closed);
+        assert_eq!(subpaths[0].points.len(), 4);
+        assert_eq!(subpaths[1].points.last(), Some(&Vec2::new(3.0, 3.0)));
+
+        //  Filling closes every subpath, so the open curve encloses the area against its chord
+        let polygons = path.to_multi_polygon(0.25);
+        assert_eq!(polygons.len(), 2);
+        assert_eq!(polygons.polygons()[0].area(), 16.0);
+    }
+}
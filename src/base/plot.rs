@@ -0,0 +1,643 @@
+use {
+    super::{
+        error::Result,
+        math::{Function, Transform2D, Vec2},
+        shape::Rect,
+    },
+    crate::util::{
+        event::{EventType, MouseEvent},
+        mouse::{MouseButton, MouseWheelDirection},
+    },
+    sdl2::pixels::Color,
+};
+
+/**
+ * The smallest width or height of the area of the world shown by a plot, which keeps its transform invertible
+ */
+pub const MIN_WORLD_SIZE: f32 = 1e-6;
+
+/**
+ * The largest width or height of the area of the world shown by a plot
+ */
+pub const MAX_WORLD_SIZE: f32 = 1e12;
+
+/**
+ * The colors and layout options used when drawing a `Plot`
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotStyle {
+    pub background: Option<Color>,
+    pub axis: Color,
+    pub grid: Color,
+    pub minor_grid: Color,
+    pub text: Color,
+    /**
+     * The scale of the built-in font used for labels
+     */
+    pub text_scale: u32,
+    /**
+     * The number of minor grid cells between each pair of major grid lines, where `1` or less disables the minor grid
+     */
+    pub minor_divisions: u32,
+    /**
+     * The approximate distance in pixels between major grid lines
+     */
+    pub spacing: f32,
+    pub show_grid: bool,
+    pub show_legend: bool,
+}
+
+impl Default for PlotStyle {
+    fn default() -> Self {
+        Self {
+            background: Some(Color::RGB(255, 255, 255)),
+            axis: Color::RGB(0, 0, 0),
+            grid: Color::RGB(200, 200, 200),
+            minor_grid: Color::RGB(235, 235, 235),
+            text: Color::RGB(40, 40, 40),
+            text_scale: 1,
+            minor_divisions: 5,
+            spacing: 80.0,
+            show_grid: true,
+            show_legend: true,
+        }
+    }
+}
+
+/**
+ * A function drawn by a `Plot`, along with its color and the label shown for it in the legend
+ */
+#[derive(Debug)]
+pub struct Series {
+    pub function: Function,
+    pub color: Color,
+    pub label: String,
+}
+
+/**
+ * A graph which maps a rectangle of the world, where `y` points up, onto a viewport of the screen, and draws functions with axes, a grid and labels over it
+ */
+#[derive(Debug)]
+pub struct Plot {
+    world: Rect,
+    viewport: Rect,
+    series: Vec<Series>,
+    labels: (String, String),
+    style: PlotStyle,
+    controls: bool,
+    pan_button: MouseButton,
+    zoom_step: f32,
+    cursor: Vec2,
+    dragging: bool,
+}
+
+impl Plot {
+    /**
+     * Initialize a new `Plot` showing the provided area of the world within the provided area of the screen
+     *
+     * Both areas must be finite with a positive width and height, and the size of the world is clamped between `MIN_WORLD_SIZE` and `MAX_WORLD_SIZE`
+     */
+    pub fn new(world: Rect, viewport: Rect) -> Result<Self> {
+        if !valid(&world) || !valid(&viewport) {
+            return Err(
+                "The world and viewport of a plot must be finite with a positive width and height"
+                    .into(),
+            );
+        }
+        Ok(Self {
+            world: clamp_size(world),
+            viewport,
+            series: Vec::new(),
+            labels: ("x".to_owned(), "y".to_owned()),
+            style: PlotStyle::default(),
+            controls: false,
+            pan_button: MouseButton::Left,
+            zoom_step: 1.1,
+            cursor: Vec2::ZERO,
+            dragging: false,
+        })
+    }
+
+    /**
+     * The area of the world shown by the plot
+     */
+    pub fn world(&self) -> Rect {
+        self.world
+    }
+
+    /**
+     * Set the area of the world shown by the plot, with its size clamped between `MIN_WORLD_SIZE` and `MAX_WORLD_SIZE`, ignoring areas which are not finite or have no width or height
+     */
+    pub fn set_world(&mut self, world: Rect) {
+        if valid(&world) {
+            self.world = clamp_size(world)
+        }
+    }
+
+    /**
+     * The area of the screen the plot is drawn within
+     */
+    pub fn viewport(&self) -> Rect {
+        self.viewport
+    }
+
+    /**
+     * Set the area of the screen the plot is drawn within, ignoring areas which are not finite or have no width or height
+     */
+    pub fn set_viewport(&mut self, viewport: Rect) {
+        if valid(&viewport) {
+            self.viewport = viewport
+        }
+    }
+
+    pub fn style(&self) -> &PlotStyle {
+        &self.style
+    }
+
+    pub fn set_style(&mut self, style: PlotStyle) {
+        self.style = style
+    }
+
+    /**
+     * The labels of the x and y axes
+     */
+    pub fn labels(&self) -> (&str, &str) {
+        (&self.labels.0, &self.labels.1)
+    }
+
+    pub fn set_labels<T: Into<String>>(&mut self, x: T, y: T) {
+        self.labels = (x.into(), y.into())
+    }
+
+    /**
+     * The functions drawn by the plot
+     */
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    /**
+     * Add a function to the plot, labelled in the legend by its `Display` output
+     */
+    pub fn add_function(&mut self, function: Function, color: Color) {
+        let label = function.function().to_string();
+        self.add_labeled_function(function, color, label)
+    }
+
+    /**
+     * Add a function to the plot with its own label in the legend
+     */
+    pub fn add_labeled_function<T: Into<String>>(
+        &mut self,
+        function: Function,
+        color: Color,
+        label: T,
+    ) {
+        self.series.push(Series {
+            function,
+            color,
+            label: label.into(),
+        })
+    }
+
+    /**
+     * Remove every function from the plot
+     */
+    pub fn clear(&mut self) {
+        self.series.clear()
+    }
+
+    /**
+     * The transform mapping world coordinates onto the screen
+     */
+    pub fn transform(&self) -> Transform2D {
+        let (sx, sy) = (
+            self.viewport.width / self.world.width,
+            self.viewport.height / self.world.height,
+        );
+        Transform2D::translation(self.viewport.x, self.viewport.y + self.viewport.height)
+            * Transform2D::scaling(sx, -sy)
+            * Transform2D::translation(-self.world.x, -self.world.y)
+    }
+
+    /**
+     * Convert a point in world coordinates into screen coordinates
+     */
+    pub fn world_to_screen<T: Into<Vec2>>(&self, point: T) -> Vec2 {
+        self.transform().apply(point)
+    }
+
+    /**
+     * Convert a point in screen coordinates, such as those of a `MouseEvent`, into world coordinates, or `None` if the scale between the viewport and the world is too extreme to be undone
+     */
+    pub fn screen_to_world<T: Into<Vec2>>(&self, point: T) -> Option<Vec2> {
+        Some(self.transform().inverse()?.apply(point))
+    }
+
+    /**
+     * Move the shown area of the world by the provided offset in pixels, so that the graph follows the offset on the screen
+     */
+    pub fn pan<T: Into<Vec2>>(&mut self, offset: T) {
+        let Some(inverse) = self.transform().inverse() else {
+            return;
+        };
+        let offset = inverse.apply_vector(offset);
+        let world = Rect::new(
+            self.world.x - offset.x,
+            self.world.y - offset.y,
+            self.world.width,
+            self.world.height,
+        );
+        self.set_world(world)
+    }
+
+    /**
+     * Shrink the shown area of the world by `factor` while keeping the world point under the provided screen point fixed
+     *
+     * The size of the world stays between `MIN_WORLD_SIZE` and `MAX_WORLD_SIZE`, so zooming stops at either limit
+     */
+    pub fn zoom_at<T: Into<Vec2>>(&mut self, screen: T, factor: f32) {
+        if factor <= 0.0 || !factor.is_finite() {
+            return;
+        }
+        let Some(anchor) = self.screen_to_world(screen) else {
+            return;
+        };
+        let world = clamp_size(Rect::new(
+            self.world.x,
+            self.world.y,
+            self.world.width / factor,
+            self.world.height / factor,
+        ));
+
+        //  Each axis is scaled by the factor actually applied after clamping, so the anchor stays fixed at the limits
+        let (fx, fy) = (
+            self.world.width / world.width,
+            self.world.height / world.height,
+        );
+        self.set_world(Rect::new(
+            anchor.x - (anchor.x - self.world.x) / fx,
+            anchor.y - (anchor.y - self.world.y) / fy,
+            world.width,
+            world.height,
+        ))
+    }
+
+    /**
+     * The distance between major grid lines along an axis spanning `range` world units over `pixels` pixels
+     */
+    pub(crate) fn major_step(&self, range: f32, pixels: f32) -> f32 {
+        nice_step(range, (pixels / self.style.spacing.max(1.0)).max(1.0))
+    }
+
+    /**
+     * Return `true` if the built-in mouse controls are enabled
+     */
+    pub fn controls(&self) -> bool {
+        self.controls
+    }
+
+    /**
+     * Enable or disable mouse-wheel zooming towards the cursor and drag panning within the viewport, which are disabled by default
+     */
+    pub fn set_controls(&mut self, enabled: bool) {
+        self.controls = enabled;
+        self.dragging = false
+    }
+
+    /**
+     * Set the mouse button which pans the plot while held
+     */
+    pub fn set_pan_button(&mut self, button: MouseButton) {
+        self.pan_button = button
+    }
+
+    /**
+     * Set the factor the shown area is shrunk by for each step of the mouse wheel
+     */
+    pub fn set_zoom_step(&mut self, step: f32) {
+        if step > 0.0 {
+            self.zoom_step = step
+        }
+    }
+
+    /**
+     * Update the plot from the provided event, returning `true` if the shown area changed
+     */
+    pub fn handle_event(&mut self, event: &EventType) -> bool {
+        let EventType::Mouse(ref mouse) = *event else {
+            return false;
+        };
+        if !self.controls {
+            return false;
+        }
+        let inside = |plot: &Self, x: i32, y: i32| {
+            let (min, max) = (plot.viewport.min(), plot.viewport.max());
+            (min.x..=max.x).contains(&(x as f32)) && (min.y..=max.y).contains(&(y as f32))
+        };
+
+        match *mouse {
+            MouseEvent::MouseButtonDown {
+                mouse_btn, x, y, ..
+            } if mouse_btn == self.pan_button && inside(self, x, y) => {
+                self.cursor = Vec2::new(x as f32, y as f32);
+                self.dragging = true;
+                false
+            }
+            MouseEvent::MouseButtonUp { mouse_btn, .. } if mouse_btn == self.pan_button => {
+                self.dragging = false;
+                false
+            }
+            MouseEvent::MouseMotion { x, y, .. } => {
+                let cursor = Vec2::new(x as f32, y as f32);
+                let delta = cursor - self.cursor;
+                self.cursor = cursor;
+
+                if self.dragging {
+                    self.pan(delta);
+                }
+                self.dragging
+            }
+            MouseEvent::MouseWheel { y, direction, .. }
+                if y != 0 && inside(self, self.cursor.x as i32, self.cursor.y as i32) =>
+            {
+                let steps = if let MouseWheelDirection::Flipped = direction {
+                    -y
+                } else {
+                    y
+                };
+                self.zoom_at(self.cursor, self.zoom_step.powi(steps));
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/**
+ * Return `true` if the area is finite with a positive width and height
+ */
+fn valid(rect: &Rect) -> bool {
+    [rect.x, rect.y, rect.width, rect.height]
+        .iter()
+        .all(|v| v.is_finite())
+        && rect.width > 0.0
+        && rect.height > 0.0
+}
+
+/**
+ * The area with its width and height clamped between `MIN_WORLD_SIZE` and `MAX_WORLD_SIZE`
+ */
+fn clamp_size(rect: Rect) -> Rect {
+    Rect::new(
+        rect.x,
+        rect.y,
+        rect.width.clamp(MIN_WORLD_SIZE, MAX_WORLD_SIZE),
+        rect.height.clamp(MIN_WORLD_SIZE, MAX_WORLD_SIZE),
+    )
+}
+
+/**
+ * A "nice" step of 1, 2 or 5 times a power of ten which splits `range` into roughly `count` parts
+ */
+pub fn nice_step(range: f32, count: f32) -> f32 {
+    let raw = range.abs() / count.max(1.0);
+
+    if raw <= 0.0 || !raw.is_finite() {
+        return 1.0;
+    }
+    let magnitude = 10f32.powf(raw.log10().floor());
+
+    match raw / magnitude {
+        r if r < 1.5 => magnitude,
+        r if r < 3.5 => 2.0 * magnitude,
+        r if r < 7.5 => 5.0 * magnitude,
+        _ => 10.0 * magnitude,
+    }
+}
+
+/**
+ * The multiples of `step` within `[min, max]`
+ */
+pub fn ticks(min: f32, max: f32, step: f32) -> Vec<f32> {
+    if step <= 0.0 || !step.is_finite() || max < min {
+        return Vec::new();
+    }
+    let (first, last) = ((min / step).ceil() as i64, (max / step).floor() as i64);
+    (first..=last).map(|k| k as f32 * step).collect()
+}
+
+/**
+ * Format a tick value with just enough decimals for the provided step
+ */
+pub fn tick_label(value: f32, step: f32) -> String {
+    let decimals = (-step.log10().floor()).max(0.0) as usize;
+    let label = format!("{:.*}", decimals, value);
+
+    //  Rounding can leave a negative sign on zero
+    if label
+        .trim_start_matches('-')
+        .chars()
+        .all(|c| c == '0' || c == '.')
+    {
+        label.trim_start_matches('-').to_owned()
+    } else {
+        label
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::util::mouse::MouseState};
+
+    fn plot() -> Plot {
+        Plot::new(
+            Rect::new(-10.0, -10.0, 20.0, 20.0),
+            Rect::new(0.0, 0.0, 800.0, 600.0),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn invalid_areas() {
+        let world = Rect::new(-10.0, -10.0, 20.0, 20.0);
+        assert!(Plot::new(world, Rect::new(0.0, 0.0, 0.0, 600.0)).is_err());
+        assert!(Plot::new(Rect::new(0.0, 0.0, 20.0, 0.0), world).is_err());
+        assert!(Plot::new(Rect::new(f32::NAN, 0.0, 20.0, 20.0), world).is_err());
+
+        let mut plot = plot();
+        plot.set_viewport(Rect::new(0.0, 0.0, 800.0, 0.0));
+        plot.set_world(Rect::new(0.0, 0.0, f32::INFINITY, 1.0));
+        assert_eq!(plot.viewport(), Rect::new(0.0, 0.0, 800.0, 600.0));
+        assert_eq!(plot.world(), world);
+    }
+
+    #[test]
+    fn round_trip() {
+        let plot = plot();
+        assert_eq!(plot.world_to_screen((-10.0, -10.0)), Vec2::new(0.0, 600.0));
+        assert_eq!(plot.world_to_screen((10.0, 10.0)), Vec2::new(800.0, 0.0));
+        let center = plot.screen_to_world((400.0, 300.0)).unwrap();
+        assert!(center.distance(Vec2::ZERO) < 1e-5);
+    }
+
+    #[test]
+    fn zoom_limits() {
+        let mut plot = plot();
+        for _ in 0..2000 {
+            plot.zoom_at((400.0, 300.0), 1.1);
+        }
+        assert_eq!(plot.world().width, MIN_WORLD_SIZE);
+        assert!(plot.screen_to_world((400.0, 300.0)).is_some());
+
+        for _ in 0..4000 {
+            plot.zoom_at((400.0, 300.0), 1.0 / 1.1);
+        }
+        assert_eq!(plot.world().height, MAX_WORLD_SIZE);
+        assert!(plot.screen_to_world((0.0, 0.0)).is_some());
+    }
+
+    #[test]
+    fn zoom_keeps_anchor() {
+        let mut plot = plot();
+        let anchor = plot.screen_to_world((200.0, 150.0)).unwrap();
+        plot.zoom_at((200.0, 150.0), 2.0);
+
+        let after = plot.screen_to_world((200.0, 150.0)).unwrap();
+        assert!(after.distance(anchor) < 1e-4);
+        assert_eq!(plot.world().width, 10.0);
+    }
+
+    #[test]
+    fn pan() {
+        let mut plot = plot();
+        let grabbed = plot.screen_to_world((100.0, 100.0)).unwrap();
+
+        //  The world point follows the offset on the screen
+        plot.pan((40.0, -30.0));
+        let moved = plot.world_to_screen(grabbed);
+        assert!(moved.distance(Vec2::new(140.0, 70.0)) < 1e-3);
+        assert_eq!((plot.world().width, plot.world().height), (20.0, 20.0));
+    }
+
+    #[test]
+    fn series() {
+        let mut plot = plot();
+        plot.add_function(Function::from(((0, 1), (4, 9))), Color::RED);
+        plot.add_labeled_function(Function::from(((0, 0), (1, 1))), Color::BLUE, "identity");
+
+        assert_eq!(plot.series().len(), 2);
+        assert_eq!(plot.series()[0].label, "f(x) = 2x + 1");
+        assert_eq!(plot.series()[1].label, "identity");
+
+        plot.clear();
+        assert!(plot.series().is_empty());
+    }
+
+    #[test]
+    fn nice_steps() {
+        assert_eq!(nice_step(10.0, 10.0), 1.0);
+        assert_eq!(nice_step(10.0, 4.0), 2.0);
+        assert_eq!(nice_step(-10.0, 2.0), 5.0);
+        assert_eq!(nice_step(0.9, 1.0), 1.0);
+        assert!((nice_step(0.03, 10.0) - 0.002).abs() < 1e-9);
+        assert_eq!(nice_step(3e5, 3.0), 1e5);
+
+        //  Degenerate ranges fall back to a step of one
+        assert_eq!(nice_step(0.0, 5.0), 1.0);
+        assert_eq!(nice_step(f32::NAN, 5.0), 1.0);
+
+        //  Major grid lines are spaced by roughly the style's spacing in pixels
+        let plot = plot();
+        let pixels = plot.style().spacing * 10.0;
+        assert_eq!(plot.major_step(100.0, pixels), 10.0);
+    }
+
+    #[test]
+    fn ticks_and_labels() {
+        assert_eq!(ticks(-1.0, 2.5, 1.0), [-1.0, 0.0, 1.0, 2.0]);
+        assert_eq!(ticks(0.1, 0.9, 0.5), [0.5]);
+        assert!(ticks(1.0, 0.0, 1.0).is_empty());
+        assert!(ticks(0.0, 1.0, 0.0).is_empty());
+
+        assert_eq!(tick_label(2.0, 1.0), "2");
+        assert_eq!(tick_label(0.25, 0.05), "0.25");
+        assert_eq!(tick_label(1500.0, 500.0), "1500");
+        assert_eq!(tick_label(-0.0001, 0.1), "0.0");
+    }
+
+    #[test]
+    fn mouse_controls() {
+        let mut plot = plot();
+        let event = |mouse| EventType::Mouse(mouse);
+        let motion = |x, y| {
+            event(MouseEvent::MouseMotion {
+                timestamp: 0,
+                window_id: 0,
+                which: 0,
+                mousestate: MouseState::from_sdl_state(0),
+                x,
+                y,
+                xrel: 0,
+                yrel: 0,
+            })
+        };
+        let wheel = event(MouseEvent::MouseWheel {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            x: 0,
+            y: 1,
+            direction: MouseWheelDirection::Normal,
+        });
+
+        //  The controls are off until enabled
+        assert!(!plot.handle_event(&wheel));
+        plot.set_controls(true);
+
+        //  The wheel zooms towards the cursor
+        plot.handle_event(&motion(200, 150));
+        let anchor = plot.screen_to_world((200.0, 150.0)).unwrap();
+        assert!(plot.handle_event(&wheel));
+        assert!((plot.world().width - 20.0 / 1.1).abs() < 1e-4);
+        assert!(
+            plot.screen_to_world((200.0, 150.0))
+                .unwrap()
+                .distance(anchor)
+                < 1e-4
+        );
+
+        //  Dragging keeps the grabbed world point under the cursor
+        plot.handle_event(&event(MouseEvent::MouseButtonDown {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mouse_btn: MouseButton::Left,
+            clicks: 1,
+            x: 200,
+            y: 150,
+        }));
+        assert!(plot.handle_event(&motion(260, 100)));
+        assert!(
+            plot.screen_to_world((260.0, 100.0))
+                .unwrap()
+                .distance(anchor)
+                < 1e-4
+        );
+
+        plot.handle_event(&event(MouseEvent::MouseButtonUp {
+            timestamp: 0,
+            window_id: 0,
+            which: 0,
+            mouse_btn: MouseButton::Left,
+            clicks: 1,
+            x: 260,
+            y: 100,
+        }));
+        assert!(!plot.handle_event(&motion(300, 300)));
+
+        //  Outside of the viewport the wheel is ignored
+        plot.set_viewport(Rect::new(0.0, 0.0, 100.0, 100.0));
+        assert!(!plot.handle_event(&wheel));
+    }
+}
@@ -1,17 +1,56 @@
+mod boolean;
+mod clip;
+mod multi;
+mod offset;
+mod simplify;
+mod triangulate;
+mod validate;
+
+pub use {boolean::*, multi::*, validate::PolygonError};
+
 pub use sdl2::{pixels::Color, rect::Point};
 use {
-    super::math::{Function, Variable},
+    super::math::{
+        on_segment, orient2d, point_segment_distance, Bounds, Function, Transform2D, Variable, Vec2,
+    },
     crate::{error::Result, math::FunctionType},
     std::vec::IntoIter,
 };
 
+/**
+ * The rule used to decide which points are inside of a shape whose edges may overlap
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum FillRule {
+    /** A point is inside if a ray from it crosses the edges an odd number of times */
+    EvenOdd,
+    /** A point is inside if the edges wind around it at least once */
+    #[default]
+    NonZero,
+}
+
+impl FillRule {
+    /**
+     * Return `true` if the provided winding number is considered inside under this rule
+     */
+    pub fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            Self::EvenOdd => winding % 2 != 0,
+            Self::NonZero => winding != 0,
+        }
+    }
+}
+
+/**
+ * A closed ring of verticies, kept at `f32` precision until it is drawn
+ */
 #[derive(Clone, Debug)]
-pub struct Polygon(Vec<Point>);
+pub struct Polygon(Vec<Vec2>);
 
 impl Polygon {
-    pub fn new<A: Into<Point>, T: IntoIterator<Item = A>>(iter: T) -> Result<Self> {
-        //  Convert each value into a valid `Point`
-        let mut points = iter.into_iter().map(Into::into).collect::<Vec<Point>>();
+    pub fn new<A: Into<Vec2>, T: IntoIterator<Item = A>>(iter: T) -> Result<Self> {
+        //  Convert each value into a valid `Vec2`
+        let mut points = iter.into_iter().map(Into::into).collect::<Vec<Vec2>>();
 
         //  Remove any consecutive points
         points.dedup();
@@ -29,22 +68,58 @@ impl Polygon {
             }
             Ok(Polygon(points))
         } else {
-            Err("Not enough points (Required 3 or more)".into())
+            Err(PolygonError::TooFewPoints { count: n }.into())
         }
     }
 
     /**
      * The number of verticies
      */
+    #[allow(clippy::len_without_is_empty)] //  a valid polygon is never empty
     pub fn len(&self) -> usize {
         self.0.len() - 1
     }
 
     /**
-     * The points of the polygon excluding the tail connector
+     * The points of the polygon excluding the tail connector, rounded to the nearest pixel
      */
     pub fn points(&self) -> Vec<Point> {
-        self.0[..self.len()].to_vec()
+        self.vertices().iter().copied().map(Point::from).collect()
+    }
+
+    /**
+     * The verticies of the polygon excluding the tail connector
+     */
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.0[..self.len()]
+    }
+
+    /**
+     * Return a new polygon with every vertex passed through the provided transform
+     */
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        Self(self.0.iter().map(|&v| transform.apply(v)).collect())
+    }
+
+    /**
+     * Return a new polygon moved by the provided offset
+     */
+    pub fn translate(&self, x: f32, y: f32) -> Self {
+        self.transform(&Transform2D::translation(x, y))
+    }
+
+    /**
+     * Return a new polygon rotated about `center` by `angle` radians
+     */
+    pub fn rotate_about<T: Into<Vec2>>(&self, center: T, angle: f32) -> Self {
+        self.transform(&Transform2D::rotation_about(center, angle))
+    }
+
+    /**
+     * Return a new polygon scaled away from `center`
+     */
+    pub fn scale_about<T: Into<Vec2>>(&self, center: T, x: f32, y: f32) -> Self {
+        self.transform(&Transform2D::scaling_about(center, x, y))
     }
 
     /**
@@ -57,7 +132,7 @@ impl Polygon {
         b.remove(0);
         b.push(a[0]);
 
-        let pairs = a.into_iter().zip(b.into_iter());
+        let pairs = a.into_iter().zip(b);
 
         if let Some(v) = ignore {
             pairs
@@ -65,7 +140,7 @@ impl Polygon {
                     let f = Function::from(points);
 
                     if let FunctionType::Constant { var, .. } = &f.function() {
-                        (var != &v).then(|| f)
+                        (var != &v).then_some(f)
                     } else {
                         Some(f)
                     }
@@ -113,6 +188,106 @@ impl Polygon {
         self.intersection_at(Variable::Y, n, Some(Variable::Y))
     }
 
+    /**
+     * The smallest axis-aligned box containing every vertex
+     */
+    pub fn bounds(&self) -> Bounds {
+        Bounds::from_points(self.vertices().iter().copied()).unwrap()
+    }
+
+    /**
+     * The pairs of points connecting each vertex to the next, including the tail connector
+     */
+    fn edges(&self) -> impl Iterator<Item = (Vec2, Vec2)> + '_ {
+        self.0.windows(2).map(|pair| (pair[0], pair[1]))
+    }
+
+    /**
+     * Return `true` if the provided point lies on one of the edges of the polygon
+     */
+    pub fn on_edge<T: Into<Vec2>>(&self, point: T) -> bool {
+        let p = point.into();
+        self.edges().any(|(a, b)| on_segment(p, a, b))
+    }
+
+    /**
+     * The number of times the edges of the polygon wind around the provided point
+     */
+    pub fn winding_number<T: Into<Vec2>>(&self, point: T) -> i32 {
+        let p = point.into();
+        self.edges().fold(0, |winding, (a, b)| {
+            if a.y <= p.y {
+                if b.y > p.y && orient2d(a, b, p) > 0.0 {
+                    return winding + 1;
+                }
+            } else if b.y <= p.y && orient2d(a, b, p) < 0.0 {
+                return winding - 1;
+            }
+            winding
+        })
+    }
+
+    /**
+     * Return `true` if the provided point is inside of the polygon or on one of its edges
+     */
+    pub fn contains<T: Into<Vec2>>(&self, point: T) -> bool {
+        self.contains_with(point, FillRule::NonZero, true)
+    }
+
+    /**
+     * Return `true` if the provided point is inside of the polygon according to the specified `FillRule`, treating points on the edges as inside only if `edges` is `true`
+     */
+    pub fn contains_with<T: Into<Vec2>>(&self, point: T, rule: FillRule, edges: bool) -> bool {
+        let p = point.into();
+
+        if self.on_edge(p) {
+            edges
+        } else {
+            rule.is_inside(self.winding_number(p))
+        }
+    }
+
+    /**
+     * The shortest distance between the provided point and the edges of the polygon
+     */
+    pub fn distance_to<T: Into<Vec2>>(&self, point: T) -> f32 {
+        self.nearest_edge(point).1
+    }
+
+    /**
+     * The index of the edge closest to the provided point along with its distance, where edge `i` connects vertex `i` to the next
+     */
+    pub fn nearest_edge<T: Into<Vec2>>(&self, point: T) -> (usize, f32) {
+        let p = point.into();
+        nearest(self.edges().map(|(a, b)| point_segment_distance(p, a, b)))
+    }
+
+    /**
+     * The index of the vertex closest to the provided point along with its distance
+     */
+    pub fn nearest_vertex<T: Into<Vec2>>(&self, point: T) -> (usize, f32) {
+        let p = point.into();
+        nearest(self.vertices().iter().map(|&v| v.distance(p)))
+    }
+
+    /**
+     * The x-values at which the edges of the polygon cross the horizontal line at `y`, each paired with the direction of the edge (`1` for downwards on the screen, `-1` for upwards)
+     *
+     * Each edge includes its upper endpoint but not its lower one, so a vertex shared by two edges is only counted once
+     */
+    pub fn crossings_at_y(&self, y: f32) -> Vec<(f32, i32)> {
+        self.edges()
+            .filter_map(|(a, b)| {
+                let (top, bottom) = if a.y < b.y { (a, b) } else { (b, a) };
+
+                (y >= top.y && y < bottom.y).then(|| {
+                    let x = top.x + (y - top.y) / (bottom.y - top.y) * (bottom.x - top.x);
+                    (x, if b.y > a.y { 1 } else { -1 })
+                })
+            })
+            .collect()
+    }
+
     /**
      * A helper method for calculating a limit based on the provided bounds and comparison function pointers
      */
@@ -157,18 +332,124 @@ impl Polygon {
     }
 }
 
-impl<'a> From<&'a Polygon> for &'a [Point] {
+/**
+ * The index and value of the smallest distance
+ */
+fn nearest(distances: impl Iterator<Item = f32>) -> (usize, f32) {
+    distances.enumerate().fold(
+        (0, f32::INFINITY),
+        |best, (i, d)| {
+            if d < best.1 {
+                (i, d)
+            } else {
+                best
+            }
+        },
+    )
+}
+
+/**
+ * The closed ring of verticies, including the tail connector
+ *
+ * Verticies are stored at `f32` precision, so this replaces the former conversion into `&[Point]`. Use the conversion into `Vec<Point>` or `Polygon::points` for pixel coordinates
+ */
+impl<'a> From<&'a Polygon> for &'a [Vec2] {
     fn from(polygon: &'a Polygon) -> Self {
         &polygon.0
     }
 }
 
+/**
+ * The closed ring of verticies rounded to the nearest pixel, including the tail connector
+ */
+impl From<&Polygon> for Vec<Point> {
+    fn from(polygon: &Polygon) -> Self {
+        polygon.0.iter().copied().map(Point::from).collect()
+    }
+}
+
 impl IntoIterator for &Polygon {
     type Item = Point;
 
     type IntoIter = IntoIter<Point>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.0.clone().into_iter()
+        self.0
+            .iter()
+            .copied()
+            .map(Point::from)
+            .collect::<Vec<Point>>()
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Polygon {
+        Polygon::new([(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]).unwrap()
+    }
+
+    #[test]
+    fn contains() {
+        let square = square();
+        assert!(square.contains((2.0, 2.0)));
+        assert!(square.contains((4.0, 2.0)));
+        assert!(!square.contains((5.0, 2.0)));
+        assert!(!square.contains((2.0, -0.5)));
+
+        //  Points on the edges are only inside when edges are included
+        assert!(square.on_edge((4.0, 2.0)));
+        assert!(!square.contains_with((4.0, 2.0), FillRule::NonZero, false));
+        assert!(square.contains_with((0.0, 0.0), FillRule::EvenOdd, true));
+    }
+
+    #[test]
+    fn fill_rules() {
+        //  A pentagram winds twice around its center, which is only inside with the non-zero rule
+        let star = Polygon::new([
+            (0.0, 3.0),
+            (1.76, -2.43),
+            (-2.85, 0.93),
+            (2.85, 0.93),
+            (-1.76, -2.43),
+        ])
+        .unwrap();
+        assert_eq!(star.winding_number((0.0, 0.0)).abs(), 2);
+        assert!(star.contains_with((0.0, 0.0), FillRule::NonZero, true));
+        assert!(!star.contains_with((0.0, 0.0), FillRule::EvenOdd, true));
+
+        //  The points of the star wind once, so both rules agree
+        assert!(star.contains_with((0.0, 2.5), FillRule::EvenOdd, true));
+        assert_eq!(star.winding_number((5.0, 5.0)), 0);
+    }
+
+    #[test]
+    fn nearest() {
+        let square = square();
+        assert_eq!(square.distance_to((2.0, 2.0)), 2.0);
+        assert_eq!(square.distance_to((7.0, 4.0)), 3.0);
+        assert_eq!(square.distance_to((7.0, 8.0)), 5.0);
+
+        //  Edge `i` joins vertex `i` to the next, so edge 1 is the right side
+        assert_eq!(square.nearest_edge((5.0, 2.0)), (1, 1.0));
+        assert_eq!(square.nearest_edge((2.0, 5.0)), (2, 1.0));
+        assert_eq!(square.nearest_vertex((3.0, 3.5)), (2, 1.118034));
+    }
+
+    #[test]
+    fn transform() {
+        let moved = square()
+            .translate(0.25, 0.5)
+            .scale_about((0.0, 0.0), 2.0, 1.0);
+        assert_eq!(moved.vertices()[2], Vec2::new(8.5, 4.5));
+
+        //  Verticies keep their precision until they are rounded into pixels
+        assert_eq!(moved.points()[0], Point::new(1, 1));
+        assert_eq!(Vec::<Point>::from(&moved).len(), 5);
+
+        let turned = square().rotate_about((2.0, 2.0), std::f32::consts::PI);
+        assert!(turned.vertices()[0].distance(Vec2::new(4.0, 4.0)) < 1e-5);
     }
 }
@@ -0,0 +1,524 @@
+use {
+    super::{Contours, FillRule, MultiPolygon, Polygon},
+    crate::math::{orient2d_f64, Vec2},
+    std::collections::{HashMap, HashSet},
+};
+
+/**
+ * The set operation used to combine two shapes
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BooleanOp {
+    /** Everything covered by either shape */
+    Union,
+    /** Everything covered by both shapes */
+    Intersection,
+    /** Everything covered by the first shape but not the second */
+    Difference,
+    /** Everything covered by exactly one of the shapes */
+    Xor,
+}
+
+impl BooleanOp {
+    fn apply(&self, a: bool, b: bool) -> bool {
+        match self {
+            Self::Union => a || b,
+            Self::Intersection => a && b,
+            Self::Difference => a && !b,
+            Self::Xor => a != b,
+        }
+    }
+}
+
+impl Polygon {
+    /**
+     * The area covered by either this polygon or `other`
+     */
+    pub fn union<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        MultiPolygon::boolean(self, other, BooleanOp::Union)
+    }
+
+    /**
+     * The area covered by both this polygon and `other`
+     */
+    pub fn intersection<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        MultiPolygon::boolean(self, other, BooleanOp::Intersection)
+    }
+
+    /**
+     * The area covered by this polygon but not by `other`
+     */
+    pub fn difference<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        MultiPolygon::boolean(self, other, BooleanOp::Difference)
+    }
+
+    /**
+     * The area covered by exactly one of this polygon and `other`
+     */
+    pub fn xor<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        MultiPolygon::boolean(self, other, BooleanOp::Xor)
+    }
+}
+
+impl MultiPolygon {
+    /**
+     * The area covered by either this shape or `other`
+     */
+    pub fn union<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        Self::boolean(self, other, BooleanOp::Union)
+    }
+
+    /**
+     * The area covered by both this shape and `other`
+     */
+    pub fn intersection<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        Self::boolean(self, other, BooleanOp::Intersection)
+    }
+
+    /**
+     * The area covered by this shape but not by `other`
+     */
+    pub fn difference<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        Self::boolean(self, other, BooleanOp::Difference)
+    }
+
+    /**
+     * The area covered by exactly one of this shape and `other`
+     */
+    pub fn xor<C: Contours + ?Sized>(&self, other: &C) -> MultiPolygon {
+        Self::boolean(self, other, BooleanOp::Xor)
+    }
+
+    /**
+     * Combine two shapes with the provided set operation, treating the contours of each shape with the even-odd rule
+     *
+     * Every edge is split wherever it meets another, shared and overlapping edges are merged, and each remaining edge is kept only if the result is filled on exactly one side of it
+     */
+    pub fn boolean<A: Contours + ?Sized, B: Contours + ?Sized>(
+        a: &A,
+        b: &B,
+        op: BooleanOp,
+    ) -> MultiPolygon {
+        combine(a, FillRule::EvenOdd, b, FillRule::EvenOdd, op)
+    }
+}
+
+/**
+ * Combine two shapes with the provided set operation, deciding which points are inside of each shape with its own `FillRule`
+ */
+pub(crate) fn combine<A: Contours + ?Sized, B: Contours + ?Sized>(
+    a: &A,
+    a_rule: FillRule,
+    b: &B,
+    b_rule: FillRule,
+    op: BooleanOp,
+) -> MultiPolygon {
+    let operands = [rings(a), rings(b)];
+
+    let segments = operands
+        .iter()
+        .flatten()
+        .flat_map(|ring| (0..ring.len()).map(|i| (ring[i], ring[(i + 1) % ring.len()])))
+        .filter(|(p, q)| p != q)
+        .collect::<Vec<(P, P)>>();
+
+    if segments.is_empty() {
+        return MultiPolygon::default();
+    }
+    let (min, max) = segments.iter().fold(
+        ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]),
+        |(min, max), (p, q)| {
+            (
+                [min[0].min(p[0]).min(q[0]), min[1].min(p[1]).min(q[1])],
+                [max[0].max(p[0]).max(q[0]), max[1].max(p[1]).max(q[1])],
+            )
+        },
+    );
+    let scale = (max[0] - min[0]).max(max[1] - min[1]).max(1.0);
+
+    //  The verticies come from `f32` values, so anything closer than their precision is the same point
+    let magnitude = [min[0], min[1], max[0], max[1]]
+        .into_iter()
+        .fold(scale, |m, v| m.max(v.abs()));
+    let eps = magnitude * 1e-6;
+
+    //  Split every segment at each point where it meets another
+    let mut cuts = vec![vec![0.0, 1.0]; segments.len()];
+    for i in 0..segments.len() {
+        for j in (i + 1)..segments.len() {
+            split(&segments, i, j, eps, &mut cuts)
+        }
+    }
+
+    //  Merge verticies which are within `eps` of each other and remove duplicate edges
+    let mut pool = Pool::new(eps);
+    let mut edges = HashSet::new();
+    for (&(p, q), cuts) in segments.iter().zip(cuts.iter_mut()) {
+        cuts.sort_by(f64::total_cmp);
+        cuts.dedup();
+
+        let ids = cuts
+            .iter()
+            .map(|&t| pool.insert(lerp(p, q, t)))
+            .collect::<Vec<usize>>();
+
+        for pair in ids.windows(2) {
+            if pair[0] != pair[1] {
+                edges.insert((pair[0].min(pair[1]), pair[0].max(pair[1])));
+            }
+        }
+    }
+
+    //  Keep the edges with the result on exactly one side, directed so the result is on the left
+    let inside = |p: P| {
+        op.apply(
+            a_rule.is_inside(winding(&operands[0], p)),
+            b_rule.is_inside(winding(&operands[1], p)),
+        )
+    };
+    let mut directed = Vec::new();
+    for (u, v) in edges {
+        let (p, q) = (pool.points[u], pool.points[v]);
+        let d = sub(q, p);
+        let length = d[0].hypot(d[1]);
+
+        let offset = (length * 0.25).min(eps * 100.0);
+        let normal = [-d[1] / length * offset, d[0] / length * offset];
+        let mid = lerp(p, q, 0.5);
+
+        let left = inside([mid[0] + normal[0], mid[1] + normal[1]]);
+        let right = inside([mid[0] - normal[0], mid[1] - normal[1]]);
+
+        if left != right {
+            directed.push(if left { (u, v) } else { (v, u) });
+        }
+    }
+    link(&pool.points, &directed)
+}
+
+/**
+ * A point in `f64` precision
+ */
+type P = [f64; 2];
+
+fn sub(a: P, b: P) -> P {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn cross(a: P, b: P) -> f64 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+fn dot(a: P, b: P) -> f64 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn lerp(a: P, b: P, t: f64) -> P {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+}
+
+/**
+ * The verticies of each contour in `f64` precision, excluding the tail connector
+ */
+fn rings<C: Contours + ?Sized>(shape: &C) -> Vec<Vec<P>> {
+    shape
+        .contours()
+        .iter()
+        .map(|polygon| {
+            polygon
+                .vertices()
+                .iter()
+                .map(|v| [v.x as f64, v.y as f64])
+                .collect()
+        })
+        .collect()
+}
+
+/**
+ * The number of times the edges of the rings wind around `p`
+ */
+fn winding(rings: &[Vec<P>], p: P) -> i32 {
+    let mut winding = 0;
+
+    for ring in rings {
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+
+            //  Edges crossing upwards count when `p` lies to their left, and those crossing downwards when it lies to their right
+            if a[1] <= p[1] && b[1] > p[1] && orient2d_f64(a, b, p) > 0.0 {
+                winding += 1
+            } else if a[1] > p[1] && b[1] <= p[1] && orient2d_f64(a, b, p) < 0.0 {
+                winding -= 1
+            }
+        }
+    }
+    winding
+}
+
+/**
+ * Record the parameters along segments `i` and `j` at which they meet, including the ends of any overlap
+ */
+fn split(segments: &[(P, P)], i: usize, j: usize, eps: f64, cuts: &mut [Vec<f64>]) {
+    let ((p, p2), (q, q2)) = (segments[i], segments[j]);
+    let (r, s, qp) = (sub(p2, p), sub(q2, q), sub(q, p));
+    let (rr, ss) = (dot(r, r), dot(s, s));
+    let denom = cross(r, s);
+
+    if denom.abs() <= 1e-12 * rr.sqrt() * ss.sqrt() {
+        //  Parallel segments only meet if they are collinear, in which case each is cut at the ends of the other
+        if cross(qp, r).abs() <= eps * rr.sqrt() {
+            for e in [q, q2] {
+                let t = dot(sub(e, p), r) / rr;
+                if t > 0.0 && t < 1.0 {
+                    cuts[i].push(t)
+                }
+            }
+            for e in [p, p2] {
+                let u = dot(sub(e, q), s) / ss;
+                if u > 0.0 && u < 1.0 {
+                    cuts[j].push(u)
+                }
+            }
+        }
+        return;
+    }
+    let t = cross(qp, s) / denom;
+    let u = cross(qp, r) / denom;
+    let (tol_t, tol_u) = (eps / rr.sqrt(), eps / ss.sqrt());
+
+    if t >= -tol_t && t <= 1.0 + tol_t && u >= -tol_u && u <= 1.0 + tol_u {
+        cuts[i].push(t.clamp(0.0, 1.0));
+        cuts[j].push(u.clamp(0.0, 1.0));
+    }
+}
+
+/**
+ * A set of unique verticies, where points within `eps` of each other are treated as the same vertex
+ */
+struct Pool {
+    points: Vec<P>,
+    grid: HashMap<(i64, i64), Vec<usize>>,
+    eps: f64,
+}
+
+impl Pool {
+    fn new(eps: f64) -> Self {
+        Self {
+            points: Vec::new(),
+            grid: HashMap::new(),
+            eps,
+        }
+    }
+
+    fn insert(&mut self, p: P) -> usize {
+        let cell = self.eps * 4.0;
+        let (cx, cy) = ((p[0] / cell).floor() as i64, (p[1] / cell).floor() as i64);
+
+        for x in (cx - 1)..=(cx + 1) {
+            for y in (cy - 1)..=(cy + 1) {
+                if let Some(ids) = self.grid.get(&(x, y)) {
+                    for &id in ids {
+                        let d = sub(self.points[id], p);
+                        if d[0].abs() <= self.eps && d[1].abs() <= self.eps {
+                            return id;
+                        }
+                    }
+                }
+            }
+        }
+        self.points.push(p);
+        self.grid
+            .entry((cx, cy))
+            .or_default()
+            .push(self.points.len() - 1);
+        self.points.len() - 1
+    }
+}
+
+/**
+ * Join the directed edges into closed rings, turning as far left as possible wherever several edges meet so that touching rings stay separate
+ */
+fn link(points: &[P], edges: &[(usize, usize)]) -> MultiPolygon {
+    let mut outgoing: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (i, &(u, _)) in edges.iter().enumerate() {
+        outgoing.entry(u).or_default().push(i)
+    }
+    let mut used = vec![false; edges.len()];
+    let mut polygons = Vec::new();
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = vec![edges[first].0];
+        let mut position = HashMap::from([(edges[first].0, 0)]);
+        let mut current = first;
+
+        loop {
+            used[current] = true;
+            let (u, v) = edges[current];
+
+            //  Returning to a vertex of the walk closes a ring, which is split off while the walk carries on from it
+            if let Some(&k) = position.get(&v) {
+                let closed = ring.split_off(k);
+                closed.iter().for_each(|w| {
+                    position.remove(w);
+                });
+                polygons.extend(simplify(points, closed));
+
+                if k == 0 {
+                    break;
+                }
+            }
+            position.insert(v, ring.len());
+            ring.push(v);
+
+            let incoming = sub(points[v], points[u]);
+            let next = outgoing.get(&v).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .copied()
+                    .filter(|&e| !used[e])
+                    .map(|e| {
+                        let out = sub(points[edges[e].1], points[v]);
+                        (e, cross(incoming, out).atan2(dot(incoming, out)))
+                    })
+                    .max_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|(e, _)| e)
+            });
+
+            match next {
+                Some(e) => current = e,
+                None => {
+                    //  An edge lost to rounding leaves the walk stuck, so the gap is closed rather than dropping what was found
+                    polygons.extend(simplify(points, ring));
+                    break;
+                }
+            }
+        }
+    }
+    MultiPolygon::new(polygons)
+}
+
+/**
+ * Remove collinear verticies and spikes from a ring, returning it as a polygon if anything remains
+ */
+fn simplify(points: &[P], mut ring: Vec<usize>) -> Option<Polygon> {
+    let mut changed = true;
+
+    while changed && ring.len() >= 3 {
+        changed = false;
+
+        for i in 0..ring.len() {
+            let n = ring.len();
+            let (a, b, c) = (
+                points[ring[(i + n - 1) % n]],
+                points[ring[i]],
+                points[ring[(i + 1) % n]],
+            );
+            let (ab, bc) = (sub(b, a), sub(c, b));
+
+            if cross(ab, bc).abs() <= 1e-6 * dot(ab, ab).sqrt() * dot(bc, bc).sqrt() {
+                ring.remove(i);
+                changed = true;
+                break;
+            }
+        }
+    }
+    if ring.len() < 3 {
+        return None;
+    }
+    Polygon::new(
+        ring.into_iter()
+            .map(|i| Vec2::new(points[i][0] as f32, points[i][1] as f32)),
+    )
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Polygon {
+        Polygon::new([(x, y), (x + size, y), (x + size, y + size), (x, y + size)]).unwrap()
+    }
+
+    fn assert_area(shape: &MultiPolygon, pieces: usize, area: f32) {
+        assert_eq!(shape.len(), pieces, "{:?}", shape);
+        assert!((shape.area() - area).abs() < 1e-3, "{:?}", shape);
+    }
+
+    #[test]
+    fn overlapping() {
+        let (a, b) = (square(0.0, 0.0, 10.0), square(5.0, 5.0, 10.0));
+
+        assert_area(&a.union(&b), 1, 175.0);
+        assert_area(&a.intersection(&b), 1, 25.0);
+        assert_area(&a.difference(&b), 1, 75.0);
+        assert_area(&a.xor(&b), 2, 150.0);
+    }
+
+    #[test]
+    fn touching_edges() {
+        let (a, b) = (square(0.0, 0.0, 10.0), square(10.0, 0.0, 10.0));
+
+        assert_area(&a.union(&b), 1, 200.0);
+        assert_area(&a.intersection(&b), 0, 0.0);
+        assert_area(&a.difference(&b), 1, 100.0);
+        assert_area(&a.xor(&b), 1, 200.0);
+    }
+
+    #[test]
+    fn touching_corners() {
+        let (a, b) = (square(0.0, 0.0, 10.0), square(10.0, 10.0, 10.0));
+
+        //  Rings which only share a vertex stay separate
+        assert_area(&a.union(&b), 2, 200.0);
+        assert_area(&a.intersection(&b), 0, 0.0);
+        assert_area(&a.difference(&b), 1, 100.0);
+    }
+
+    #[test]
+    fn nested() {
+        let (outer, inner) = (square(0.0, 0.0, 10.0), square(3.0, 3.0, 4.0));
+
+        assert_area(&outer.union(&inner), 1, 100.0);
+        assert_area(&outer.intersection(&inner), 1, 16.0);
+        assert_area(&inner.difference(&outer), 0, 0.0);
+
+        //  Cutting out the inner square leaves a hole wound the other way
+        let ring = outer.difference(&inner);
+        assert_area(&ring, 2, 84.0);
+        assert!(!ring.contains((5.0, 5.0)));
+        assert!(ring.contains((1.0, 5.0)));
+        assert_area(&outer.xor(&inner), 2, 84.0);
+    }
+
+    #[test]
+    fn disjoint() {
+        let (a, b) = (square(0.0, 0.0, 10.0), square(20.0, 0.0, 5.0));
+
+        assert_area(&a.union(&b), 2, 125.0);
+        assert_area(&a.intersection(&b), 0, 0.0);
+        assert_area(&a.difference(&b), 1, 100.0);
+        assert_area(&a.xor(&b), 2, 125.0);
+    }
+
+    #[test]
+    fn identical() {
+        let a = square(0.0, 0.0, 10.0);
+
+        assert_area(&a.union(&a), 1, 100.0);
+        assert_area(&a.intersection(&a), 1, 100.0);
+        assert_area(&a.difference(&a), 0, 0.0);
+    }
+
+    #[test]
+    fn link_keeps_unfinished_rings() {
+        let points = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]];
+
+        //  The edge back to the start is missing, as if it had been lost to rounding
+        let shape = link(&points, &[(0, 1), (1, 2), (2, 3)]);
+        assert_area(&shape, 1, 16.0);
+    }
+}
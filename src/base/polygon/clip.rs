@@ -0,0 +1,395 @@
+use {
+    super::{MultiPolygon, Polygon},
+    crate::math::{orient2d, project, segment_intersection, Bounds, SegmentIntersection, Vec2},
+};
+
+/**
+ * A point of a ring while clipping, which may be where it crosses the other ring
+ */
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    point: Vec2,
+    crossing: Option<usize>,
+}
+
+/**
+ * How the border of the subject passes through a point it shares with the border of the clip
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Passage {
+    Enter,
+    Leave,
+    /** The subject only touches the border, staying on the same side of it */
+    Touch,
+}
+
+/**
+ * A point where the rings meet, along with where it lies in each ring
+ */
+#[derive(Clone, Copy, Debug)]
+struct Crossing {
+    point: Vec2,
+    subject: (usize, f32),
+    clip: (usize, f32),
+    passage: Passage,
+}
+
+impl Polygon {
+    /**
+     * Clip the polygon to an axis-aligned rectangle using the Sutherland-Hodgman algorithm, returning `None` if nothing of it remains
+     */
+    pub fn clip_to_rect<T: Into<Bounds>>(&self, rect: T) -> Option<Polygon> {
+        let Bounds { min, max } = rect.into();
+        let clip = [
+            min,
+            Vec2::new(max.x, min.y),
+            max,
+            Vec2::new(min.x, max.y),
+            min,
+        ];
+        sutherland_hodgman(self.vertices(), &clip)
+    }
+
+    /**
+     * Clip the polygon to a convex region using the Sutherland-Hodgman algorithm, returning `None` if nothing of it remains
+     *
+     * The result is always a single polygon, so parts of a concave polygon which are split apart by the clip stay joined along its border. Use `clip` to separate them
+     */
+    pub fn clip_to_convex(&self, clip: &Polygon) -> Option<Polygon> {
+        sutherland_hodgman(self.vertices(), &oriented(clip))
+    }
+
+    /**
+     * Clip the polygon to any other polygon using the Weiler-Atherton algorithm, returning each separate piece which lies within it
+     *
+     * Both polygons should be simple, and the pieces wind counter-clockwise in a y-up space
+     */
+    pub fn clip(&self, clip: &Polygon) -> MultiPolygon {
+        let (subject, region) = (oriented(self), oriented(clip));
+        let crossings = crossings(&subject, &region);
+
+        let subject_nodes = nodes(&subject, &crossings, |c| c.subject);
+        let region_nodes = nodes(&region, &crossings, |c| c.clip);
+        let crossings = classify(crossings, &subject_nodes, clip);
+
+        if !crossings.iter().any(|c| c.passage == Passage::Enter) {
+            return self.clip_without_crossings(clip);
+        }
+
+        //  Where each crossing lies in the node lists of both rings
+        let position =
+            |nodes: &[Node], id: usize| nodes.iter().position(|n| n.crossing == Some(id)).unwrap();
+        let positions = (0..crossings.len())
+            .map(|id| (position(&subject_nodes, id), position(&region_nodes, id)))
+            .collect::<Vec<(usize, usize)>>();
+
+        let mut visited = vec![false; crossings.len()];
+        let mut pieces = Vec::new();
+
+        for start in 0..crossings.len() {
+            if visited[start] || crossings[start].passage != Passage::Enter {
+                continue;
+            }
+
+            //  Follow the subject from where it enters until it leaves, then the clip until the subject enters again
+            let (mut on_subject, mut k) = (true, positions[start].0);
+            let mut ring = Vec::new();
+            let limit = subject_nodes.len() + region_nodes.len();
+
+            while ring.len() <= limit {
+                let nodes = if on_subject {
+                    &subject_nodes
+                } else {
+                    &region_nodes
+                };
+                ring.push(nodes[k].point);
+                k = (k + 1) % nodes.len();
+
+                if let Some(id) = nodes[k].crossing {
+                    if id == start {
+                        break;
+                    }
+                    visited[id] = true;
+
+                    let passage = crossings[id].passage;
+                    if (on_subject && passage == Passage::Leave)
+                        || (!on_subject && passage == Passage::Enter)
+                    {
+                        on_subject = !on_subject;
+                        k = if on_subject {
+                            positions[id].0
+                        } else {
+                            positions[id].1
+                        };
+                    }
+                }
+            }
+            visited[start] = true;
+
+            if let Ok(piece) = Polygon::new(ring) {
+                pieces.push(piece)
+            }
+        }
+        MultiPolygon::new(pieces)
+    }
+
+    /**
+     * Clip the polygon when its border never crosses the border of the clip, so that one either contains the other or they are apart
+     */
+    fn clip_without_crossings(&self, clip: &Polygon) -> MultiPolygon {
+        //  A point along the border which is not shared with the clip settles which side the polygon is on
+        let outside = self
+            .edges()
+            .map(|(a, b)| (a + b) / 2.0)
+            .find(|&m| !clip.on_edge(m))
+            .is_some_and(|m| !clip.contains(m));
+
+        if !outside {
+            MultiPolygon::new([Polygon(oriented(self))])
+        } else if clip.vertices().iter().all(|&v| self.contains(v)) {
+            MultiPolygon::new([Polygon(oriented(clip))])
+        } else {
+            MultiPolygon::default()
+        }
+    }
+}
+
+/**
+ * The closed ring of the polygon winding counter-clockwise in a y-up space
+ */
+fn oriented(polygon: &Polygon) -> Vec<Vec2> {
+    if polygon.signed_area() < 0.0 {
+        polygon.reversed().0
+    } else {
+        polygon.0.clone()
+    }
+}
+
+/**
+ * Clip the points to each side of a closed convex ring in turn, keeping whatever lies to the left of the side or on it
+ */
+fn sutherland_hodgman(subject: &[Vec2], clip: &[Vec2]) -> Option<Polygon> {
+    let mut output = subject.to_vec();
+
+    for side in clip.windows(2) {
+        let (a, b) = (side[0], side[1]);
+        if a == b || output.is_empty() {
+            continue;
+        }
+        let input = std::mem::take(&mut output);
+
+        for (k, &s) in input.iter().enumerate() {
+            let e = input[(k + 1) % input.len()];
+            let (ds, de) = (orient2d(a, b, s), orient2d(a, b, e));
+
+            if ds >= 0.0 {
+                output.push(s)
+            }
+            if (ds > 0.0 && de < 0.0) || (ds < 0.0 && de > 0.0) {
+                let t = ds / (ds - de);
+                output.push(Vec2::new(
+                    (s.x as f64 + (e.x - s.x) as f64 * t) as f32,
+                    (s.y as f64 + (e.y - s.y) as f64 * t) as f32,
+                ))
+            }
+        }
+    }
+    Polygon::new(output).ok()
+}
+
+/**
+ * Every point where the borders of two closed rings meet, each found once
+ */
+fn crossings(subject: &[Vec2], clip: &[Vec2]) -> Vec<Crossing> {
+    //  A point at the end of an edge is recorded as the start of the next one, so that every point has one position
+    let locate = |ring: &[Vec2], i: usize, p: Vec2| {
+        let n = ring.len() - 1;
+        if p == ring[i] {
+            (i, 0.0)
+        } else if p == ring[i + 1] {
+            ((i + 1) % n, 0.0)
+        } else {
+            (i, project(p, ring[i], ring[i + 1]))
+        }
+    };
+    let mut crossings: Vec<Crossing> = Vec::new();
+
+    for i in 0..subject.len() - 1 {
+        for j in 0..clip.len() - 1 {
+            let points =
+                match segment_intersection(subject[i], subject[i + 1], clip[j], clip[j + 1]) {
+                    SegmentIntersection::None => continue,
+                    SegmentIntersection::Point(p) => vec![p],
+                    SegmentIntersection::Overlap(p, q) => vec![p, q],
+                };
+
+            for p in points {
+                if crossings.iter().all(|c| c.point != p) {
+                    crossings.push(Crossing {
+                        point: p,
+                        subject: locate(subject, i, p),
+                        clip: locate(clip, j, p),
+                        passage: Passage::Touch,
+                    })
+                }
+            }
+        }
+    }
+    crossings
+}
+
+/**
+ * The verticies of a closed ring with the crossings inserted along its edges in order
+ *
+ * Crossings at a vertex take its place, and every crossing appears exactly once
+ */
+fn nodes<F: Fn(&Crossing) -> (usize, f32)>(
+    ring: &[Vec2],
+    crossings: &[Crossing],
+    position: F,
+) -> Vec<Node> {
+    let at_vertex = |c: &Crossing| {
+        let (i, t) = position(c);
+        t == 0.0 && ring[i] == c.point
+    };
+    let mut entries = ring[..ring.len() - 1]
+        .iter()
+        .enumerate()
+        .map(|(i, &point)| {
+            let crossing = crossings
+                .iter()
+                .position(|c| at_vertex(c) && position(c).0 == i);
+            ((i, 0.0), Node { point, crossing })
+        })
+        .collect::<Vec<((usize, f32), Node)>>();
+
+    //  The sort is stable, so crossings which round onto a vertex still follow it
+    entries.extend(
+        crossings
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !at_vertex(c))
+            .map(|(id, c)| {
+                let node = Node {
+                    point: c.point,
+                    crossing: Some(id),
+                };
+                (position(c), node)
+            }),
+    );
+    entries.sort_by(|a, b| a.0 .0.cmp(&b.0 .0).then(a.0 .1.total_cmp(&b.0 .1)));
+    entries.into_iter().map(|(_, node)| node).collect()
+}
+
+/**
+ * Find how the subject passes through each crossing, from the pieces of the subject on either side of it
+ *
+ * Pieces along the border of the clip count as inside
+ */
+fn classify(mut crossings: Vec<Crossing>, subject: &[Node], clip: &Polygon) -> Vec<Crossing> {
+    let n = subject.len();
+
+    for (k, node) in subject.iter().enumerate() {
+        let Some(id) = node.crossing else {
+            continue;
+        };
+        let (prev, next) = (subject[(k + n - 1) % n].point, subject[(k + 1) % n].point);
+        let before = clip.contains((prev + node.point) / 2.0);
+        let after = clip.contains((node.point + next) / 2.0);
+
+        crossings[id].passage = match (before, after) {
+            (false, true) => Passage::Enter,
+            (true, false) => Passage::Leave,
+            _ => Passage::Touch,
+        };
+    }
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(min: f32, max: f32) -> Polygon {
+        Polygon::new([(min, min), (max, min), (max, max), (min, max)]).unwrap()
+    }
+
+    /**
+     * A U shape opening upwards, with arms from `x = 0` to `2` and `4` to `6`
+     */
+    fn u_shape() -> Polygon {
+        Polygon::new([
+            (0.0, 0.0),
+            (6.0, 0.0),
+            (6.0, 6.0),
+            (4.0, 6.0),
+            (4.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 6.0),
+            (0.0, 6.0),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn rects() {
+        let clipped = square(0.0, 4.0)
+            .clip_to_rect(Bounds::new((2.0, -1.0), (6.0, 3.0)))
+            .unwrap();
+        assert_eq!(clipped.area(), 6.0);
+        assert!(clipped.contains(Vec2::new(3.0, 1.0)));
+        assert!(!clipped.contains(Vec2::new(1.0, 1.0)));
+
+        assert!(square(0.0, 4.0)
+            .clip_to_rect(Bounds::new((5.0, 5.0), (6.0, 6.0)))
+            .is_none());
+        assert_eq!(
+            square(1.0, 2.0)
+                .clip_to_rect(Bounds::new((0.0, 0.0), (4.0, 4.0)))
+                .unwrap()
+                .area(),
+            1.0
+        );
+
+        //  Clipping to a convex polygon works the same whichever way it winds
+        let diamond = Polygon::new([(2.0, 0.0), (4.0, 2.0), (2.0, 4.0), (0.0, 2.0)]).unwrap();
+        let a = square(0.0, 2.0).clip_to_convex(&diamond).unwrap();
+        let b = square(0.0, 2.0)
+            .clip_to_convex(&diamond.reversed())
+            .unwrap();
+        assert_eq!((a.area(), b.area()), (2.0, 2.0));
+    }
+
+    #[test]
+    fn pieces() {
+        //  A bar across the arms of the U cuts out two separate pieces
+        let bar = Polygon::new([(-1.0, 3.0), (7.0, 3.0), (7.0, 5.0), (-1.0, 5.0)]).unwrap();
+        let pieces = u_shape().clip(&bar);
+        assert_eq!(pieces.len(), 2);
+        assert!(pieces
+            .polygons()
+            .iter()
+            .all(|p| p.area() == 4.0 && p.signed_area() > 0.0));
+
+        //  Sutherland-Hodgman keeps them joined along the border of the clip instead
+        assert_eq!(u_shape().clip_to_convex(&bar).unwrap().area(), 8.0);
+
+        let overlap = square(0.0, 4.0).clip(&square(2.0, 6.0));
+        assert_eq!(overlap.len(), 1);
+        assert_eq!(overlap.area(), 4.0);
+    }
+
+    #[test]
+    fn without_crossings() {
+        //  One polygon inside of the other leaves the inner one, and apart leaves nothing
+        assert_eq!(square(1.0, 2.0).clip(&square(0.0, 4.0)).area(), 1.0);
+        assert_eq!(square(0.0, 4.0).clip(&square(1.0, 2.0)).area(), 1.0);
+        assert!(square(0.0, 1.0).clip(&square(2.0, 3.0)).is_empty());
+
+        //  Shared borders are not crossings
+        assert_eq!(
+            square(0.0, 4.0).clip(&square(0.0, 4.0).reversed()).area(),
+            16.0
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use {
+    super::{FillRule, Polygon},
+    crate::math::{Bounds, Transform2D, Vec2},
+};
+
+/**
+ * Anything made up of one or more closed contours which can be filled together, such as a `Polygon` or a `MultiPolygon`
+ */
+pub trait Contours {
+    fn contours(&self) -> &[Polygon];
+
+    /**
+     * The smallest axis-aligned box containing every contour, if there are any
+     */
+    fn bounds(&self) -> Option<Bounds> {
+        self.contours()
+            .iter()
+            .map(Polygon::bounds)
+            .reduce(|a, b| a.union(&b))
+    }
+
+    /**
+     * The ranges of x-values inside of the shape along the horizontal line at `y`, according to the specified `FillRule`
+     */
+    fn spans_at_y(&self, y: f32, rule: FillRule) -> Vec<(f32, f32)> {
+        let mut crossings = self
+            .contours()
+            .iter()
+            .flat_map(|contour| contour.crossings_at_y(y))
+            .collect::<Vec<(f32, i32)>>();
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut spans = Vec::new();
+        let (mut winding, mut start) = (0, 0.0);
+
+        for (x, direction) in crossings {
+            let inside = rule.is_inside(winding);
+            winding += direction;
+
+            match (inside, rule.is_inside(winding)) {
+                (false, true) => start = x,
+                (true, false) if x > start => spans.push((start, x)),
+                _ => (),
+            }
+        }
+        spans
+    }
+}
+
+impl Contours for Polygon {
+    fn contours(&self) -> &[Polygon] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl Contours for MultiPolygon {
+    fn contours(&self) -> &[Polygon] {
+        &self.0
+    }
+}
+
+/**
+ * A shape made up of several contours, where contours nested inside of others form holes
+ *
+ * Contours produced by this crate wind counter-clockwise (in a y-up space) around filled areas and clockwise around holes, so both fill rules agree on them
+ */
+#[derive(Clone, Debug, Default)]
+pub struct MultiPolygon(Vec<Polygon>);
+
+impl MultiPolygon {
+    pub fn new<T: IntoIterator<Item = Polygon>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+
+    /**
+     * The contours making up the shape
+     */
+    pub fn polygons(&self) -> &[Polygon] {
+        &self.0
+    }
+
+    /**
+     * The number of contours
+     */
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /**
+     * Return `true` if the shape has no contours
+     */
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /**
+     * The area covered by the shape, with holes subtracted
+     */
+    pub fn area(&self) -> f32 {
+        self.0.iter().map(Polygon::signed_area).sum::<f32>().abs()
+    }
+
+    /**
+     * Return `true` if the provided point is inside of an odd number of contours, or on one of their edges
+     */
+    pub fn contains<T: Into<Vec2>>(&self, point: T) -> bool {
+        let p = point.into();
+        self.0.iter().any(|polygon| polygon.on_edge(p))
+            || self
+                .0
+                .iter()
+                .map(|polygon| polygon.winding_number(p))
+                .sum::<i32>()
+                % 2
+                != 0
+    }
+
+    /**
+     * Return a new shape with every contour passed through the provided transform
+     */
+    pub fn transform(&self, transform: &Transform2D) -> Self {
+        Self(self.0.iter().map(|p| p.transform(transform)).collect())
+    }
+}
+
+impl From<Polygon> for MultiPolygon {
+    fn from(polygon: Polygon) -> Self {
+        Self(vec![polygon])
+    }
+}
+
+impl From<Vec<Polygon>> for MultiPolygon {
+    fn from(polygons: Vec<Polygon>) -> Self {
+        Self(polygons)
+    }
+}
+
+impl IntoIterator for MultiPolygon {
+    type Item = Polygon;
+
+    type IntoIter = std::vec::IntoIter<Polygon>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
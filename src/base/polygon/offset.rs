@@ -0,0 +1,122 @@
+use {
+    super::{combine, BooleanOp, FillRule, MultiPolygon, Polygon},
+    crate::stroke::{band, LineJoin, DEFAULT_MITER_LIMIT},
+};
+
+impl Polygon {
+    /**
+     * Grow the polygon outwards by `distance`, or shrink it inwards if `distance` is negative, connecting the shifted edges with the provided join
+     *
+     * Parts which collapse while shrinking are removed, so the result may contain any number of contours
+     */
+    pub fn offset(&self, distance: f32, join: LineJoin) -> MultiPolygon {
+        self.offset_with_limit(distance, join, DEFAULT_MITER_LIMIT)
+    }
+
+    /**
+     * Grow or shrink the polygon like `offset`, falling back to a bevel wherever a miter join would be longer than `miter_limit` times the distance
+     */
+    pub fn offset_with_limit(
+        &self,
+        distance: f32,
+        join: LineJoin,
+        miter_limit: f32,
+    ) -> MultiPolygon {
+        if distance == 0.0 || !distance.is_finite() {
+            return self.clone().into();
+        }
+        let pieces = MultiPolygon::new(band(
+            self.vertices(),
+            true,
+            distance.abs(),
+            join,
+            miter_limit,
+        ));
+
+        //  Every point within `distance` of an edge is either added to or removed from the polygon
+        combine(
+            self,
+            FillRule::EvenOdd,
+            &pieces,
+            FillRule::NonZero,
+            if distance > 0.0 {
+                BooleanOp::Union
+            } else {
+                BooleanOp::Difference
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::stroke::StrokeStyle};
+
+    fn square() -> Polygon {
+        Polygon::new([(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]).unwrap()
+    }
+
+    fn assert_area(shape: &MultiPolygon, area: f32) {
+        assert!(
+            (shape.area() - area).abs() < 0.05,
+            "expected an area of {}, found {}",
+            area,
+            shape.area()
+        );
+    }
+
+    #[test]
+    fn grow() {
+        let square = square();
+        assert_area(&square.offset(1.0, LineJoin::Miter), 36.0);
+        assert_area(&square.offset(1.0, LineJoin::Bevel), 34.0);
+
+        //  Round joins are approximated by segments within a quarter of a pixel of the arc
+        let round = square.offset(1.0, LineJoin::Round).area();
+        assert!(round > 34.5 && round <= 32.0 + std::f32::consts::PI);
+
+        //  A miter longer than the limit is cut back to a bevel
+        assert_area(&square.offset_with_limit(1.0, LineJoin::Miter, 1.0), 34.0);
+        assert!(square.offset(1.0, LineJoin::Miter).contains((-0.9, -0.9)));
+    }
+
+    #[test]
+    fn shrink() {
+        let square = square();
+        assert_area(&square.offset(-1.0, LineJoin::Miter), 4.0);
+        assert!(square.offset(-3.0, LineJoin::Miter).is_empty());
+        assert_area(&square.offset(0.0, LineJoin::Miter), 16.0);
+
+        //  Shrinking a dumbbell past the width of its handle splits it in two
+        let dumbbell = Polygon::new([
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 1.5),
+            (6.0, 1.5),
+            (6.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 4.0),
+            (6.0, 4.0),
+            (6.0, 2.5),
+            (4.0, 2.5),
+            (4.0, 4.0),
+            (0.0, 4.0),
+        ])
+        .unwrap();
+        let parts = dumbbell.offset(-1.0, LineJoin::Miter);
+        assert_eq!(parts.len(), 2);
+        assert_area(&parts, 8.0);
+    }
+
+    #[test]
+    fn polyline_outline() {
+        let style = StrokeStyle::new(2.0);
+        let outline = style.outline([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], false);
+
+        //  Two 10 by 2 bands overlapping in a 1 by 1 corner, plus the outer corner of the miter
+        assert_area(&outline, 40.0);
+        assert_eq!(outline.len(), 1);
+        assert!(outline.contains((10.9, -0.9)));
+        assert!(!outline.contains((-0.5, 0.0)));
+    }
+}
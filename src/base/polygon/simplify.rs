@@ -0,0 +1,289 @@
+use {
+    super::Polygon,
+    crate::math::{orient2d, point_line_distance, point_segment_distance, Vec2},
+    std::{cmp::Reverse, collections::BinaryHeap},
+};
+
+impl Polygon {
+    /**
+     * Remove every vertex which lies within `tolerance` of the outline of the rest using the Ramer-Douglas-Peucker algorithm
+     *
+     * The ring is split at the first vertex and the vertex furthest from it, and at least 3 verticies are always kept
+     */
+    pub fn simplify(&self, tolerance: f32) -> Polygon {
+        let vertices = self.vertices();
+        let n = vertices.len();
+
+        let from_first = |i: usize| vertices[0].distance(vertices[i]);
+        let far = (1..n)
+            .max_by(|&i, &j| from_first(i).total_cmp(&from_first(j)))
+            .unwrap();
+        let mut keep = vec![false; n];
+        (keep[0], keep[far]) = (true, true);
+
+        douglas_peucker(&self.0, 0, far, tolerance, &mut keep);
+        douglas_peucker(&self.0, far, n, tolerance, &mut keep);
+
+        //  A triangle needs one more vertex than the two the ring was split at
+        if keep.iter().filter(|&&k| k).count() < 3 {
+            let from_line = |i: usize| point_line_distance(vertices[i], vertices[0], vertices[far]);
+            let third = (1..n)
+                .filter(|&i| i != far)
+                .max_by(|&i, &j| from_line(i).total_cmp(&from_line(j)))
+                .unwrap();
+            keep[third] = true;
+        }
+        self.retain(&keep)
+    }
+
+    /**
+     * Repeatedly remove the vertex which forms the smallest triangle with its neighbours, while that triangle is smaller than `area`, using the Visvalingam-Whyatt algorithm
+     *
+     * At least 3 verticies are always kept
+     */
+    pub fn simplify_by_area(&self, area: f32) -> Polygon {
+        let vertices = self.vertices();
+        let n = vertices.len();
+
+        let mut prev = (0..n).map(|i| (i + n - 1) % n).collect::<Vec<usize>>();
+        let mut next = (0..n).map(|i| (i + 1) % n).collect::<Vec<usize>>();
+        let mut keep = vec![true; n];
+        let mut remaining = n;
+
+        //  Areas in the heap are stamped with the version of their vertex, so stale entries can be skipped
+        let mut version = vec![0u32; n];
+        let triangle = |i: usize, prev: &[usize], next: &[usize]| {
+            (orient2d(vertices[prev[i]], vertices[i], vertices[next[i]]).abs() / 2.0) as f32
+        };
+        let mut heap = (0..n)
+            .map(|i| Reverse((Area(triangle(i, &prev, &next)), i, 0)))
+            .collect::<BinaryHeap<_>>();
+
+        while let Some(Reverse((Area(smallest), i, stamp))) = heap.pop() {
+            if !keep[i] || stamp != version[i] {
+                continue;
+            }
+            if smallest >= area || remaining <= 3 {
+                break;
+            }
+            keep[i] = false;
+            remaining -= 1;
+
+            let (p, q) = (prev[i], next[i]);
+            (next[p], prev[q]) = (q, p);
+
+            for j in [p, q] {
+                version[j] += 1;
+                heap.push(Reverse((Area(triangle(j, &prev, &next)), j, version[j])));
+            }
+        }
+        self.retain(&keep)
+    }
+
+    /**
+     * Remove every vertex which lies within `tolerance` of the line through its neighbours, including spikes which double back on themselves
+     *
+     * Unlike the deduplication in `Polygon::new`, this removes verticies which are not repeated but add nothing to the outline. At least 3 verticies are always kept
+     */
+    pub fn remove_collinear(&self, tolerance: f32) -> Polygon {
+        let mut ring = self.vertices().to_vec();
+        let mut k = 0;
+        let mut unchanged = 0;
+
+        //  Every removal may make a neighbour collinear, so walk the ring until a full lap removes nothing
+        while ring.len() > 3 && unchanged < ring.len() {
+            let n = ring.len();
+            let (a, p, b) = (ring[(k + n - 1) % n], ring[k], ring[(k + 1) % n]);
+
+            let collinear = if tolerance > 0.0 {
+                point_line_distance(p, a, b) <= tolerance || a == b
+            } else {
+                orient2d(a, p, b) == 0.0
+            };
+            if collinear {
+                ring.remove(k);
+                unchanged = 0;
+            } else {
+                k += 1;
+                unchanged += 1;
+            }
+            k %= ring.len();
+        }
+        Polygon::new(ring).unwrap_or_else(|_| self.clone())
+    }
+
+    /**
+     * Round the corners of the polygon by cutting each one `iterations` times using Chaikin's algorithm
+     *
+     * Each pass replaces every edge with the points a quarter and three quarters of the way along it, doubling the number of verticies
+     */
+    pub fn smooth(&self, iterations: usize) -> Polygon {
+        let mut ring = self.0.clone();
+
+        for _ in 0..iterations {
+            let mut cut = ring
+                .windows(2)
+                .flat_map(|pair| [pair[0].lerp(pair[1], 0.25), pair[0].lerp(pair[1], 0.75)])
+                .collect::<Vec<Vec2>>();
+            cut.push(cut[0]);
+            ring = cut;
+        }
+        Polygon::new(ring).unwrap_or_else(|_| self.clone())
+    }
+
+    /**
+     * The polygon made of only the verticies which are marked to be kept
+     */
+    fn retain(&self, keep: &[bool]) -> Polygon {
+        let ring = self
+            .vertices()
+            .iter()
+            .zip(keep)
+            .filter_map(|(&v, &k)| k.then_some(v));
+        Polygon::new(ring).unwrap_or_else(|_| self.clone())
+    }
+}
+
+/**
+ * An area which can be ordered in a heap
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Area(f32);
+
+impl Eq for Area {}
+
+impl PartialOrd for Area {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Area {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+/**
+ * Mark the verticies between `start` and `end` of a closed ring which lie further than `tolerance` from the segment joining them, recursing on either side of the furthest
+ */
+fn douglas_peucker(ring: &[Vec2], start: usize, end: usize, tolerance: f32, keep: &mut [bool]) {
+    let mut stack = vec![(start, end)];
+
+    while let Some((start, end)) = stack.pop() {
+        if end - start < 2 {
+            continue;
+        }
+        let (a, b) = (ring[start], ring[end]);
+        let (index, distance) = (start + 1..end)
+            .map(|i| (i, point_segment_distance(ring[i], a, b)))
+            .max_by(|x, y| x.1.total_cmp(&y.1))
+            .unwrap();
+
+        if distance > tolerance {
+            keep[index] = true;
+            stack.push((start, index));
+            stack.push((index, end));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * A square of side 8 with a vertex every unit along its edges, each pushed slightly off of the edge
+     */
+    fn noisy_square() -> Polygon {
+        let corners = [(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0), (0.0, 0.0)];
+        let points = corners.windows(2).flat_map(|w| {
+            let (a, b) = (Vec2::from(w[0]), Vec2::from(w[1]));
+            let normal = (b - a).normalize().perp();
+            (0..8).map(move |k| {
+                let wobble = if k == 0 { 0.0 } else { 0.01 * (k % 2) as f32 };
+                a.lerp(b, k as f32 / 8.0) + normal * wobble
+            })
+        });
+        Polygon::new(points.collect::<Vec<Vec2>>()).unwrap()
+    }
+
+    fn has_corners(polygon: &Polygon) -> bool {
+        [(0.0, 0.0), (8.0, 0.0), (8.0, 8.0), (0.0, 8.0)]
+            .iter()
+            .all(|&c| polygon.vertices().contains(&Vec2::from(c)))
+    }
+
+    #[test]
+    fn ramer_douglas_peucker() {
+        let square = noisy_square();
+        assert_eq!(square.len(), 32);
+
+        let simple = square.simplify(0.1);
+        assert_eq!(simple.len(), 4);
+        assert!(has_corners(&simple));
+
+        //  Tolerances below the noise keep it
+        assert_eq!(square.simplify(0.001).len(), 32);
+
+        //  However large the tolerance, a triangle is left
+        let triangle = square.simplify(100.0);
+        assert_eq!(triangle.len(), 3);
+        assert!(triangle.area() > 0.0);
+    }
+
+    #[test]
+    fn visvalingam_whyatt() {
+        let square = noisy_square();
+
+        let simple = square.simplify_by_area(0.1);
+        assert_eq!(simple.len(), 4);
+        assert!(has_corners(&simple));
+        assert_eq!(simple.area(), 64.0);
+
+        assert_eq!(square.simplify_by_area(0.0).len(), 32);
+        assert_eq!(square.simplify_by_area(1000.0).len(), 3);
+    }
+
+    #[test]
+    fn collinear() {
+        //  Points along the edges and a spike which doubles back are removed
+        let square = Polygon::new([
+            (0.0, 0.0),
+            (2.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 4.0),
+            (4.0, 6.0),
+            (4.0, 4.0),
+            (0.0, 4.0),
+        ])
+        .unwrap();
+        let clean = square.remove_collinear(0.0);
+        assert_eq!(clean.len(), 4);
+        assert_eq!(clean.area(), 16.0);
+
+        //  A tolerance removes points which are nearly on the line too
+        let bent =
+            Polygon::new([(0.0, 0.0), (2.0, 0.05), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]).unwrap();
+        assert_eq!(bent.remove_collinear(0.0).len(), 5);
+        assert_eq!(bent.remove_collinear(0.1).len(), 4);
+    }
+
+    #[test]
+    fn chaikin() {
+        let square = Polygon::new([(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]).unwrap();
+
+        //  Each pass cuts a triangle off of every corner and doubles the verticies
+        let once = square.smooth(1);
+        assert_eq!(once.len(), 8);
+        assert_eq!(once.area(), 14.0);
+        assert!(once.vertices().contains(&Vec2::new(1.0, 0.0)));
+
+        let twice = square.smooth(2);
+        assert_eq!(twice.len(), 16);
+        assert!(twice.area() < 14.0 && twice.area() > 4.0 * std::f32::consts::PI);
+        assert!(twice.vertices().iter().all(|&v| square.contains(v)));
+
+        assert_eq!(square.smooth(0).vertices(), square.vertices());
+    }
+}
@@ -0,0 +1,519 @@
+use {
+    super::Polygon,
+    crate::{
+        error::Result,
+        math::{orient2d, segment_intersection, SegmentIntersection, Vec2},
+    },
+};
+
+impl Polygon {
+    /**
+     * The area enclosed by the polygon, positive when the verticies wind counter-clockwise in a y-up space (clockwise on the screen)
+     */
+    pub fn signed_area(&self) -> f32 {
+        self.edges().map(|(a, b)| a.cross(b)).sum::<f32>() / 2.0
+    }
+
+    /**
+     * The area enclosed by the polygon
+     */
+    pub fn area(&self) -> f32 {
+        self.signed_area().abs()
+    }
+
+    /**
+     * Return a new polygon with the order of the verticies reversed
+     */
+    pub fn reversed(&self) -> Self {
+        Self(self.0.iter().rev().copied().collect())
+    }
+
+    /**
+     * Return `true` if every turn of the polygon bends in the same direction
+     */
+    pub fn is_convex(&self) -> bool {
+        let v = self.vertices();
+        let n = v.len();
+        let (mut positive, mut negative) = (false, false);
+
+        for i in 0..n {
+            let t = orient2d(v[i], v[(i + 1) % n], v[(i + 2) % n]);
+            positive |= t > 0.0;
+            negative |= t < 0.0;
+        }
+        !(positive && negative)
+    }
+
+    /**
+     * The smallest convex polygon containing every vertex of this polygon
+     */
+    pub fn convex_hull(&self) -> Self {
+        //  A valid polygon always has three verticies, but they may be collinear
+        Self::hull(self.vertices().iter().copied()).unwrap_or_else(|_| self.clone())
+    }
+
+    /**
+     * The smallest convex polygon containing every provided point, using the monotone chain algorithm
+     *
+     * The verticies of the result wind counter-clockwise in a y-up space
+     */
+    pub fn hull<A: Into<Vec2>, T: IntoIterator<Item = A>>(iter: T) -> Result<Self> {
+        let mut points = iter.into_iter().map(Into::into).collect::<Vec<Vec2>>();
+        points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+        points.dedup();
+
+        let chain = |points: &mut dyn Iterator<Item = &Vec2>| {
+            let mut chain: Vec<Vec2> = Vec::new();
+
+            for &p in points {
+                while chain.len() > 1
+                    && orient2d(chain[chain.len() - 2], chain[chain.len() - 1], p) <= 0.0
+                {
+                    chain.pop();
+                }
+                chain.push(p)
+            }
+            chain.pop();
+            chain
+        };
+        let mut hull = chain(&mut points.iter());
+        hull.extend(chain(&mut points.iter().rev()));
+
+        Self::new(hull)
+    }
+
+    /**
+     * Split the polygon into triangles using the ear clipping method
+     */
+    pub fn triangulate(&self) -> Vec<[Vec2; 3]> {
+        clip_ears(self.oriented(true).vertices().to_vec())
+    }
+
+    /**
+     * Split the area between the polygon and the provided holes into triangles using the ear clipping method
+     *
+     * Each hole is bridged to the outer polygon before clipping, so the holes must lie inside of the polygon and must not overlap each other. A hole with any vertex outside of the polygon is an error
+     */
+    pub fn triangulate_with_holes(&self, holes: &[Polygon]) -> Result<Vec<[Vec2; 3]>> {
+        if holes
+            .iter()
+            .any(|hole| hole.vertices().iter().any(|&v| !self.contains(v)))
+        {
+            return Err("Hole lies outside of the polygon".into());
+        }
+
+        //  Ear clipping works on a counter-clockwise outer ring with clockwise holes
+        let mut ring = self.oriented(true).vertices().to_vec();
+
+        let mut holes = holes
+            .iter()
+            .map(|hole| hole.oriented(false).vertices().to_vec())
+            .collect::<Vec<Vec<Vec2>>>();
+
+        //  Bridge the holes in order of their right-most vertex so each bridge only crosses the outer ring
+        holes.sort_by(|a, b| rightmost(b).1.x.total_cmp(&rightmost(a).1.x));
+        for hole in holes {
+            bridge(&mut ring, &hole)?
+        }
+        Ok(clip_ears(ring))
+    }
+
+    /**
+     * Split the polygon into convex polygons, merging the triangles of its triangulation wherever the result remains convex (Hertel-Mehlhorn)
+     */
+    pub fn convex_decomposition(&self) -> Vec<Polygon> {
+        if self.is_convex() {
+            return vec![self.clone()];
+        }
+        let mut parts = self
+            .triangulate()
+            .into_iter()
+            .map(|triangle| triangle.to_vec())
+            .collect::<Vec<Vec<Vec2>>>();
+
+        let mut merged = true;
+        while merged {
+            merged = false;
+
+            'search: for i in 0..parts.len() {
+                for j in (i + 1)..parts.len() {
+                    if let Some(part) = merge_convex(&parts[i], &parts[j]) {
+                        parts[i] = part;
+                        parts.remove(j);
+                        merged = true;
+                        break 'search;
+                    }
+                }
+            }
+        }
+        parts
+            .into_iter()
+            .filter_map(|p| Polygon::new(p).ok())
+            .collect()
+    }
+
+    /**
+     * Return the polygon wound counter-clockwise in a y-up space if `ccw` is `true`, otherwise clockwise
+     */
+    fn oriented(&self, ccw: bool) -> Self {
+        if (self.signed_area() > 0.0) == ccw {
+            self.clone()
+        } else {
+            self.reversed()
+        }
+    }
+}
+
+/**
+ * The index and position of the right-most point
+ */
+fn rightmost(points: &[Vec2]) -> (usize, Vec2) {
+    points
+        .iter()
+        .copied()
+        .enumerate()
+        .fold(
+            (0, points[0]),
+            |best, (i, p)| {
+                if p.x > best.1.x {
+                    (i, p)
+                } else {
+                    best
+                }
+            },
+        )
+}
+
+/**
+ * Splice a clockwise hole into a counter-clockwise ring through a pair of mutually visible verticies
+ */
+fn bridge(ring: &mut Vec<Vec2>, hole: &[Vec2]) -> Result<()> {
+    let (h, m) = rightmost(hole);
+    let n = ring.len();
+
+    //  Cast a ray from the hole towards +x and find the closest edge it hits
+    let mut hit: Option<(f32, usize)> = None;
+    for i in 0..n {
+        let (a, b) = (ring[i], ring[(i + 1) % n]);
+
+        if a.y == b.y || m.y < a.y.min(b.y) || m.y > a.y.max(b.y) {
+            continue;
+        }
+        let x = a.x + (m.y - a.y) / (b.y - a.y) * (b.x - a.x);
+
+        if x >= m.x && !matches!(hit, Some((best, _)) if best <= x) {
+            hit = Some((x, i));
+        }
+    }
+    let Some((x, i)) = hit else {
+        return Err("Hole lies outside of the polygon".into());
+    };
+    let (a, b) = (ring[i], ring[(i + 1) % n]);
+
+    //  Start from the endpoint of the edge furthest along +x
+    let mut candidate = if a.x > b.x { i } else { (i + 1) % n };
+    let p = ring[candidate];
+
+    if p.y != m.y || p.x != x {
+        //  Any reflex vertex inside of the triangle (m, intersection, p) would block the bridge, so pick the one with the smallest angle instead
+        let intersection = Vec2::new(x, m.y);
+        let mut best = f32::INFINITY;
+
+        for j in 0..n {
+            let q = ring[j];
+
+            if j == candidate || !in_triangle(q, m, intersection, p) {
+                continue;
+            }
+            let reflex = orient2d(ring[(j + n - 1) % n], q, ring[(j + 1) % n]) <= 0.0;
+
+            if reflex {
+                let d = q - m;
+                let angle = (d.y / d.length()).abs();
+
+                if angle < best || (angle == best && d.x < (ring[candidate] - m).x) {
+                    best = angle;
+                    candidate = j;
+                }
+            }
+        }
+    }
+    let target = ring[candidate];
+
+    //  ring[..=candidate] + hole from m around to m + target + ring[candidate + 1..]
+    let mut spliced = ring[..=candidate].to_vec();
+    spliced.extend(hole[h..].iter().chain(&hole[..=h]));
+    spliced.push(target);
+    spliced.extend_from_slice(&ring[candidate + 1..]);
+    *ring = spliced;
+    Ok(())
+}
+
+/**
+ * Return `true` if `p` lies inside of, or on, the triangle `a`, `b`, `c`
+ */
+fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let (d1, d2, d3) = (orient2d(a, b, p), orient2d(b, c, p), orient2d(c, a, p));
+    let negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(negative && positive)
+}
+
+/**
+ * Triangulate a counter-clockwise ring by repeatedly clipping off ears
+ *
+ * When no ear can be found, degenerate verticies are dropped and otherwise the ring is split along a diagonal, so the triangles never overlap
+ */
+fn clip_ears(mut ring: Vec<Vec2>) -> Vec<[Vec2; 3]> {
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+
+    while ring.len() > 3 {
+        let n = ring.len();
+        let corner = |i: usize| (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = corner(i);
+
+            orient2d(a, b, c) > 0.0
+                && ring.iter().all(|&p| {
+                    //  Duplicated bridge verticies are shared with the ear rather than inside of it
+                    p == a || p == b || p == c || !in_triangle(p, a, b, c)
+                })
+        });
+        if let Some(i) = ear {
+            let (a, b, c) = corner(i);
+            triangles.push([a, b, c]);
+            ring.remove(i);
+            continue;
+        }
+
+        //  Collinear verticies add no area, so they can be dropped without leaving anything uncovered
+        if let Some(i) = (0..n).find(|&i| {
+            let (a, b, c) = corner(i);
+            orient2d(a, b, c) == 0.0
+        }) {
+            ring.remove(i);
+            continue;
+        }
+
+        //  Otherwise the ring is numerically awkward, so each side of a diagonal is triangulated separately
+        if let Some((i, j)) = diagonal(&ring) {
+            let mut other = ring[j..].to_vec();
+            other.extend_from_slice(&ring[..=i]);
+            triangles.extend(clip_ears(other));
+            ring = ring[i..=j].to_vec();
+            continue;
+        }
+
+        //  Without a diagonal the ring is not simple and nothing more can be clipped from it without overlapping
+        return triangles;
+    }
+    if ring.len() == 3 && orient2d(ring[0], ring[1], ring[2]) > 0.0 {
+        triangles.push([ring[0], ring[1], ring[2]]);
+    }
+    triangles
+}
+
+/**
+ * Find a pair of non-adjacent verticies `i < j` of a counter-clockwise ring which can be joined without leaving it or crossing any of its edges
+ */
+fn diagonal(ring: &[Vec2]) -> Option<(usize, usize)> {
+    let n = ring.len();
+
+    //  Whether the diagonal from vertex `i` towards `d` starts off inside of the ring
+    let in_cone = |i: usize, d: Vec2| {
+        let (a, b, c) = (ring[(i + n - 1) % n], ring[i], ring[(i + 1) % n]);
+
+        if orient2d(a, b, c) >= 0.0 {
+            orient2d(b, d, a) > 0.0 && orient2d(d, b, c) > 0.0
+        } else {
+            !(orient2d(b, d, c) >= 0.0 && orient2d(d, b, a) >= 0.0)
+        }
+    };
+
+    (0..n)
+        .flat_map(|i| (i + 2..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| !(i == 0 && j == n - 1) && ring[i] != ring[j])
+        .find(|&(i, j)| {
+            let (p, q) = (ring[i], ring[j]);
+
+            in_cone(i, q)
+                && in_cone(j, p)
+                && (0..n).all(|k| {
+                    let (a, b) = (ring[k], ring[(k + 1) % n]);
+
+                    //  Edges sharing an endpoint with the diagonal can only meet it there
+                    [a, b].iter().any(|&v| v == p || v == q)
+                        || matches!(segment_intersection(p, q, a, b), SegmentIntersection::None)
+                })
+        })
+}
+
+/**
+ * Merge two counter-clockwise convex rings which share an edge, if the result is also convex
+ */
+fn merge_convex(p: &[Vec2], q: &[Vec2]) -> Option<Vec<Vec2>> {
+    let (n, m) = (p.len(), q.len());
+
+    //  Find the edge a -> b of p which appears as b -> a in q
+    let (i, j) = (0..n).find_map(|i| {
+        let (a, b) = (p[i], p[(i + 1) % n]);
+        (0..m)
+            .find(|&j| q[j] == b && q[(j + 1) % m] == a)
+            .map(|j| (i, j))
+    })?;
+
+    //  Walk p from b back around to a, then q from a around to b, skipping the shared edge
+    let mut merged = (1..=n).map(|k| p[(i + k) % n]).collect::<Vec<Vec2>>();
+    merged.extend((2..m).map(|k| q[(j + k) % m]));
+
+    let len = merged.len();
+    (0..len)
+        .all(|k| orient2d(merged[k], merged[(k + 1) % len], merged[(k + 2) % len]) >= 0.0)
+        .then_some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(triangles: &[[Vec2; 3]]) -> f32 {
+        triangles
+            .iter()
+            .map(|&[a, b, c]| orient2d(a, b, c) as f32 / 2.0)
+            .sum()
+    }
+
+    /**
+     * Assert that every sample point is covered by exactly one triangle if `inside` holds for it, and by none otherwise
+     */
+    fn assert_covers(triangles: &[[Vec2; 3]], bounds: (f32, f32), inside: impl Fn(Vec2) -> bool) {
+        for i in 0..60 {
+            for j in 0..60 {
+                let p = Vec2::new(
+                    bounds.0 + (i as f32 + 0.37) / 60.0 * (bounds.1 - bounds.0),
+                    bounds.0 + (j as f32 + 0.61) / 60.0 * (bounds.1 - bounds.0),
+                );
+                let count = triangles
+                    .iter()
+                    .filter(|&&[a, b, c]| in_triangle(p, a, b, c))
+                    .count();
+                assert_eq!(count, inside(p) as usize, "at {}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn concave() {
+        let comb = Polygon::new([
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 10.0),
+            (8.0, 10.0),
+            (8.0, 2.0),
+            (6.0, 2.0),
+            (6.0, 10.0),
+            (4.0, 10.0),
+            (4.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 10.0),
+            (0.0, 10.0),
+        ])
+        .unwrap();
+        let triangles = comb.triangulate();
+
+        assert_eq!(triangles.len(), 10);
+        assert!(triangles.iter().all(|&[a, b, c]| orient2d(a, b, c) > 0.0));
+        assert_eq!(area(&triangles), comb.area());
+        assert_covers(&triangles, (-1.0, 11.0), |p| comb.contains(p));
+    }
+
+    #[test]
+    fn clockwise() {
+        let square = Polygon::new([(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0)]).unwrap();
+        let triangles = square.triangulate();
+
+        assert_eq!(triangles.len(), 2);
+        assert_eq!(area(&triangles), 16.0);
+    }
+
+    #[test]
+    fn collinear_runs() {
+        let mut points = Vec::new();
+        for k in 0..10 {
+            points.push(Vec2::new(k as f32, 0.0));
+        }
+        for k in 0..10 {
+            points.push(Vec2::new(10.0, k as f32));
+        }
+        points.push(Vec2::new(10.0, 10.0));
+        points.push(Vec2::new(5.0, 5.0));
+        points.push(Vec2::new(0.0, 10.0));
+        let polygon = Polygon::new(points).unwrap();
+        let triangles = polygon.triangulate();
+
+        assert!(triangles.iter().all(|&[a, b, c]| orient2d(a, b, c) > 0.0));
+        assert_eq!(area(&triangles), polygon.area());
+        assert_covers(&triangles, (-1.0, 11.0), |p| polygon.contains(p));
+    }
+
+    #[test]
+    fn holes() {
+        let outer = Polygon::new([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]).unwrap();
+        let holes = [
+            Polygon::new([(2.0, 2.0), (4.0, 2.0), (4.0, 4.0), (2.0, 4.0)]).unwrap(),
+            Polygon::new([(6.0, 5.0), (8.0, 5.0), (7.0, 8.0)]).unwrap(),
+        ];
+        let triangles = outer.triangulate_with_holes(&holes).unwrap();
+
+        assert_eq!(area(&triangles), 100.0 - 4.0 - 3.0);
+        assert_covers(&triangles, (-1.0, 11.0), |p| {
+            outer.contains(p) && holes.iter().all(|hole| !hole.contains(p))
+        });
+    }
+
+    #[test]
+    fn hole_outside() {
+        let outer = Polygon::new([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]).unwrap();
+        let hole = Polygon::new([(12.0, 2.0), (14.0, 2.0), (14.0, 4.0)]).unwrap();
+
+        assert!(outer.triangulate_with_holes(&[hole]).is_err());
+
+        //  A hole to the left is still hit by the ray used to bridge it, so it must be rejected up front
+        let hole = Polygon::new([(-5.0, 2.0), (-3.0, 2.0), (-3.0, 4.0), (-5.0, 4.0)]).unwrap();
+        assert!(outer.triangulate_with_holes(&[hole]).is_err());
+
+        let hole = Polygon::new([(8.0, 2.0), (12.0, 2.0), (12.0, 4.0), (8.0, 4.0)]).unwrap();
+        assert!(outer.triangulate_with_holes(&[hole]).is_err());
+    }
+
+    #[test]
+    fn diagonals() {
+        //  The first diagonal of the comb must pass between its teeth rather than across them
+        let comb = [
+            (0.0, 0.0),
+            (6.0, 0.0),
+            (6.0, 6.0),
+            (4.0, 6.0),
+            (4.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 6.0),
+            (0.0, 6.0),
+        ]
+        .map(Vec2::from);
+        let (i, j) = diagonal(&comb).unwrap();
+        let polygon = Polygon::new(comb).unwrap();
+
+        assert!(j > i + 1);
+        assert!(polygon.contains((comb[i] + comb[j]) / 2.0));
+        assert!(!polygon.on_edge((comb[i] + comb[j]) / 2.0));
+
+        let split = {
+            let mut other = comb[j..].to_vec();
+            other.extend_from_slice(&comb[..=i]);
+            [clip_ears(comb[i..=j].to_vec()), clip_ears(other)].concat()
+        };
+        assert_eq!(area(&split), polygon.area());
+        assert_covers(&split, (-1.0, 7.0), |p| polygon.contains(p));
+    }
+}
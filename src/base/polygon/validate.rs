@@ -0,0 +1,237 @@
+use {
+    super::Polygon,
+    crate::math::{orient2d, segment_intersection, SegmentIntersection, Vec2},
+    std::fmt::Display,
+};
+
+/**
+ * A problem which keeps a ring of points from being a valid simple polygon, along with the indices of the verticies or edges which cause it
+ *
+ * Edge `i` joins vertex `i` to the vertex after it
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolygonError {
+    /** Fewer than 3 distinct points were provided */
+    TooFewPoints { count: usize },
+    /** Some verticies have coordinates which are infinite or NaN */
+    NonFinite { indices: Vec<usize> },
+    /** Every vertex lies on one line, so the polygon encloses no area */
+    ZeroArea,
+    /** Some verticies lie on the line through their neighbours, including spikes which double back on themselves */
+    Collinear { indices: Vec<usize> },
+    /** Some pairs of edges cross or touch away from the vertex they share */
+    SelfIntersecting { edges: Vec<(usize, usize)> },
+}
+
+impl Display for PolygonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooFewPoints { count } => {
+                write!(f, "Not enough points (Required 3 or more, found {})", count)
+            }
+            Self::NonFinite { indices } => {
+                write!(f, "Non-finite coordinates at verticies {:?}", indices)
+            }
+            Self::ZeroArea => f.write_str("The polygon has no area"),
+            Self::Collinear { indices } => write!(f, "Collinear verticies {:?}", indices),
+            Self::SelfIntersecting { edges } => write!(f, "Intersecting edges {:?}", edges),
+        }
+    }
+}
+
+impl Polygon {
+    /**
+     * Check that the polygon is simple, with finite coordinates, a non-zero area, no redundant verticies and no edges crossing each other
+     *
+     * Problems are checked in that order and only the first kind found is returned
+     */
+    pub fn validate(&self) -> Result<(), PolygonError> {
+        let vertices = self.vertices();
+        let n = vertices.len();
+
+        if n < 3 {
+            return Err(PolygonError::TooFewPoints { count: n });
+        }
+
+        let indices = (0..n)
+            .filter(|&i| !(vertices[i].x.is_finite() && vertices[i].y.is_finite()))
+            .collect::<Vec<usize>>();
+        if !indices.is_empty() {
+            return Err(PolygonError::NonFinite { indices });
+        }
+
+        let indices = (0..n)
+            .filter(|&i| {
+                orient2d(
+                    vertices[(i + n - 1) % n],
+                    vertices[i],
+                    vertices[(i + 1) % n],
+                ) == 0.0
+            })
+            .collect::<Vec<usize>>();
+        if indices.len() == n {
+            return Err(PolygonError::ZeroArea);
+        } else if !indices.is_empty() {
+            return Err(PolygonError::Collinear { indices });
+        }
+
+        let edges = intersecting_edges(&self.0);
+        if !edges.is_empty() {
+            return Err(PolygonError::SelfIntersecting { edges });
+        }
+        Ok(())
+    }
+
+    /**
+     * Return `true` if `validate` finds no problems with the polygon
+     */
+    pub fn is_valid(&self) -> bool {
+        self.validate().is_ok()
+    }
+
+    /**
+     * Attempt to fix the polygon by dropping non-finite verticies, removing collinear verticies and spikes, and winding it counter-clockwise in a y-up space
+     *
+     * Any problem which remains afterwards, such as edges crossing each other, is returned instead
+     */
+    pub fn repair(&self) -> Result<Polygon, PolygonError> {
+        let finite = self
+            .vertices()
+            .iter()
+            .copied()
+            .filter(|v| v.x.is_finite() && v.y.is_finite())
+            .collect::<Vec<Vec2>>();
+        let count = finite.len();
+
+        let polygon = Polygon::new(finite)
+            .map_err(|_| PolygonError::TooFewPoints { count })?
+            .remove_collinear(0.0);
+
+        let polygon = if polygon.signed_area() < 0.0 {
+            polygon.reversed()
+        } else {
+            polygon
+        };
+        polygon.validate().map(|_| polygon)
+    }
+}
+
+/**
+ * Every pair of edges of a closed ring which meet anywhere other than at the vertex shared by neighbouring edges
+ */
+fn intersecting_edges(ring: &[Vec2]) -> Vec<(usize, usize)> {
+    let n = ring.len() - 1;
+
+    //  Edges are swept from left to right, so only edges whose horizontal extents overlap are compared
+    let extent = |i: usize| {
+        let (a, b) = (ring[i], ring[i + 1]);
+        (a.x.min(b.x), a.x.max(b.x))
+    };
+    let mut order = (0..n).collect::<Vec<usize>>();
+    order.sort_by(|&i, &j| extent(i).0.total_cmp(&extent(j).0));
+
+    let mut pairs = Vec::new();
+    for (k, &i) in order.iter().enumerate() {
+        let right = extent(i).1;
+
+        for &j in order[k + 1..].iter().take_while(|&&j| extent(j).0 <= right) {
+            let (i, j) = (i.min(j), i.max(j));
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+
+            match segment_intersection(ring[i], ring[i + 1], ring[j], ring[j + 1]) {
+                SegmentIntersection::None => continue,
+                SegmentIntersection::Point(_) if adjacent => continue,
+                _ => pairs.push((i, j)),
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(f32, f32)]) -> Polygon {
+        Polygon::new(points.iter().copied()).unwrap()
+    }
+
+    #[test]
+    fn valid() {
+        let square = polygon(&[(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_eq!(square.validate(), Ok(()));
+        assert!(square.is_valid());
+    }
+
+    #[test]
+    fn too_few_points() {
+        //  Repeated points are removed before counting
+        let error = Polygon::new([(0.0, 0.0), (0.0, 0.0), (1.0, 1.0)]).unwrap_err();
+        assert_eq!(
+            error.polygon(),
+            Some(&PolygonError::TooFewPoints { count: 2 })
+        );
+        assert_eq!(error.parse(), None);
+        assert_eq!(
+            error.to_string(),
+            "Polygon: Not enough points (Required 3 or more, found 2)"
+        );
+    }
+
+    #[test]
+    fn problems() {
+        let nan = polygon(&[(0.0, 0.0), (2.0, 0.0), (f32::NAN, 2.0), (0.0, 2.0)]);
+        assert_eq!(
+            nan.validate(),
+            Err(PolygonError::NonFinite { indices: vec![2] })
+        );
+
+        let line = polygon(&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)]);
+        assert_eq!(line.validate(), Err(PolygonError::ZeroArea));
+
+        let collinear = polygon(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+        assert_eq!(
+            collinear.validate(),
+            Err(PolygonError::Collinear { indices: vec![1] })
+        );
+
+        let bowtie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        assert_eq!(
+            bowtie.validate(),
+            Err(PolygonError::SelfIntersecting {
+                edges: vec![(0, 2)]
+            })
+        );
+        assert!(!bowtie.is_valid());
+    }
+
+    #[test]
+    fn repair() {
+        //  Clockwise in a y-up space, with a non-finite and a collinear vertex
+        let broken = polygon(&[
+            (0.0, 0.0),
+            (0.0, 1.0),
+            (0.0, 2.0),
+            (f32::INFINITY, 3.0),
+            (2.0, 2.0),
+            (2.0, 0.0),
+        ]);
+        let repaired = broken.repair().unwrap();
+        assert_eq!(repaired.validate(), Ok(()));
+        assert_eq!(repaired.len(), 4);
+        assert_eq!(repaired.signed_area(), 4.0);
+
+        let bowtie = polygon(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        assert!(matches!(
+            bowtie.repair(),
+            Err(PolygonError::SelfIntersecting { .. })
+        ));
+
+        let sparse = polygon(&[(0.0, 0.0), (f32::NAN, 1.0), (2.0, 0.0), (f32::NAN, 2.0)]);
+        assert_eq!(
+            sparse.repair().unwrap_err(),
+            PolygonError::TooFewPoints { count: 2 }
+        );
+    }
+}
@@ -0,0 +1,759 @@
+use {
+    super::{
+        error::Result,
+        math::{Bounds, Vec2},
+        polygon::Polygon,
+        stroke::arc_segments,
+    },
+    std::f32::consts::{FRAC_PI_2, FRAC_PI_4, PI, TAU},
+};
+
+/**
+ * A shape which can be located and hit tested, such as a `Polygon` or a `Circle`
+ */
+pub trait Shape {
+    /**
+     * The smallest axis-aligned box containing the shape
+     */
+    fn bounds(&self) -> Bounds;
+
+    /**
+     * Return `true` if the provided point is inside of the shape or on its border
+     */
+    fn contains(&self, point: Vec2) -> bool;
+
+    /**
+     * The shortest distance between the provided point and the border of the shape
+     */
+    fn distance_to(&self, point: Vec2) -> f32;
+}
+
+impl Shape for Polygon {
+    fn bounds(&self) -> Bounds {
+        Polygon::bounds(self)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        Polygon::contains(self, point)
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        Polygon::distance_to(self, point)
+    }
+}
+
+/**
+ * The unit vector at the provided angle, measured from the positive x-axis towards the positive y-axis (clockwise on the screen)
+ */
+fn direction(angle: f32) -> Vec2 {
+    Vec2::new(angle.cos(), angle.sin())
+}
+
+/**
+ * Normalize a pair of angles into a start within `[0, 2π)` and a sweep within `[0, 2π]` running towards increasing angles
+ */
+fn normalize(start: f32, end: f32) -> (f32, f32) {
+    let sweep = if end - start >= TAU {
+        TAU
+    } else {
+        (end - start).rem_euclid(TAU)
+    };
+    (start.rem_euclid(TAU), sweep)
+}
+
+/**
+ * Return `true` if the direction `d` lies within the sweep beginning at `start`
+ */
+fn within_sweep(d: Vec2, start: f32, sweep: f32) -> bool {
+    sweep >= TAU || (d.y.atan2(d.x) - start).rem_euclid(TAU) <= sweep
+}
+
+/**
+ * A circle described by its center and radius
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Circle {
+    pub center: Vec2,
+    pub radius: f32,
+}
+
+impl Circle {
+    pub fn new<T: Into<Vec2>>(center: T, radius: f32) -> Self {
+        Self {
+            center: center.into(),
+            radius: radius.abs(),
+        }
+    }
+
+    /**
+     * Approximate the circle with a polygon whose edges stay within a quarter of a pixel of it
+     */
+    pub fn to_polygon(&self) -> Result<Polygon> {
+        Ellipse::new(self.center, self.radius, self.radius).to_polygon()
+    }
+}
+
+impl Shape for Circle {
+    fn bounds(&self) -> Bounds {
+        let r = Vec2::new(self.radius, self.radius);
+        Bounds::new(self.center - r, self.center + r)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.center.distance(point) <= self.radius
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        (self.center.distance(point) - self.radius).abs()
+    }
+}
+
+/**
+ * An axis-aligned ellipse described by its center and the radius along each axis
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipse {
+    pub center: Vec2,
+    pub rx: f32,
+    pub ry: f32,
+}
+
+impl Ellipse {
+    pub fn new<T: Into<Vec2>>(center: T, rx: f32, ry: f32) -> Self {
+        Self {
+            center: center.into(),
+            rx: rx.abs(),
+            ry: ry.abs(),
+        }
+    }
+
+    /**
+     * The point on the ellipse at the provided angle from its center
+     */
+    pub fn point_at(&self, angle: f32) -> Vec2 {
+        let d = direction(angle);
+        self.center + Vec2::new(d.x * self.rx, d.y * self.ry)
+    }
+
+    /**
+     * The points along the ellipse between two angles, close enough together to stay within a quarter of a pixel of it
+     */
+    pub(crate) fn points(&self, start: f32, sweep: f32) -> Vec<Vec2> {
+        let steps = arc_segments(self.rx.max(self.ry), sweep);
+        (0..=steps)
+            .map(|k| self.point_at(start + sweep * k as f32 / steps as f32))
+            .collect()
+    }
+
+    /**
+     * Approximate the ellipse with a polygon whose edges stay within a quarter of a pixel of it
+     */
+    pub fn to_polygon(&self) -> Result<Polygon> {
+        Polygon::new(self.points(0.0, TAU))
+    }
+}
+
+impl Shape for Ellipse {
+    fn bounds(&self) -> Bounds {
+        let r = Vec2::new(self.rx, self.ry);
+        Bounds::new(self.center - r, self.center + r)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let p = point - self.center;
+        (p.x / self.rx).powi(2) + (p.y / self.ry).powi(2) <= 1.0
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        //  Iteratively refine the closest point within the quadrant of the point, using the evolute of the ellipse
+        let p = point - self.center;
+        let (px, py) = (p.x.abs(), p.y.abs());
+        let (a, b) = (self.rx, self.ry);
+
+        if a == 0.0 || b == 0.0 {
+            let extent = Vec2::new(px.min(a), py.min(b));
+            return Vec2::new(px, py).distance(extent);
+        }
+        let (mut tx, mut ty) = (
+            std::f32::consts::FRAC_1_SQRT_2,
+            std::f32::consts::FRAC_1_SQRT_2,
+        );
+
+        for _ in 0..4 {
+            let (x, y) = (a * tx, b * ty);
+            let ex = (a * a - b * b) * tx.powi(3) / a;
+            let ey = (b * b - a * a) * ty.powi(3) / b;
+
+            let (rx, ry) = (x - ex, y - ey);
+            let (qx, qy) = (px - ex, py - ey);
+
+            let r = rx.hypot(ry);
+            let q = qx.hypot(qy).max(f32::EPSILON);
+
+            tx = ((qx * r / q + ex) / a).clamp(0.0, 1.0);
+            ty = ((qy * r / q + ey) / b).clamp(0.0, 1.0);
+
+            let t = tx.hypot(ty);
+            tx /= t;
+            ty /= t;
+        }
+        Vec2::new(px, py).distance(Vec2::new(a * tx, b * ty))
+    }
+}
+
+/**
+ * A circular arc described by its center, radius and the angles it runs between
+ *
+ * Angles are in radians, measured from the positive x-axis towards the positive y-axis (clockwise on the screen), and the arc always runs towards increasing angles
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arc {
+    pub center: Vec2,
+    pub radius: f32,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Arc {
+    pub fn new<T: Into<Vec2>>(center: T, radius: f32, start: f32, end: f32) -> Self {
+        Self {
+            center: center.into(),
+            radius: radius.abs(),
+            start,
+            end,
+        }
+    }
+
+    /**
+     * The start of the arc within `[0, 2π)` and the angle it sweeps through within `[0, 2π]`
+     */
+    pub fn sweep(&self) -> (f32, f32) {
+        normalize(self.start, self.end)
+    }
+
+    /**
+     * The points along the arc, close enough together to stay within a quarter of a pixel of it
+     */
+    pub fn points(&self) -> Vec<Vec2> {
+        let (start, sweep) = self.sweep();
+        Ellipse::new(self.center, self.radius, self.radius).points(start, sweep)
+    }
+
+    /**
+     * The smallest box containing the arc, and its center if `center` is `true`
+     */
+    fn bounds_with(&self, center: bool) -> Bounds {
+        let (start, sweep) = self.sweep();
+        let mut points = vec![
+            self.center + direction(start) * self.radius,
+            self.center + direction(start + sweep) * self.radius,
+        ];
+
+        //  Include each extreme of the circle which the arc passes through
+        points.extend(
+            (0..4)
+                .map(|k| k as f32 * FRAC_PI_2)
+                .filter(|&angle| within_sweep(direction(angle), start, sweep))
+                .map(|angle| self.center + direction(angle) * self.radius),
+        );
+        if center {
+            points.push(self.center)
+        }
+        Bounds::from_points(points).unwrap()
+    }
+}
+
+impl Shape for Arc {
+    fn bounds(&self) -> Bounds {
+        self.bounds_with(false)
+    }
+
+    /**
+     * Return `true` if the provided point lies within half of a pixel of the arc
+     */
+    fn contains(&self, point: Vec2) -> bool {
+        self.distance_to(point) <= 0.5
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        let (start, sweep) = self.sweep();
+        let p = point - self.center;
+
+        if within_sweep(p, start, sweep) {
+            (p.length() - self.radius).abs()
+        } else {
+            let a = self.center + direction(start) * self.radius;
+            let b = self.center + direction(start + sweep) * self.radius;
+            a.distance(point).min(b.distance(point))
+        }
+    }
+}
+
+/**
+ * A slice of a circle bounded by an `Arc` and the two radii at its ends
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pie {
+    pub arc: Arc,
+}
+
+impl Pie {
+    pub fn new<T: Into<Vec2>>(center: T, radius: f32, start: f32, end: f32) -> Self {
+        Self {
+            arc: Arc::new(center, radius, start, end),
+        }
+    }
+
+    /**
+     * Approximate the pie with a polygon whose edges stay within a quarter of a pixel of it
+     */
+    pub fn to_polygon(&self) -> Result<Polygon> {
+        let mut points = self.arc.points();
+
+        if self.arc.sweep().1 < TAU {
+            points.push(self.arc.center)
+        }
+        Polygon::new(points)
+    }
+}
+
+impl Shape for Pie {
+    fn bounds(&self) -> Bounds {
+        self.arc.bounds_with(true)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let (start, sweep) = self.arc.sweep();
+        let p = point - self.arc.center;
+        p.length() <= self.arc.radius && (p == Vec2::ZERO || within_sweep(p, start, sweep))
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        let (start, sweep) = self.arc.sweep();
+        let c = self.arc.center;
+
+        //  The nearest of the arc and the two straight edges
+        let mut distance = self.arc.distance_to(point);
+
+        if sweep < TAU {
+            for angle in [start, start + sweep] {
+                let end = c + direction(angle) * self.arc.radius;
+                let t = ((point - c).dot(end - c) / (end - c).length_squared()).clamp(0.0, 1.0);
+                distance = distance.min(point.distance(c + (end - c) * t));
+            }
+        }
+        distance
+    }
+}
+
+/**
+ * The directions swept by an arc, precomputed so rasterizers can test each pixel without trigonometry
+ */
+pub(crate) struct Sector {
+    start: Vec2,
+    end: Vec2,
+    sweep: f32,
+}
+
+impl Sector {
+    pub(crate) fn new(start: f32, sweep: f32) -> Self {
+        Self {
+            start: direction(start),
+            end: direction(start + sweep),
+            sweep,
+        }
+    }
+
+    /**
+     * Return `true` if the direction lies within the sector
+     */
+    pub(crate) fn contains(&self, d: Vec2) -> bool {
+        if self.sweep >= TAU {
+            true
+        } else if self.sweep <= PI {
+            self.start.cross(d) >= 0.0 && d.cross(self.end) >= 0.0
+        } else {
+            !(self.end.cross(d) > 0.0 && d.cross(self.start) > 0.0)
+        }
+    }
+}
+
+/**
+ * An axis-aligned rectangle described by its top-left corner and its size
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    /**
+     * Create a rectangle, moving the corner so that the width and height are never negative
+     */
+    pub fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x: x.min(x + width),
+            y: y.min(y + height),
+            width: width.abs(),
+            height: height.abs(),
+        }
+    }
+
+    /**
+     * The corner with the smallest coordinates
+     */
+    pub fn min(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /**
+     * The corner with the largest coordinates
+     */
+    pub fn max(&self) -> Vec2 {
+        Vec2::new(self.x + self.width, self.y + self.height)
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min() + self.max()) / 2.0
+    }
+
+    pub fn area(&self) -> f32 {
+        self.width * self.height
+    }
+
+    /**
+     * The rectangle as a polygon, running clockwise on the screen from the top-left corner
+     */
+    pub fn to_polygon(&self) -> Result<Polygon> {
+        let (min, max) = (self.min(), self.max());
+        Polygon::new([min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)])
+    }
+}
+
+impl From<Bounds> for Rect {
+    fn from(bounds: Bounds) -> Self {
+        Self::new(bounds.min.x, bounds.min.y, bounds.width(), bounds.height())
+    }
+}
+
+impl From<Rect> for Bounds {
+    fn from(rect: Rect) -> Self {
+        Self::new(rect.min(), rect.max())
+    }
+}
+
+impl From<sdl2::rect::Rect> for Rect {
+    fn from(rect: sdl2::rect::Rect) -> Self {
+        Self::new(
+            rect.x() as f32,
+            rect.y() as f32,
+            rect.width() as f32,
+            rect.height() as f32,
+        )
+    }
+}
+
+impl Shape for Rect {
+    fn bounds(&self) -> Bounds {
+        Bounds::new(self.min(), self.max())
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.bounds().contains(point)
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        let (min, max) = (self.min(), self.max());
+        let dx = (min.x - point.x).max(point.x - max.x);
+        let dy = (min.y - point.y).max(point.y - max.y);
+
+        if dx <= 0.0 && dy <= 0.0 {
+            -dx.max(dy)
+        } else {
+            Vec2::new(dx.max(0.0), dy.max(0.0)).length()
+        }
+    }
+}
+
+/**
+ * A rectangle whose corners are rounded with quarter circles
+ *
+ * The radii are ordered top-left, top-right, bottom-right and bottom-left, and are scaled down together whenever two neighbouring corners would overlap
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RoundedRect {
+    pub rect: Rect,
+    pub radii: [f32; 4],
+}
+
+impl RoundedRect {
+    /**
+     * Create a rounded rectangle with the same radius on every corner
+     */
+    pub fn new(rect: Rect, radius: f32) -> Self {
+        Self::with_radii(rect, [radius; 4])
+    }
+
+    /**
+     * Create a rounded rectangle with a separate radius on each corner
+     */
+    pub fn with_radii(rect: Rect, radii: [f32; 4]) -> Self {
+        let mut radii = radii.map(|r| r.max(0.0));
+        let [tl, tr, br, bl] = radii;
+
+        let scale = [
+            rect.width / (tl + tr),
+            rect.height / (tr + br),
+            rect.width / (br + bl),
+            rect.height / (bl + tl),
+        ]
+        .into_iter()
+        .filter(|s| s.is_finite())
+        .fold(1.0f32, f32::min);
+
+        radii.iter_mut().for_each(|r| *r *= scale);
+        Self { rect, radii }
+    }
+
+    /**
+     * The center of each corner's circle, paired with its radius and the angle its quarter begins at
+     */
+    fn corners(&self) -> [(Vec2, f32, f32); 4] {
+        let (min, max) = (self.rect.min(), self.rect.max());
+        let [tl, tr, br, bl] = self.radii;
+        [
+            (Vec2::new(min.x + tl, min.y + tl), tl, PI),
+            (Vec2::new(max.x - tr, min.y + tr), tr, PI + FRAC_PI_2),
+            (Vec2::new(max.x - br, max.y - br), br, 0.0),
+            (Vec2::new(min.x + bl, max.y - bl), bl, FRAC_PI_2),
+        ]
+    }
+
+    /**
+     * Approximate the rounded rectangle with a polygon whose edges stay within a quarter of a pixel of it
+     */
+    pub fn to_polygon(&self) -> Result<Polygon> {
+        let points = self.corners().into_iter().flat_map(|(center, r, start)| {
+            if r > 0.0 {
+                Ellipse::new(center, r, r).points(start, FRAC_PI_2)
+            } else {
+                vec![center]
+            }
+        });
+        Polygon::new(points.collect::<Vec<_>>())
+    }
+}
+
+impl From<Rect> for RoundedRect {
+    fn from(rect: Rect) -> Self {
+        Self::new(rect, 0.0)
+    }
+}
+
+impl Shape for RoundedRect {
+    fn bounds(&self) -> Bounds {
+        self.rect.bounds()
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        if !self.rect.contains(point) {
+            return false;
+        }
+
+        //  Only the corners outside of their quarter circle are excluded
+        self.corners().into_iter().all(|(center, r, start)| {
+            let d = point - center;
+            let outward = direction(start + FRAC_PI_4);
+            d.x * outward.x <= 0.0 || d.y * outward.y <= 0.0 || d.length() <= r
+        })
+    }
+
+    fn distance_to(&self, point: Vec2) -> f32 {
+        let corners = self.corners();
+
+        //  The border is made of the four quarter circles and the straight edges joining them
+        (0..4)
+            .map(|k| {
+                let (center, r, start) = corners[k];
+                let (next, r_next, start_next) = corners[(k + 1) % 4];
+
+                let a = center + direction(start + FRAC_PI_2) * r;
+                let b = next + direction(start_next) * r_next;
+                let t = ((point - a).dot(b - a) / (b - a).length_squared()).clamp(0.0, 1.0);
+                let edge = point.distance(if t.is_finite() { a + (b - a) * t } else { a });
+
+                edge.min(Arc::new(center, r, start, start + FRAC_PI_2).distance_to(point))
+            })
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn circles() {
+        let circle = Circle::new((10.0, 10.0), -5.0);
+        assert_eq!(circle.radius, 5.0);
+        assert_eq!(circle.bounds(), Bounds::new((5.0, 5.0), (15.0, 15.0)));
+
+        assert!(circle.contains(Vec2::new(14.0, 10.0)));
+        assert!(circle.contains(Vec2::new(15.0, 10.0)));
+        assert!(!circle.contains(Vec2::new(14.0, 14.0)));
+        assert_eq!(circle.distance_to(Vec2::new(10.0, 18.0)), 3.0);
+        assert_eq!(circle.distance_to(Vec2::new(10.0, 10.0)), 5.0);
+
+        //  The approximating polygon stays within a quarter of a pixel of the circle
+        let polygon = circle.to_polygon().unwrap();
+        assert!(polygon.area() > PI * 4.75f32.powi(2) && polygon.area() < PI * 25.0);
+        assert!(polygon
+            .vertices()
+            .iter()
+            .all(|&p| circle.distance_to(p) < 1e-4));
+    }
+
+    #[test]
+    fn ellipses() {
+        let ellipse = Ellipse::new((0.0, 0.0), 4.0, 2.0);
+        assert_eq!(ellipse.bounds(), Bounds::new((-4.0, -2.0), (4.0, 2.0)));
+        assert!(ellipse.contains(Vec2::new(3.9, 0.0)));
+        assert!(ellipse.contains(Vec2::new(0.0, -2.0)));
+        assert!(!ellipse.contains(Vec2::new(3.0, 1.5)));
+
+        assert!((ellipse.point_at(FRAC_PI_2) - Vec2::new(0.0, 2.0)).length() < 1e-6);
+        assert!((ellipse.distance_to(Vec2::new(7.0, 0.0)) - 3.0).abs() < 1e-3);
+        assert!((ellipse.distance_to(Vec2::new(0.0, -5.0)) - 3.0).abs() < 1e-3);
+        assert!((ellipse.distance_to(Vec2::ZERO) - 2.0).abs() < 1e-3);
+
+        //  Degenerate ellipses measure the distance to the segment they collapse into
+        let flat = Ellipse::new((0.0, 0.0), 4.0, 0.0);
+        assert_eq!(flat.distance_to(Vec2::new(2.0, 3.0)), 3.0);
+        assert_eq!(flat.distance_to(Vec2::new(7.0, 4.0)), 5.0);
+    }
+
+    #[test]
+    fn arcs() {
+        //  The angles wrap around and the arc runs towards increasing angles
+        let (start, sweep) = Arc::new((0.0, 0.0), 1.0, -FRAC_PI_2, FRAC_PI_2).sweep();
+        assert!((start - 3.0 * FRAC_PI_2).abs() < 1e-5);
+        assert!((sweep - PI).abs() < 1e-5);
+        assert_eq!(Arc::new((0.0, 0.0), 1.0, 0.0, 3.0 * TAU).sweep().1, TAU);
+
+        //  The bounds include the extremes of the circle which the arc passes through
+        let arc = Arc::new((0.0, 0.0), 2.0, -FRAC_PI_4, FRAC_PI_4);
+        let bounds = arc.bounds();
+        assert!((bounds.max.x - 2.0).abs() < 1e-5);
+        assert!((bounds.min.x - 2.0 * FRAC_PI_4.cos()).abs() < 1e-5);
+        assert!((bounds.max.y - 2.0 * FRAC_PI_4.sin()).abs() < 1e-5);
+
+        assert!(arc.contains(Vec2::new(2.3, 0.0)));
+        assert!(!arc.contains(Vec2::new(1.0, 0.0)));
+        assert!(!arc.contains(Vec2::new(-2.0, 0.0)));
+
+        //  Points outside of the sweep measure the distance to the nearest end
+        let end = Vec2::new(FRAC_PI_4.cos(), FRAC_PI_4.sin()) * 2.0;
+        assert!(
+            (arc.distance_to(Vec2::new(0.0, 3.0)) - end.distance(Vec2::new(0.0, 3.0))).abs() < 1e-5
+        );
+
+        let points = arc.points();
+        assert!(points.len() > 2);
+        assert!(points.iter().all(|&p| arc.distance_to(p) < 1e-4));
+    }
+
+    #[test]
+    fn pies() {
+        //  A quarter of a circle running clockwise on the screen from the positive x-axis
+        let pie = Pie::new((0.0, 0.0), 2.0, 0.0, FRAC_PI_2);
+        assert!(pie.bounds().min.length() < 1e-5);
+        assert_eq!(pie.bounds().max, Vec2::new(2.0, 2.0));
+
+        assert!(pie.contains(Vec2::ZERO));
+        assert!(pie.contains(Vec2::new(1.0, 1.0)));
+        assert!(!pie.contains(Vec2::new(-1.0, 1.0)));
+        assert!(!pie.contains(Vec2::new(1.5, 1.5)));
+
+        assert!((pie.distance_to(Vec2::new(1.0, -1.0)) - 1.0).abs() < 1e-5);
+        assert!((pie.distance_to(Vec2::new(1.0, 0.5)) - 0.5).abs() < 1e-5);
+
+        let polygon = pie.to_polygon().unwrap();
+        assert!(polygon.area() > PI * 1.75f32.powi(2) / 4.0 && polygon.area() < PI);
+
+        //  A full pie is a circle, without its center as a vertex
+        let full = Pie::new((0.0, 0.0), 2.0, 0.0, TAU).to_polygon().unwrap();
+        assert!(full.vertices().iter().all(|p| p.length() > 1.9));
+    }
+
+    #[test]
+    fn sectors() {
+        let narrow = Sector::new(0.0, FRAC_PI_2);
+        assert!(narrow.contains(Vec2::new(1.0, 1.0)));
+        assert!(narrow.contains(Vec2::new(1.0, 0.0)));
+        assert!(!narrow.contains(Vec2::new(-1.0, 1.0)));
+        assert!(!narrow.contains(Vec2::new(1.0, -1.0)));
+
+        //  Sectors wider than a half turn are the complement of the remaining directions
+        let wide = Sector::new(0.0, 3.0 * FRAC_PI_2);
+        assert!(wide.contains(Vec2::new(-1.0, 1.0)));
+        assert!(wide.contains(Vec2::new(-1.0, -1.0)));
+        assert!(!wide.contains(Vec2::new(1.0, -1.0)));
+
+        assert!(Sector::new(1.0, TAU).contains(Vec2::new(0.3, -0.7)));
+    }
+
+    #[test]
+    fn rects() {
+        let rect = Rect::new(10.0, 20.0, -4.0, 2.0);
+        assert_eq!(rect, Rect::new(6.0, 20.0, 4.0, 2.0));
+        assert_eq!(rect.center(), Vec2::new(8.0, 21.0));
+        assert_eq!(rect.area(), 8.0);
+        assert_eq!(Rect::from(Bounds::from(rect)), rect);
+        assert_eq!(
+            Rect::from(sdl2::rect::Rect::new(-1, 2, 3, 4)),
+            Rect::new(-1.0, 2.0, 3.0, 4.0)
+        );
+
+        assert!(rect.contains(Vec2::new(10.0, 22.0)));
+        assert!(!rect.contains(Vec2::new(10.5, 21.0)));
+
+        //  The distance is measured to the border from both sides
+        assert_eq!(rect.distance_to(Vec2::new(7.0, 21.0)), 1.0);
+        assert_eq!(rect.distance_to(Vec2::new(8.0, 25.0)), 3.0);
+        assert_eq!(rect.distance_to(Vec2::new(13.0, 26.0)), 5.0);
+
+        let polygon = rect.to_polygon().unwrap();
+        assert_eq!(polygon.area(), 8.0);
+        assert_eq!(polygon.vertices()[0], rect.min());
+    }
+
+    #[test]
+    fn rounded_rects() {
+        let rect = Rect::new(0.0, 0.0, 6.0, 4.0);
+
+        //  Radii are scaled down together when neighbouring corners would overlap
+        let rounded = RoundedRect::with_radii(rect, [4.0, 4.0, -1.0, 0.0]);
+        assert_eq!(rounded.radii, [3.0, 3.0, 0.0, 0.0]);
+        assert_eq!(RoundedRect::new(rect, 1.0).radii, [1.0; 4]);
+        assert_eq!(RoundedRect::from(rect).radii, [0.0; 4]);
+
+        //  Only the rounded corners are cut away
+        assert!(!rounded.contains(Vec2::new(0.2, 0.2)));
+        assert!(rounded.contains(Vec2::new(3.0, 0.1)));
+        assert!(rounded.contains(Vec2::new(0.0, 3.9)));
+        assert!(rounded.contains(Vec2::new(5.9, 3.9)));
+        assert!(!rounded.contains(Vec2::new(5.8, 0.2)));
+
+        assert!((rounded.distance_to(Vec2::new(-1.0, -1.0)) - (32.0f32.sqrt() - 3.0)).abs() < 1e-5);
+        assert!((rounded.distance_to(Vec2::new(3.0, 2.0)) - 2.0).abs() < 1e-5);
+        assert!((rounded.distance_to(Vec2::new(8.0, 5.0)) - 5.0f32.sqrt()).abs() < 1e-5);
+
+        //  Each rounded corner removes the area between its quarter circle and its square, and the approximating edges cut at most a quarter of a pixel further along the arcs
+        let expected = 24.0 - 2.0 * (9.0 - 9.0 * FRAC_PI_4);
+        let area = rounded.to_polygon().unwrap().area();
+        assert!(area <= expected && area > expected - 3.0 * PI * 0.25);
+    }
+}
@@ -0,0 +1,594 @@
+use super::{
+    math::{liang_barsky, Bounds, Vec2},
+    polygon::{combine, BooleanOp, FillRule, MultiPolygon, Polygon},
+};
+
+/**
+ * The miter limit used when none is specified, matching the default of SVG and the HTML canvas
+ */
+pub const DEFAULT_MITER_LIMIT: f32 = 4.0;
+
+/**
+ * The largest distance, in pixels, between a curve and the straight segments approximating it
+ */
+pub(crate) const TOLERANCE: f32 = 0.25;
+
+/**
+ * The most dashes a single line is split into, beyond which the rest of the line is left undrawn
+ */
+pub const MAX_DASHES: usize = 1 << 16;
+
+/**
+ * The shape used to connect two segments meeting at an angle
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /** Extend the outer edges until they meet, falling back to `Bevel` beyond the miter limit */
+    #[default]
+    Miter,
+    /** Connect the outer edges with a circular arc */
+    Round,
+    /** Connect the outer edges with a straight line */
+    Bevel,
+}
+
+/**
+ * The shape drawn at the open ends of a line
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineCap {
+    /** End the line exactly at its endpoints */
+    #[default]
+    Butt,
+    /** Extend the line past its endpoints with a half circle */
+    Round,
+    /** Extend the line past its endpoints by half of its width */
+    Square,
+}
+
+/**
+ * The way lines are outlined by `Engine::draw_line`, `Engine::draw_lines` and `Engine::draw_polygon`
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    pub miter_limit: f32,
+    /** Alternating lengths of drawn and skipped parts of the line, where an empty array draws a solid line */
+    pub dash: Vec<f32>,
+    /** The distance into the dash pattern at which the line starts */
+    pub dash_offset: f32,
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            cap: LineCap::default(),
+            join: LineJoin::default(),
+            miter_limit: DEFAULT_MITER_LIMIT,
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+}
+
+impl StrokeStyle {
+    /**
+     * Initialize a solid `StrokeStyle` of the provided width with the default cap and join
+     */
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn with_miter_limit(mut self, miter_limit: f32) -> Self {
+        self.miter_limit = miter_limit;
+        self
+    }
+
+    pub fn with_dash<T: Into<Vec<f32>>>(mut self, dash: T, offset: f32) -> Self {
+        self.dash = dash.into();
+        self.dash_offset = offset;
+        self
+    }
+
+    /**
+     * Return `true` if the style is a solid line no wider than a pixel, which can be drawn directly
+     */
+    pub fn is_hairline(&self) -> bool {
+        self.width <= 1.0 && self.pattern().is_none()
+    }
+
+    /**
+     * The dash pattern with an even number of entries, if it draws anything other than a solid line
+     */
+    fn pattern(&self) -> Option<Vec<f32>> {
+        let valid = self.dash.iter().all(|d| d.is_finite() && *d >= 0.0);
+
+        if !valid || self.dash.iter().sum::<f32>() <= 0.0 {
+            return None;
+        }
+        let mut pattern = self.dash.clone();
+
+        //  An odd number of entries is repeated to make the pattern even
+        if pattern.len() % 2 == 1 {
+            pattern.extend_from_slice(&self.dash)
+        }
+        Some(pattern)
+    }
+
+    /**
+     * The fillable outline of a line through the provided points in this style, connecting the last point back to the first if `closed` is `true`
+     *
+     * This merges the `pieces` of the line into a single shape, which takes quadratic time in the number of points
+     */
+    pub fn outline<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        &self,
+        iter: T,
+        closed: bool,
+    ) -> MultiPolygon {
+        combine(
+            &self.pieces(iter, closed),
+            FillRule::NonZero,
+            &MultiPolygon::default(),
+            FillRule::NonZero,
+            BooleanOp::Union,
+        )
+    }
+
+    /**
+     * The overlapping pieces of a line through the provided points in this style, which cover the line when filled with `FillRule::NonZero`
+     *
+     * Each segment, join, cap and dash is a separate piece wound counter-clockwise in a y-up space, so they are cheap to build and fill without merging them
+     */
+    pub fn pieces<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        &self,
+        iter: T,
+        closed: bool,
+    ) -> MultiPolygon {
+        self.pieces_within(iter, closed, None)
+    }
+
+    /**
+     * The `pieces` of a line, where only the dashes which cross `visible` are built, so that long dashed lines off the screen cost nothing
+     */
+    pub(crate) fn pieces_within<A: Into<Vec2>, T: IntoIterator<Item = A>>(
+        &self,
+        iter: T,
+        closed: bool,
+        visible: Option<&Bounds>,
+    ) -> MultiPolygon {
+        let mut points = iter.into_iter().map(Into::into).collect::<Vec<Vec2>>();
+        points.dedup();
+
+        let radius = self.width / 2.0;
+        let mut pieces = Vec::new();
+
+        match self.pattern() {
+            Some(pattern) => {
+                if closed && points.len() > 1 {
+                    points.push(points[0])
+                }
+                for (dash, direction) in dashes(&points, &pattern, self.dash_offset, visible) {
+                    pieces.extend(band(&dash, false, radius, self.join, self.miter_limit));
+                    pieces.extend(caps(&dash, direction, radius, self.cap));
+                }
+            }
+            None => {
+                pieces.extend(band(&points, closed, radius, self.join, self.miter_limit));
+
+                if !closed && points.len() > 1 {
+                    let direction = points[1] - points[0];
+                    pieces.extend(caps(&points, direction, radius, self.cap));
+                }
+            }
+        }
+        MultiPolygon::new(pieces)
+    }
+}
+
+/**
+ * Split a polyline into the parts drawn by the dash pattern, each paired with the direction at its start
+ *
+ * Only the parts of the polyline within `visible` are walked, and at most `MAX_DASHES` dashes are made. Distances are measured at `f64` precision, so that the walk keeps moving along long lines
+ */
+fn dashes(
+    points: &[Vec2],
+    pattern: &[f32],
+    offset: f32,
+    visible: Option<&Bounds>,
+) -> Vec<(Vec<Vec2>, Vec2)> {
+    if points.len() < 2 {
+        return Vec::new();
+    }
+    //  The distance along the polyline at which each point lies
+    let mut distances = vec![0.0];
+    for pair in points.windows(2) {
+        distances.push(distances[distances.len() - 1] + pair[0].distance(pair[1]) as f64)
+    }
+    let period = pattern.iter().map(|&d| d as f64).sum::<f64>();
+    let first = -(offset as f64).rem_euclid(period);
+
+    let mut dashes = Vec::new();
+
+    for (lower, upper) in spans(points, &distances, visible) {
+        //  The walk begins at the start of the period containing the span, so the pattern keeps its phase
+        let mut position = first + ((lower - first) / period).floor().max(0.0) * period;
+
+        for (i, &dash) in pattern.iter().cycle().enumerate() {
+            if position > upper || dashes.len() >= MAX_DASHES {
+                break;
+            }
+            let dash = dash as f64;
+
+            //  Zero-length dashes are still drawn as dots by their caps
+            let (start, end) = (position.max(lower), (position + dash).min(upper));
+            if i % 2 == 0 && (end > start || (dash == 0.0 && position >= lower)) {
+                dashes.push(extract(points, &distances, start, end))
+            }
+            position += dash;
+        }
+    }
+    dashes
+}
+
+/**
+ * The ranges of distance along a polyline which lie within `visible`, or its whole length without it
+ */
+fn spans(points: &[Vec2], distances: &[f64], visible: Option<&Bounds>) -> Vec<(f64, f64)> {
+    let length = distances[distances.len() - 1];
+    let Some(visible) = visible else {
+        return vec![(0.0, length)];
+    };
+    let mut spans: Vec<(f64, f64)> = Vec::new();
+
+    for (i, pair) in points.windows(2).enumerate() {
+        let Some((a, b)) = liang_barsky(pair[0], pair[1], visible) else {
+            continue;
+        };
+        let (lower, upper) = (
+            distances[i] + pair[0].distance(a) as f64,
+            distances[i] + pair[0].distance(b) as f64,
+        );
+
+        //  Neighbouring segments which both reach the shared point form a single span
+        match spans.last_mut() {
+            Some(last) if last.1 >= lower => last.1 = last.1.max(upper),
+            _ => spans.push((lower, upper)),
+        }
+    }
+    spans
+}
+
+/**
+ * The part of a polyline between two distances along it, paired with its direction at the start
+ */
+fn extract(points: &[Vec2], distances: &[f64], start: f64, end: f64) -> (Vec<Vec2>, Vec2) {
+    let at = |distance: f64| {
+        let i = distances[1..]
+            .partition_point(|&d| d < distance)
+            .min(distances.len() - 2);
+        let t = (distance - distances[i]) / (distances[i + 1] - distances[i]);
+        (i, points[i] + (points[i + 1] - points[i]) * t as f32)
+    };
+    let ((first, a), (last, b)) = (at(start), at(end));
+
+    let mut dash = vec![a];
+    dash.extend_from_slice(&points[(first + 1)..=last]);
+    dash.push(b);
+    dash.dedup();
+
+    (dash, points[first + 1] - points[first])
+}
+
+/**
+ * The pieces covering the caps at both ends of an open polyline, where `direction` is used for lines with a single point
+ */
+fn caps(points: &[Vec2], direction: Vec2, radius: f32, cap: LineCap) -> Vec<Polygon> {
+    let n = points.len();
+
+    if n == 0 || direction == Vec2::ZERO {
+        return Vec::new();
+    }
+    let (first, last) = (points[0], points[n - 1]);
+    let (start, end) = if n > 1 {
+        (points[1] - first, last - points[n - 2])
+    } else {
+        (direction, direction)
+    };
+
+    [(first, -start), (last, end)]
+        .into_iter()
+        .filter_map(|(p, outward)| {
+            let d = outward / outward.length() * radius;
+            let side = Vec2::new(-d.y, d.x);
+
+            match cap {
+                LineCap::Butt => None,
+                LineCap::Square => piece(vec![p + side, p + side + d, p - side + d, p - side]),
+                LineCap::Round => {
+                    let start = side.y.atan2(side.x);
+                    let steps = arc_segments(radius, std::f32::consts::PI);
+
+                    piece(
+                        (0..=steps)
+                            .map(|k| {
+                                let angle = start - std::f32::consts::PI * k as f32 / steps as f32;
+                                p + Vec2::new(angle.cos(), angle.sin()) * radius
+                            })
+                            .collect(),
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
+/**
+ * The most segments used to approximate a single arc, so that huge radii do not allocate without limit
+ */
+const MAX_ARC_SEGMENTS: usize = 1 << 16;
+
+/**
+ * The number of segments needed to approximate an arc of `angle` radians and the provided radius within the tolerance
+ */
+pub(crate) fn arc_segments(radius: f32, angle: f32) -> usize {
+    //  The step is found at `f64` precision, as it rounds to zero for large radii at `f32`
+    let step = if radius > TOLERANCE {
+        2.0 * (1.0 - TOLERANCE as f64 / radius as f64).acos()
+    } else {
+        std::f64::consts::FRAC_PI_2
+    };
+    ((angle.abs() as f64 / step).ceil() as usize).clamp(1, MAX_ARC_SEGMENTS)
+}
+
+/**
+ * The unit normal on the left of the direction `d` in a y-up space
+ */
+fn normal(d: Vec2) -> Vec2 {
+    Vec2::new(-d.y, d.x) / d.length()
+}
+
+/**
+ * Build a polygon from the provided points, wound counter-clockwise in a y-up space so that overlapping pieces add up under the non-zero rule
+ */
+fn piece(points: Vec<Vec2>) -> Option<Polygon> {
+    let polygon = Polygon::new(points).ok()?;
+
+    if polygon.signed_area() < 0.0 {
+        Some(polygon.reversed())
+    } else if polygon.signed_area() > 0.0 {
+        Some(polygon)
+    } else {
+        None
+    }
+}
+
+/**
+ * The overlapping pieces which together cover every point within `radius` of the polyline, excluding its ends
+ *
+ * A rectangle covers each segment and a join covers the outer side of each corner, so the pieces must be combined with the non-zero rule
+ */
+pub(crate) fn band(
+    points: &[Vec2],
+    closed: bool,
+    radius: f32,
+    join: LineJoin,
+    miter_limit: f32,
+) -> Vec<Polygon> {
+    let mut points = points.to_vec();
+    points.dedup();
+
+    if closed && points.len() > 1 && points[0] == points[points.len() - 1] {
+        points.pop();
+    }
+    let n = points.len();
+    let segments = if closed { n } else { n.saturating_sub(1) };
+
+    let mut pieces = Vec::new();
+
+    for i in 0..segments {
+        let (a, b) = (points[i], points[(i + 1) % n]);
+        let offset = normal(b - a) * radius;
+        pieces.extend(piece(vec![a + offset, b + offset, b - offset, a - offset]));
+    }
+
+    let corners = if closed { 0..n } else { 1..n.saturating_sub(1) };
+    for i in corners {
+        let (prev, v, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        pieces.extend(corner(prev, v, next, radius, join, miter_limit));
+    }
+    pieces
+}
+
+/**
+ * The piece covering the outer side of the corner at `v`
+ */
+fn corner(
+    prev: Vec2,
+    v: Vec2,
+    next: Vec2,
+    radius: f32,
+    join: LineJoin,
+    miter_limit: f32,
+) -> Option<Polygon> {
+    let (d1, d2) = (v - prev, next - v);
+    let turn = d1.cross(d2);
+
+    //  The outer side of a left turn is on the right, and the other way around
+    let side = if turn > 0.0 { -1.0 } else { 1.0 };
+    let (n1, n2) = (normal(d1) * side, normal(d2) * side);
+
+    if turn == 0.0 && d1.dot(d2) > 0.0 {
+        return None;
+    }
+
+    match join {
+        LineJoin::Bevel => piece(vec![v, v + n1 * radius, v + n2 * radius]),
+        LineJoin::Miter => {
+            let bisector = n1 + n2;
+            let cos = bisector.length() / 2.0;
+
+            if cos == 0.0 || 1.0 / cos > miter_limit {
+                return corner(prev, v, next, radius, LineJoin::Bevel, miter_limit);
+            }
+            let miter = v + bisector * (radius / (1.0 + n1.dot(n2)));
+            piece(vec![v, v + n1 * radius, miter, v + n2 * radius])
+        }
+        LineJoin::Round => {
+            let start = n1.y.atan2(n1.x);
+            let mut sweep = n2.y.atan2(n2.x) - start;
+
+            //  Sweep the short way around, which always lies on the outer side
+            if sweep > std::f32::consts::PI {
+                sweep -= std::f32::consts::TAU
+            } else if sweep < -std::f32::consts::PI {
+                sweep += std::f32::consts::TAU
+            }
+            let steps = arc_segments(radius, sweep);
+
+            let mut points = vec![v];
+            points.extend((0..=steps).map(|k| {
+                let angle = start + sweep * k as f32 / steps as f32;
+                v + Vec2::new(angle.cos(), angle.sin()) * radius
+            }));
+            piece(points)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * Return `true` if any piece covers the point, which is how the pieces are filled with `FillRule::NonZero`
+     */
+    fn covers(style: &StrokeStyle, points: &[(f32, f32)], p: (f32, f32)) -> bool {
+        style
+            .pieces(points.iter().copied(), false)
+            .polygons()
+            .iter()
+            .any(|piece| piece.contains(p))
+    }
+
+    #[test]
+    fn caps() {
+        let line = [(0.0, 0.0), (10.0, 0.0)];
+        let butt = StrokeStyle::new(2.0);
+        assert!(covers(&butt, &line, (5.0, 0.9)));
+        assert!(!covers(&butt, &line, (5.0, 1.1)));
+        assert!(!covers(&butt, &line, (-0.5, 0.0)));
+
+        let square = StrokeStyle::new(2.0).with_cap(LineCap::Square);
+        assert!(covers(&square, &line, (-0.9, 0.9)));
+        assert!(covers(&square, &line, (10.9, -0.9)));
+
+        let round = StrokeStyle::new(2.0).with_cap(LineCap::Round);
+        assert!(covers(&round, &line, (-0.8, 0.0)));
+        assert!(!covers(&round, &line, (-0.9, 0.9)));
+    }
+
+    #[test]
+    fn joins() {
+        let corner = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)];
+        let outer = (10.9, -0.9);
+
+        let miter = StrokeStyle::new(2.0);
+        assert!(covers(&miter, &corner, outer));
+
+        let bevel = StrokeStyle::new(2.0).with_join(LineJoin::Bevel);
+        assert!(!covers(&bevel, &corner, outer));
+        assert!(covers(&bevel, &corner, (10.4, -0.4)));
+
+        let round = StrokeStyle::new(2.0).with_join(LineJoin::Round);
+        assert!(!covers(&round, &corner, outer));
+        assert!(covers(&round, &corner, (10.6, -0.6)));
+
+        //  A sharp turn would need a long miter, so it falls back to a bevel past the limit
+        let sharp = [(0.0, 0.0), (10.0, 0.0), (0.0, 1.0)];
+        assert!(covers(
+            &miter.clone().with_miter_limit(100.0),
+            &sharp,
+            (11.5, 0.0)
+        ));
+        assert!(!covers(&miter, &sharp, (11.5, 0.0)));
+    }
+
+    #[test]
+    fn dash_patterns() {
+        let line = [(0.0, 0.0), (10.0, 0.0)];
+        let dashed = StrokeStyle::new(2.0).with_dash(vec![2.0, 2.0], 0.0);
+        assert_eq!(dashed.pieces(line, false).len(), 3);
+        assert!(covers(&dashed, &line, (1.0, 0.0)));
+        assert!(!covers(&dashed, &line, (3.0, 0.0)));
+        assert!(covers(&dashed, &line, (9.0, 0.0)));
+
+        //  The offset shifts the pattern backwards along the line
+        let shifted = dashed.clone().with_dash(vec![2.0, 2.0], 1.0);
+        assert!(!covers(&shifted, &line, (1.5, 0.0)));
+        assert!(covers(&shifted, &line, (3.5, 0.0)));
+
+        //  An odd pattern repeats to alternate, and zero-length dashes leave dots drawn by their caps
+        let odd = StrokeStyle::new(2.0).with_dash(vec![1.0], 0.0);
+        assert!(covers(&odd, &line, (0.5, 0.0)) && !covers(&odd, &line, (1.5, 0.0)));
+        let dots = StrokeStyle::new(2.0)
+            .with_cap(LineCap::Round)
+            .with_dash(vec![0.0, 5.0], 0.0);
+        assert!(covers(&dots, &line, (5.5, 0.0)));
+        assert!(!covers(&dots, &line, (2.5, 0.0)));
+    }
+
+    #[test]
+    fn hairline() {
+        assert!(StrokeStyle::default().is_hairline());
+        assert!(!StrokeStyle::new(2.0).is_hairline());
+        assert!(!StrokeStyle::new(1.0)
+            .with_dash(vec![1.0, 1.0], 0.0)
+            .is_hairline());
+
+        //  Patterns which draw nothing but a solid line are ignored
+        assert!(StrokeStyle::new(1.0)
+            .with_dash(vec![0.0, 0.0], 0.0)
+            .is_hairline());
+        assert!(StrokeStyle::new(1.0)
+            .with_dash(vec![-1.0, 1.0], 0.0)
+            .is_hairline());
+    }
+
+    #[test]
+    fn long_dashed_lines() {
+        let style = StrokeStyle::new(2.0).with_dash(vec![1.0, 1.0], 0.0);
+
+        //  The walk is capped rather than stalling once the position passes the precision of `f32`
+        let pieces = style.pieces([(0.0, 0.0), (1e8, 0.0)], false);
+        assert_eq!(pieces.len(), MAX_DASHES);
+
+        //  Only the dashes crossing the visible area are built, in phase with the rest of the pattern
+        let visible = Bounds::new(Vec2::new(1e6 + 0.5, -5.0), Vec2::new(1e6 + 4.5, 5.0));
+        let dashes = dashes(
+            &[Vec2::ZERO, Vec2::new(1e8, 0.0)],
+            &[1.0, 1.0],
+            0.0,
+            Some(&visible),
+        );
+        assert_eq!(dashes.len(), 3);
+        for ((dash, _), start) in dashes.iter().zip([0.5, 2.0, 4.0]) {
+            assert!((dash[0].x - (1e6 + start)).abs() < 0.1);
+        }
+    }
+}
@@ -16,7 +16,7 @@ pub mod util {
         pub use sdl2::event::{DisplayEvent, WindowEvent as RawWindowEvent};
         use {
             super::{super::error::Error, keyboard::*, mouse::*},
-            sdl2::event::Event,
+            sdl2::{event::Event, rect::Point},
         };
 
         #[derive(Debug)]
@@ -81,6 +81,20 @@ pub mod util {
             },
         }
 
+        impl MouseEvent {
+            /**
+             * The position of the cursor when the event occurred, if the event carries one
+             */
+            pub fn position(&self) -> Option<Point> {
+                match *self {
+                    Self::MouseMotion { x, y, .. }
+                    | Self::MouseButtonDown { x, y, .. }
+                    | Self::MouseButtonUp { x, y, .. } => Some(Point::new(x, y)),
+                    Self::MouseWheel { .. } => None,
+                }
+            }
+        }
+
         #[derive(Debug)]
         pub enum WindowEvent {
             Quit {